@@ -1,12 +1,16 @@
-use iced::widget::{button, column, container, row, scrollable, text, text_input, Space};
-use iced::{alignment, executor, time, window, Application, Command, Element, Length, Settings, Subscription, Theme, Color, Font};
+use auto_launch::AutoLaunchBuilder;
+use iced::widget::{button, canvas, checkbox, column, container, pick_list, row, scrollable, text, text_editor, text_input, Space};
+use iced::{alignment, executor, mouse, time, window, Application, Command, Element, Length, Settings, Subscription, Theme, Color, Font};
+use iced::{event, keyboard, Event, Point, Rectangle, Renderer, Size};
 use serde::{Deserialize, Serialize};
-use std::collections::VecDeque;
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::fs;
-use std::path::PathBuf;
+use std::io::Write;
+use std::path::{Path, PathBuf};
 use std::process::Stdio;
+use std::str::FromStr;
 use std::time::{Duration, Instant};
-use chrono::{DateTime, Local};
+use chrono::{DateTime, Datelike, Local, TimeZone};
 use uuid::Uuid;
 
 //Error Handling
@@ -16,6 +20,7 @@ pub enum AppError {
     Serialization(String),
     Config(String),
     Execution(String),
+    Cancelled,
 }
 
 impl std::fmt::Display for AppError {
@@ -25,6 +30,7 @@ impl std::fmt::Display for AppError {
             AppError::Serialization(msg) => write!(f, "Serialization error: {}", msg),
             AppError::Config(msg) => write!(f, "Configuration error: {}", msg),
             AppError::Execution(msg) => write!(f, "Execution error: {}", msg),
+            AppError::Cancelled => write!(f, "cancelled by user"),
         }
     }
 }
@@ -41,21 +47,234 @@ impl From<serde_json::Error> for AppError {
     }
 }
 
+impl From<zip::result::ZipError> for AppError {
+    fn from(err: zip::result::ZipError) -> Self {
+        AppError::Io(err.to_string())
+    }
+}
+
 //Data Structures
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct Task {
     id: Uuid,
     title: String,
     command: String,
-    interval_seconds: u64,
+    #[serde(flatten)]
+    schedule: Schedule,
     is_active: bool,
     last_run: Option<DateTime<Local>>,
     next_run: Option<DateTime<Local>>,
     created_at: DateTime<Local>,
     success_count: u32,
     failure_count: u32,
+    // Not stored in tasks.json; rebuilt from the newest log once both have loaded
     #[serde(skip)]
     last_output: String,
+    #[serde(default)]
+    max_output_bytes: Option<usize>,
+    // Relative paths resolve against working_dir when it is set; see output_path()
+    #[serde(default)]
+    output_file: Option<PathBuf>,
+    #[serde(default)]
+    output_file_append: bool,
+    #[serde(default)]
+    paused_until: Option<DateTime<Local>>,
+    #[serde(default)]
+    skip_command_prefix: bool,
+    #[serde(default)]
+    watch_path: Option<PathBuf>,
+    #[serde(default)]
+    failure_alert_after_seconds: Option<u64>,
+    #[serde(default)]
+    failing_since: Option<DateTime<Local>>,
+    #[serde(default)]
+    run_on_startup: bool,
+    #[serde(default)]
+    startup_order: Option<u32>,
+    #[serde(default)]
+    on_success_command: Option<String>,
+    #[serde(default)]
+    timeout_seconds: Option<u64>,
+    #[serde(default)]
+    timeout_grace_seconds: Option<u64>,
+    #[serde(default)]
+    run_elevated: bool,
+    #[serde(default)]
+    max_retries: u32,
+    #[serde(default)]
+    retry_delay_seconds: u64,
+    #[serde(default)]
+    desktop_override: AlertOverride,
+    #[serde(default)]
+    sound_override: AlertOverride,
+    #[serde(default)]
+    verbose_logging: bool,
+    // First capture group (or whole match) is parsed as a number from each run's output
+    #[serde(default)]
+    metric_regex: Option<String>,
+    #[serde(default)]
+    login_shell: bool,
+    #[serde(default)]
+    working_dir: Option<PathBuf>,
+    // Values may reference secrets as {{secret:NAME}}
+    #[serde(default)]
+    env_vars: Vec<(String, String)>,
+    // Activating the task runs it right away instead of waiting a full interval
+    #[serde(default = "default_run_on_activate")]
+    run_on_activate: bool,
+    // Scheduled runs wait until this task has succeeded since our last run
+    #[serde(default)]
+    depends_on: Option<Uuid>,
+    // Deactivated after its first successful run instead of being rescheduled
+    #[serde(default)]
+    one_shot: bool,
+    // When a one-shot task finished; cleared when it is started again
+    #[serde(default)]
+    completed_at: Option<DateTime<Local>>,
+    // In-app notifications for finished runs; off keeps a noisy task quiet
+    #[serde(default = "default_true")]
+    notify_on_success: bool,
+    #[serde(default = "default_true")]
+    notify_on_failure: bool,
+    // POSTed a JSON summary (WebhookPayload) after every run
+    #[serde(default)]
+    webhook_url: Option<String>,
+    // When a run last succeeded; tasks that depend on this one wait for it
+    #[serde(default)]
+    last_success: Option<DateTime<Local>>,
+}
+
+fn default_run_on_activate() -> bool {
+    true
+}
+
+fn default_true() -> bool {
+    true
+}
+
+// Stored as the `interval_seconds` and `cron` keys tasks.json has always had;
+// a cron task keeps interval_seconds at 0, like crontab imports
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(from = "ScheduleFields", into = "ScheduleFields")]
+enum Schedule {
+    Interval(u64),
+    Cron(String),
+}
+
+#[derive(Serialize, Deserialize)]
+struct ScheduleFields {
+    #[serde(default)]
+    interval_seconds: u64,
+    #[serde(default)]
+    cron: Option<String>,
+}
+
+impl From<ScheduleFields> for Schedule {
+    fn from(fields: ScheduleFields) -> Self {
+        match fields.cron {
+            Some(expr) => Schedule::Cron(expr),
+            None => Schedule::Interval(fields.interval_seconds),
+        }
+    }
+}
+
+impl From<Schedule> for ScheduleFields {
+    fn from(schedule: Schedule) -> Self {
+        match schedule {
+            Schedule::Interval(interval_seconds) => ScheduleFields { interval_seconds, cron: None },
+            Schedule::Cron(expr) => ScheduleFields { interval_seconds: 0, cron: Some(expr) },
+        }
+    }
+}
+
+impl Task {
+    fn is_paused_at(&self, now: DateTime<Local>) -> bool {
+        self.paused_until.is_some_and(|until| now < until)
+    }
+    
+    // Failure alerts are held back until the task has been failing
+    // continuously for `failure_alert_after_seconds`
+    fn failure_alert_due(&self, now: DateTime<Local>) -> bool {
+        match (self.failing_since, self.failure_alert_after_seconds) {
+            (Some(since), Some(secs)) => now >= add_interval(since, secs),
+            _ => true,
+        }
+    }
+    
+    // Where stdout is written: a relative output file lands in the task's working
+    // directory like the command's own relative paths, not in the app's
+    fn output_path(&self) -> Option<PathBuf> {
+        let path = self.output_file.as_ref()?;
+        Some(match &self.working_dir {
+            Some(dir) if path.is_relative() => dir.join(path),
+            _ => path.clone(),
+        })
+    }
+}
+
+impl Schedule {
+    fn next_after(&self, from: DateTime<Local>) -> Option<DateTime<Local>> {
+        match self {
+            Schedule::Interval(seconds) => Some(add_interval(from, *seconds)),
+            Schedule::Cron(expr) => parse_cron(expr).ok()?.after(&from).next(),
+        }
+    }
+    
+    fn describe(&self) -> String {
+        match self {
+            Schedule::Interval(seconds) => format!("Every {}", TaskWithMe::format_duration(*seconds)),
+            Schedule::Cron(expr) => format!("Cron: {}", expr),
+        }
+    }
+}
+
+// Intervals are capped at MAX_INTERVAL_SECONDS, and a result past chrono's
+// range falls back to the latest representable time instead of panicking
+fn add_interval(from: DateTime<Local>, seconds: u64) -> DateTime<Local> {
+    let capped = seconds.min(MAX_INTERVAL_SECONDS) as i64;
+    from.checked_add_signed(chrono::Duration::seconds(capped))
+        .unwrap_or_else(|| Local.from_utc_datetime(&(chrono::NaiveDateTime::MAX - chrono::Duration::days(1))))
+}
+
+fn next_run_for(task: &Task, interval_override: Option<u64>, from: DateTime<Local>) -> Option<DateTime<Local>> {
+    match interval_override {
+        Some(seconds) => Schedule::Interval(seconds).next_after(from),
+        None => task.schedule.next_after(from),
+    }
+}
+
+// Schedules step from the previous scheduled point instead of from now, so
+// execution time and polling delay don't accumulate into drift. Whole intervals
+// missed while the app was asleep are skipped rather than run back to back; a
+// cron slot that a long run overlapped is still due, but only the one
+fn next_scheduled_run(
+    task: &Task,
+    interval_override: Option<u64>,
+    previous: Option<DateTime<Local>>,
+    now: DateTime<Local>,
+) -> Option<DateTime<Local>> {
+    // None for cron schedules
+    let interval = match (interval_override, &task.schedule) {
+        (Some(seconds), _) => Some(seconds.min(MAX_INTERVAL_SECONDS)),
+        (None, Schedule::Interval(seconds)) => Some((*seconds).min(MAX_INTERVAL_SECONDS)),
+        (None, Schedule::Cron(_)) => None,
+    };
+    
+    match (previous, interval) {
+        // A manual run ahead of schedule leaves the schedule alone
+        (Some(previous), Some(1..) | None) if previous > now => Some(previous),
+        (Some(previous), None) => match task.schedule.next_after(previous) {
+            Some(slot) if slot > now => Some(slot),
+            Some(slot) if task.schedule.next_after(slot).is_some_and(|after| after > now) => Some(slot),
+            _ => next_run_for(task, None, now),
+        },
+        (Some(previous), Some(interval)) if interval > 0 => {
+            let behind = (now - previous).num_seconds().max(0) as u64;
+            let next = add_interval(previous, (behind / interval + 1).saturating_mul(interval));
+            if next > now { Some(next) } else { next_run_for(task, interval_override, now) }
+        }
+        _ => next_run_for(task, interval_override, now),
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -64,8 +283,150 @@ struct ExecutionLog {
     task_id: Uuid,
     timestamp: DateTime<Local>,
     success: bool,
+    // Only for logs without stdout/stderr: ones written before the streams were kept
+    // apart, and runs with just a status message. Use output_text() to read it
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     output: String,
     duration_ms: u64,
+    // 0 for the first try, n for the n-th retry
+    #[serde(default)]
+    attempt: u32,
+    // Only recorded for tasks with verbose logging
+    #[serde(default)]
+    metadata: Option<RunMetadata>,
+    #[serde(default)]
+    metric: Option<f64>,
+    #[serde(default)]
+    stdout: String,
+    #[serde(default)]
+    stderr: String,
+    // None when the process was killed by a signal, cancelled or timed out
+    #[serde(default)]
+    exit_code: Option<i32>,
+}
+
+impl ExecutionLog {
+    // Both streams combined, derived rather than stored so logs.jsonl holds each line once
+    fn output_text(&self) -> std::borrow::Cow<'_, str> {
+        if self.stdout.is_empty() && self.stderr.is_empty() {
+            std::borrow::Cow::Borrowed(&self.output)
+        } else {
+            std::borrow::Cow::Owned(combine_output(&self.stdout, &self.stderr))
+        }
+    }
+}
+
+// Body of the per-task webhook POST
+#[derive(Debug, Clone, Serialize)]
+struct WebhookPayload {
+    task_id: Uuid,
+    title: String,
+    timestamp: DateTime<Local>,
+    success: bool,
+    duration_ms: u64,
+    exit_code: Option<i32>,
+    attempt: u32,
+    output: String,
+}
+
+impl WebhookPayload {
+    fn from_log(log: &ExecutionLog, title: &str) -> Self {
+        WebhookPayload {
+            task_id: log.task_id,
+            title: title.to_string(),
+            timestamp: log.timestamp,
+            success: log.success,
+            duration_ms: log.duration_ms,
+            exit_code: log.exit_code,
+            attempt: log.attempt,
+            output: truncate_output(log.output_text().into_owned(), Some(WEBHOOK_OUTPUT_BYTES)),
+        }
+    }
+}
+
+// What exactly ran, for reproducing a run later. Environment values are never stored
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+struct RunMetadata {
+    command: String,
+    shell: String,
+    working_dir: String,
+    env_keys: Vec<String>,
+}
+
+impl RunMetadata {
+    fn describe(&self) -> String {
+        let env = self.env_keys.iter()
+            .map(|key| format!("{}=****", key))
+            .collect::<Vec<_>>()
+            .join(" ");
+        format!(
+            "Command: {}\nShell: {}\nWorking dir: {}\nEnvironment: {}",
+            self.command, self.shell, self.working_dir, env
+        )
+    }
+}
+
+// Configuration and lifecycle changes, kept for the Audit screen
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct AppEvent {
+    id: Uuid,
+    timestamp: DateTime<Local>,
+    kind: AppEventKind,
+    task_id: Option<Uuid>,
+    detail: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+enum AppEventKind {
+    TaskCreated,
+    TaskUpdated,
+    TaskDeleted,
+    TaskActivated,
+    TaskDeactivated,
+    TaskPaused,
+    TaskResumed,
+    TasksImported,
+    SchedulesRecomputed,
+    SettingsChanged,
+    SecretChanged,
+    StatsReset,
+}
+
+impl AppEventKind {
+    const ALL: [AppEventKind; 12] = [
+        AppEventKind::TaskCreated,
+        AppEventKind::TaskUpdated,
+        AppEventKind::TaskDeleted,
+        AppEventKind::TaskActivated,
+        AppEventKind::TaskDeactivated,
+        AppEventKind::TaskPaused,
+        AppEventKind::TaskResumed,
+        AppEventKind::TasksImported,
+        AppEventKind::SchedulesRecomputed,
+        AppEventKind::SettingsChanged,
+        AppEventKind::SecretChanged,
+        AppEventKind::StatsReset,
+    ];
+}
+
+impl std::fmt::Display for AppEventKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let label = match self {
+            AppEventKind::TaskCreated => "Task created",
+            AppEventKind::TaskUpdated => "Task updated",
+            AppEventKind::TaskDeleted => "Task deleted",
+            AppEventKind::TaskActivated => "Task activated",
+            AppEventKind::TaskDeactivated => "Task deactivated",
+            AppEventKind::TaskPaused => "Task paused",
+            AppEventKind::TaskResumed => "Task resumed",
+            AppEventKind::TasksImported => "Tasks imported",
+            AppEventKind::SchedulesRecomputed => "Schedules recomputed",
+            AppEventKind::SettingsChanged => "Settings changed",
+            AppEventKind::SecretChanged => "Secret changed",
+            AppEventKind::StatsReset => "Stats reset",
+        };
+        write!(f, "{}", label)
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -74,23 +435,545 @@ struct Config {
     max_logs: usize,
     theme: AppTheme,
     log_to_file: bool,
+    #[serde(default)]
+    success_rate_precision: u8,
+    // Multiplies every size in the UI, between MIN_UI_SCALE and MAX_UI_SCALE
+    #[serde(default = "default_ui_scale")]
+    ui_scale: f32,
+    // None means unlimited; configs predating the setting get the default cap
+    #[serde(default = "default_max_output_bytes")]
+    max_output_bytes: Option<usize>,
+    #[serde(default)]
+    start_at_login: bool,
+    #[serde(default)]
+    start_minimized: bool,
+    #[serde(default)]
+    confirm_before_run: bool,
+    #[serde(default = "default_persist_interval")]
+    persist_interval: u64,
+    #[serde(default)]
+    command_prefix: Option<String>,
+    // None runs commands through the platform default (sh -c / cmd /C)
+    #[serde(default)]
+    shell: Option<String>,
+    // Empty means the usual flag for `shell` (see default_shell_args)
+    #[serde(default)]
+    shell_args: Vec<String>,
+    // Where tasks, logs and history live (config.json itself stays in the
+    // default directory); read at startup, so changes apply after a restart
+    #[serde(default)]
+    data_dir: Option<PathBuf>,
+    // Names only; the values live in the OS keyring
+    #[serde(default)]
+    secret_names: Vec<String>,
+    #[serde(default = "default_task_page_size")]
+    task_page_size: usize,
+    #[serde(default)]
+    update_check_url: Option<String>,
+    #[serde(default)]
+    desktop_notifications: bool,
+    #[serde(default)]
+    alert_sound: bool,
+    // Keep a deleted task's logs instead of purging them
+    #[serde(default)]
+    keep_orphaned_logs: bool,
+    // Suspend scheduled runs while the machine runs on battery
+    #[serde(default)]
+    pause_on_battery: bool,
+    // Keep the activity feed in notifications.json across restarts
+    #[serde(default)]
+    persist_notifications: bool,
+    #[serde(default = "default_max_concurrent_tasks")]
+    max_concurrent_tasks: usize,
+    #[serde(default)]
+    missed_run_policy: MissedRunPolicy,
+    #[serde(default)]
+    duplicate_titles: DuplicateTitlePolicy,
+    #[serde(default)]
+    log_level: LogLevel,
+    // Set once the first-launch panel has been dismissed or used
+    #[serde(default)]
+    onboarded: bool,
+}
+
+fn default_persist_interval() -> u64 {
+    10
+}
+
+fn default_ui_scale() -> f32 {
+    1.0
+}
+
+const MIN_UI_SCALE: f32 = 0.8;
+const MAX_UI_SCALE: f32 = 2.0;
+const UI_SCALE_STEP: f32 = 0.1;
+
+fn default_task_page_size() -> usize {
+    25
+}
+
+fn default_max_concurrent_tasks() -> usize {
+    4
+}
+
+fn default_max_output_bytes() -> Option<usize> {
+    Some(8 * 1024)
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 enum AppTheme {
     Light,
     Dark,
+    // Follows the OS color scheme
+    System,
 }
 
 #[derive(Debug, Clone)]
+struct TestSchedule {
+    interval_seconds: u64,
+    expires_at: DateTime<Local>,
+}
+
+const MAX_INTERVAL_SECONDS: u64 = 10 * 365 * 24 * 60 * 60;
+const TEST_SCHEDULE_INTERVAL: u64 = 10;
+const TEST_SCHEDULE_DURATION: i64 = 600;
+const FAILURE_WINDOW_HOURS: i64 = 24;
+const COMPARE_RECENT_RUNS: usize = 5;
+const DETAIL_RECENT_LOGS: usize = 10;
+// Runs without a timeout are highlighted once they pass this
+const LONG_RUN_WARNING_SECONDS: u64 = 300;
+// Table rows and full outputs included in a task's Markdown report
+const REPORT_RECENT_RUNS: usize = 20;
+const REPORT_OUTPUTS: usize = 3;
+// Output sent with each webhook call; the full output stays in the logs
+const WEBHOOK_OUTPUT_BYTES: usize = 4096;
+const DEFAULT_TIMEOUT_GRACE_SECONDS: u64 = 5;
+const MAX_APP_EVENTS: usize = 1000;
+const PREVIEW_LINES: usize = 3;
+const DEFAULT_RETRY_DELAY_SECONDS: u64 = 10;
+const LOG_PAGE_SIZES: [usize; 4] = [25, 50, 100, 200];
+const LOG_COMPACT_SLACK: usize = 100;
+const SCHEDULER_POLL: Duration = Duration::from_secs(1);
+// Quiet time after the last keystroke before the task search re-filters
+const SEARCH_DEBOUNCE: Duration = Duration::from_millis(200);
+// How often the OS color scheme is re-read while the System theme is selected
+const SYSTEM_THEME_POLL: Duration = Duration::from_secs(5);
+// How often the power source is re-read while "Pause on battery" is on
+const POWER_SOURCE_POLL: Duration = Duration::from_secs(30);
+// Redraw rate while a running task's output is on screen
+const LIVE_OUTPUT_REFRESH: Duration = Duration::from_millis(250);
+// Only the tail of a run's output is kept for the live view; the log gets all of it
+const LIVE_OUTPUT_MAX_BYTES: usize = 32 * 1024;
+const MAX_CATCH_UP_RUNS: u32 = 10;
+const MAX_ACTIVITY_ENTRIES: usize = 500;
+const SHELL_PRESETS: [&str; 6] = ["sh", "bash", "zsh", "fish", "pwsh", "cmd"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PauseUntil {
+    OneHour,
+    Tomorrow,
+    NextMonday,
+}
+
+impl PauseUntil {
+    const ALL: [PauseUntil; 3] = [PauseUntil::OneHour, PauseUntil::Tomorrow, PauseUntil::NextMonday];
+    
+    fn resolve(&self, now: DateTime<Local>) -> DateTime<Local> {
+        let today = now.date_naive();
+        match self {
+            PauseUntil::OneHour => now + chrono::Duration::hours(1),
+            PauseUntil::Tomorrow => local_midnight(today + chrono::Duration::days(1)),
+            PauseUntil::NextMonday => {
+                let days = 7 - today.weekday().num_days_from_monday() as i64;
+                local_midnight(today + chrono::Duration::days(days))
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for PauseUntil {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let label = match self {
+            PauseUntil::OneHour => "1 hour",
+            PauseUntil::Tomorrow => "Tomorrow",
+            PauseUntil::NextMonday => "Next Monday",
+        };
+        write!(f, "{}", label)
+    }
+}
+
+// What to do at startup with runs that fell due while the app was closed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+enum MissedRunPolicy {
+    Skip,
+    #[default]
+    RunOnce,
+    RunAll,
+}
+
+impl MissedRunPolicy {
+    const ALL: [MissedRunPolicy; 3] = [MissedRunPolicy::Skip, MissedRunPolicy::RunOnce, MissedRunPolicy::RunAll];
+}
+
+impl std::fmt::Display for MissedRunPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let label = match self {
+            MissedRunPolicy::Skip => "Skip",
+            MissedRunPolicy::RunOnce => "Run once",
+            MissedRunPolicy::RunAll => "Run each missed",
+        };
+        write!(f, "{}", label)
+    }
+}
+
+// What saving a task whose title another task already has does
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+enum DuplicateTitlePolicy {
+    // Asks for confirmation first
+    #[default]
+    Warn,
+    Block,
+    Allow,
+}
+
+impl DuplicateTitlePolicy {
+    const ALL: [DuplicateTitlePolicy; 3] = [DuplicateTitlePolicy::Warn, DuplicateTitlePolicy::Block, DuplicateTitlePolicy::Allow];
+}
+
+impl std::fmt::Display for DuplicateTitlePolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let label = match self {
+            DuplicateTitlePolicy::Warn => "Ask first",
+            DuplicateTitlePolicy::Block => "Don't allow",
+            DuplicateTitlePolicy::Allow => "Allow",
+        };
+        write!(f, "{}", label)
+    }
+}
+
+// Diagnostic output on stderr; RUST_LOG, when set, takes precedence
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+enum LogLevel {
+    Off,
+    Error,
+    #[default]
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl LogLevel {
+    const ALL: [LogLevel; 6] = [
+        LogLevel::Off,
+        LogLevel::Error,
+        LogLevel::Warn,
+        LogLevel::Info,
+        LogLevel::Debug,
+        LogLevel::Trace,
+    ];
+    
+    fn filter(self) -> log::LevelFilter {
+        match self {
+            LogLevel::Off => log::LevelFilter::Off,
+            LogLevel::Error => log::LevelFilter::Error,
+            LogLevel::Warn => log::LevelFilter::Warn,
+            LogLevel::Info => log::LevelFilter::Info,
+            LogLevel::Debug => log::LevelFilter::Debug,
+            LogLevel::Trace => log::LevelFilter::Trace,
+        }
+    }
+}
+
+impl std::fmt::Display for LogLevel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let label = match self {
+            LogLevel::Off => "Off",
+            LogLevel::Error => "Error",
+            LogLevel::Warn => "Warning",
+            LogLevel::Info => "Info",
+            LogLevel::Debug => "Debug",
+            LogLevel::Trace => "Trace",
+        };
+        write!(f, "{}", label)
+    }
+}
+
+fn apply_log_level(level: LogLevel) {
+    if std::env::var_os("RUST_LOG").is_none() {
+        log::set_max_level(level.filter());
+    }
+}
+
+// Counts scheduled runs from `next_run` up to `now`, stopping at MAX_CATCH_UP_RUNS
+fn missed_runs(task: &Task, now: DateTime<Local>) -> u32 {
+    let mut count = 0;
+    let mut due = task.next_run;
+    while let Some(at) = due {
+        if at > now || count >= MAX_CATCH_UP_RUNS {
+            break;
+        }
+        count += 1;
+        due = next_run_for(task, None, at);
+    }
+    count
+}
+
+// Entry in the "Depends on" pick list; `id: None` clears the dependency
+#[derive(Debug, Clone, PartialEq)]
+struct TaskChoice {
+    id: Option<Uuid>,
+    title: String,
+}
+
+impl std::fmt::Display for TaskChoice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.title)
+    }
+}
+
+// True when making `id` depend on `depends_on` would close a loop
+fn creates_dependency_cycle(tasks: &[Task], id: Uuid, depends_on: Uuid) -> bool {
+    let mut current = Some(depends_on);
+    let mut seen = HashSet::new();
+    while let Some(next) = current {
+        if next == id {
+            return true;
+        }
+        if !seen.insert(next) {
+            return false;
+        }
+        current = tasks.iter().find(|t| t.id == next).and_then(|t| t.depends_on);
+    }
+    false
+}
+
+// Per-task override of a global alert setting
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+enum AlertOverride {
+    #[default]
+    Inherit,
+    On,
+    Off,
+}
+
+impl AlertOverride {
+    const ALL: [AlertOverride; 3] = [AlertOverride::Inherit, AlertOverride::On, AlertOverride::Off];
+    
+    fn resolve(&self, global: bool) -> bool {
+        match self {
+            AlertOverride::Inherit => global,
+            AlertOverride::On => true,
+            AlertOverride::Off => false,
+        }
+    }
+}
+
+impl std::fmt::Display for AlertOverride {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let label = match self {
+            AlertOverride::Inherit => "Global",
+            AlertOverride::On => "On",
+            AlertOverride::Off => "Off",
+        };
+        write!(f, "{}", label)
+    }
+}
+
+// Manual is the saved list order, which the ↑/↓ buttons edit
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+enum TaskSort {
+    #[default]
+    Manual,
+    Title,
+    NextRun,
+    SuccessRate,
+    LastRun,
+    Created,
+}
+
+impl TaskSort {
+    const ALL: [TaskSort; 6] = [
+        TaskSort::Manual,
+        TaskSort::Title,
+        TaskSort::NextRun,
+        TaskSort::SuccessRate,
+        TaskSort::LastRun,
+        TaskSort::Created,
+    ];
+}
+
+impl std::fmt::Display for TaskSort {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let label = match self {
+            TaskSort::Manual => "Manual order",
+            TaskSort::Title => "Title",
+            TaskSort::NextRun => "Next run",
+            TaskSort::SuccessRate => "Success rate",
+            TaskSort::LastRun => "Last run",
+            TaskSort::Created => "Created",
+        };
+        write!(f, "{}", label)
+    }
+}
+
+// Orders two optional keys in `descending` order or not; a missing key (never
+// run, not scheduled) always goes last
+fn compare_optional<T: PartialOrd>(a: Option<T>, b: Option<T>, descending: bool) -> std::cmp::Ordering {
+    use std::cmp::Ordering;
+    match (a, b) {
+        (Some(a), Some(b)) => {
+            let ordering = a.partial_cmp(&b).unwrap_or(Ordering::Equal);
+            if descending { ordering.reverse() } else { ordering }
+        }
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LogStatusFilter {
+    All,
+    Success,
+    Failed,
+}
+
+impl LogStatusFilter {
+    const ALL: [LogStatusFilter; 3] = [LogStatusFilter::All, LogStatusFilter::Success, LogStatusFilter::Failed];
+    
+    fn matches(&self, log: &ExecutionLog) -> bool {
+        match self {
+            LogStatusFilter::All => true,
+            LogStatusFilter::Success => log.success,
+            LogStatusFilter::Failed => !log.success,
+        }
+    }
+}
+
+impl std::fmt::Display for LogStatusFilter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let label = match self {
+            LogStatusFilter::All => "All statuses",
+            LogStatusFilter::Success => "Success only",
+            LogStatusFilter::Failed => "Failed only",
+        };
+        write!(f, "{}", label)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LogRange {
+    All,
+    LastDay,
+    LastWeek,
+    LastMonth,
+}
+
+impl LogRange {
+    const ALL: [LogRange; 4] = [LogRange::All, LogRange::LastDay, LogRange::LastWeek, LogRange::LastMonth];
+    
+    fn since(&self, now: DateTime<Local>) -> Option<DateTime<Local>> {
+        match self {
+            LogRange::All => None,
+            LogRange::LastDay => Some(now - chrono::Duration::days(1)),
+            LogRange::LastWeek => Some(now - chrono::Duration::days(7)),
+            LogRange::LastMonth => Some(now - chrono::Duration::days(30)),
+        }
+    }
+}
+
+impl std::fmt::Display for LogRange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let label = match self {
+            LogRange::All => "All time",
+            LogRange::LastDay => "Last 24 hours",
+            LogRange::LastWeek => "Last 7 days",
+            LogRange::LastMonth => "Last 30 days",
+        };
+        write!(f, "{}", label)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum PendingAction {
+    RunTask(Uuid),
+    DeleteTask(Uuid),
+    DeleteTasks(Vec<Uuid>),
+    ResetTaskStats(Uuid),
+    // Title of the existing task the form's title clashes with
+    SaveDuplicateTitle(String),
+}
+
+impl PendingAction {
+    fn is_destructive(&self) -> bool {
+        matches!(
+            self,
+            PendingAction::DeleteTask(_) | PendingAction::DeleteTasks(_) | PendingAction::ResetTaskStats(_)
+        )
+    }
+}
+
+// Kept in templates.json; the built-in ones are written there on first run
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 struct TaskTemplate {
-    name: &'static str,
-    description: &'static str,
-    command: &'static str,
+    name: String,
+    description: String,
+    command: String,
     interval: u64,
 }
 
-#[derive(Debug, Clone)]
+impl TaskTemplate {
+    // Created paused: template commands delete and archive files
+    fn to_task(&self) -> Task {
+        Task {
+            id: Uuid::new_v4(),
+            title: self.name.clone(),
+            command: self.command.clone(),
+            schedule: Schedule::Interval(self.interval),
+            is_active: false,
+            last_run: None,
+            next_run: None,
+            created_at: Local::now(),
+            success_count: 0,
+            failure_count: 0,
+            last_output: String::new(),
+            max_output_bytes: None,
+            output_file: None,
+            output_file_append: false,
+            paused_until: None,
+            skip_command_prefix: false,
+            watch_path: None,
+            failure_alert_after_seconds: None,
+            failing_since: None,
+            run_on_startup: false,
+            startup_order: None,
+            on_success_command: None,
+            timeout_seconds: None,
+            timeout_grace_seconds: None,
+            run_elevated: false,
+            max_retries: 0,
+            retry_delay_seconds: DEFAULT_RETRY_DELAY_SECONDS,
+            desktop_override: AlertOverride::Inherit,
+            sound_override: AlertOverride::Inherit,
+            verbose_logging: false,
+            metric_regex: None,
+            login_shell: false,
+            working_dir: None,
+            env_vars: Vec::new(),
+            run_on_activate: true,
+            depends_on: None,
+            one_shot: false,
+            completed_at: None,
+            notify_on_success: true,
+            notify_on_failure: true,
+            webhook_url: None,
+            last_success: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 struct Notification {
     id: Uuid,
     message: String,
@@ -98,7 +981,7 @@ struct Notification {
     timestamp: DateTime<Local>,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 enum NotificationLevel {
     Info,
     Success,
@@ -116,51 +999,261 @@ enum Message {
     TitleInput(String),
     CommandInput(String),
     IntervalInput(String),
+    CronModeToggled(bool),
+    CronInput(String),
+    MaxOutputInput(String),
+    OutputFileInput(String),
+    OutputAppendToggled(bool),
+    SkipPrefixToggled(bool),
+    WatchPathInput(String),
+    FailureAlertInput(String),
+    RunOnStartupToggled(bool),
+    RunOnActivateToggled(bool),
+    OneShotToggled(bool),
+    NotifyOnSuccessToggled(bool),
+    NotifyOnFailureToggled(bool),
+    SetTaskNotifications(Uuid, bool, bool),
+    DependsOnSelected(TaskChoice),
+    PreviewCommand,
+    ClosePreview,
+    StartupOrderInput(String),
+    OnSuccessInput(String),
+    TimeoutInput(String),
+    GraceInput(String),
+    RunElevatedToggled(bool),
+    RetriesInput(String),
+    RetryDelayInput(String),
+    DesktopOverrideChanged(AlertOverride),
+    SoundOverrideChanged(AlertOverride),
+    VerboseLoggingToggled(bool),
+    MetricRegexInput(String),
+    LoginShellToggled(bool),
+    WorkingDirInput(String),
+    AddEnvVar,
+    RemoveEnvVar(usize),
+    EnvKeyInput(usize, String),
+    EnvValueInput(usize, String),
+    DesktopNotificationsChanged(bool),
+    LogToFileChanged(bool),
+    KeepOrphanedLogsChanged(bool),
+    PauseOnBatteryChanged(bool),
+    PersistNotificationsChanged(bool),
+    ClearActivity,
+    AlertSoundChanged(bool),
     CreateTask,
     DeleteTask(Uuid),
     ToggleTask(Uuid),
     ExecuteTask(Uuid),
+    RetryTask(Uuid),
+    CancelTask(Uuid),
+    RunAllActive,
+    RunAlertSent(Result<(), AppError>),
+    WebhookSent(String, Result<(), AppError>),
+    WebhookInput(String),
+    RunTask(Uuid),
+    RequestDeleteTask(Uuid),
+    // Multi-select
+    ToggleTaskSelected(Uuid, bool),
+    SelectAllFiltered,
+    ClearSelection,
+    RequestDeleteSelected,
+    DeleteTasks(Vec<Uuid>),
+    TasksDeleted(Vec<Uuid>, Result<(), AppError>),
+    SetSelectedActive(bool),
+    RequestResetTaskStats(Uuid),
+    ResetTaskStats(Uuid),
+    ConfirmPendingAction,
+    CancelPendingAction,
+    ToggleTestSchedule(Uuid),
+    PauseTaskUntil(Uuid, PauseUntil),
+    ResumeTask(Uuid),
+    ToggleCrontabImport,
+    CrontabEdited(text_editor::Action),
+    ImportCrontab,
     
     // Async Results
-    TasksLoaded(Result<Vec<Task>, AppError>),
-    LogsLoaded(Result<Vec<ExecutionLog>, AppError>),
+    TasksLoaded(Result<Loaded<Vec<Task>>, AppError>),
+    LogsLoaded(Result<Loaded<Vec<ExecutionLog>>, AppError>),
+    EventsLoaded(Result<Loaded<Vec<AppEvent>>, AppError>),
+    NotificationsLoaded(Result<Loaded<Vec<Notification>>, AppError>),
     TaskSaved(Result<(), AppError>),
+    // The save started by the task form; only this one re-enables Create/Update
+    TaskFormSaved(Result<(), AppError>),
     TaskExecuted(Uuid, Result<ExecutionResult, AppError>),
-    TaskDeleted(Result<(), AppError>),
-    ConfigLoaded(Result<Config, AppError>),
+    TaskDeleted(Uuid, Result<(), AppError>),
+    ConfigLoaded(Result<Loaded<Config>, AppError>),
     ConfigSaved(Result<(), AppError>),
+    StateFlushed(FlushedState, Result<(), AppError>),
+    ShutdownFlushed(Result<(), AppError>),
+    LoginItemUpdated(Result<(), AppError>),
     
     // UI Actions
     SelectTemplate(usize),
+    SaveFormAsTemplate,
+    DeleteTemplate(usize),
+    TemplatesLoaded(Result<Loaded<Vec<TaskTemplate>>, AppError>),
+    TemplatesSaved(Result<(), AppError>),
     SearchInput(String),
+    ApplySearch(u64),
     FilterChanged(TaskFilter),
+    TaskSortChanged(TaskSort),
+    ToggleSortDirection,
+    ShowTasksFiltered(TaskFilter),
+    CompareTask(Uuid),
+    TaskPageChanged(usize),
+    EditTask(Uuid),
+    CloneTask(Uuid),
+    MoveTaskUp(Uuid),
+    MoveTaskDown(Uuid),
+    EnterFocus(Uuid),
+    ExitFocus,
+    FocusNewTask,
+    CancelEdit,
     ViewTaskLogs(Uuid),
+    SaveLogOutput(Uuid),
+    LogOutputSaved(Result<Option<PathBuf>, AppError>),
+    LogStatusFilterChanged(LogStatusFilter),
+    LogRangeChanged(LogRange),
+    LogSearchInput(String),
+    LogPageChanged(usize),
+    ToggleLogDay(chrono::NaiveDate),
+    LogPageSizeChanged(usize),
+    ExportLogBundle(Option<Uuid>),
+    ExportLogsCsv(Option<Uuid>),
+    ExportTaskReport(Uuid),
+    LogBundleExported(Result<Option<PathBuf>, AppError>),
+    AuditSearchInput(String),
+    AuditKindChanged(Option<AppEventKind>),
+    AuditRangeChanged(LogRange),
+    ExportAuditEvents { csv: bool },
+    AuditExported(Result<Option<PathBuf>, AppError>),
+    ExportTasks,
+    TasksExported(Result<Option<PathBuf>, AppError>),
+    ImportTasks,
+    TasksFileRead(Result<Option<Vec<Task>>, AppError>),
     CloseNotification(Uuid),
     ClearNotifications,
     
     // Settings
     ThemeChanged(AppTheme),
+    ThemeSaved(Result<(), AppError>),
+    CheckSystemTheme,
+    SystemThemeDetected(Option<bool>),
+    OnboardingChecked(bool),
+    SeedTemplates,
+    DismissOnboarding,
+    OnboardingSaved(Result<(), AppError>),
+    CheckPowerSource,
+    PowerSourceDetected(Option<bool>),
     RefreshIntervalChanged(String),
     MaxLogsChanged(String),
+    PersistIntervalChanged(String),
+    TaskPageSizeChanged(String),
+    MaxConcurrentChanged(String),
+    MissedRunPolicyChanged(MissedRunPolicy),
+    DuplicateTitlePolicyChanged(DuplicateTitlePolicy),
+    LogLevelChanged(LogLevel),
+    GlobalMaxOutputChanged(String),
+    UpdateUrlChanged(String),
+    CheckForUpdates,
+    UpdateChecked(Result<Option<String>, AppError>),
+    CommandPrefixChanged(String),
+    DataDirChanged(String),
+    ShellChanged(String),
+    ShellArgsChanged(String),
+    ShellPresetSelected(&'static str),
+    SecretNameInput(String),
+    SecretValueInput(String),
+    SaveSecret,
+    DeleteSecret(String),
+    SecretSaved(Result<String, AppError>),
+    SecretDeleted(Result<String, AppError>),
+    SecretIndexSaved(Result<(), AppError>),
+    SuccessRatePrecisionChanged(u8),
+    UiScaleChanged(f32),
+    StartAtLoginChanged(bool),
+    StartMinimizedChanged(bool),
+    ConfirmBeforeRunChanged(bool),
+    ToggleTrustedSession,
+    CalendarRangeChanged(CalendarRange),
     SaveSettings,
+    DiscardSettings,
+    RecomputeSchedules,
     
     // Background
     Tick,
+    RefreshLiveOutput,
+    WatchTriggered(Uuid),
+    WatchFailed(Uuid, String),
+    CloseRequested,
     CheckScheduledTasks,
+    RefreshStats,
 }
 
 #[derive(Debug, Clone)]
 struct ExecutionResult {
     success: bool,
     output: String,
+    stdout: String,
+    stderr: String,
+    exit_code: Option<i32>,
     duration_ms: u64,
+    metadata: Option<RunMetadata>,
+    metric: Option<f64>,
 }
 
-#[derive(Debug, Clone, PartialEq)]
-enum TaskFilter {
-    All,
-    Active,
-    Inactive,
+impl ExecutionResult {
+    // A run that ended in an error rather than an exit status; the error goes to stderr
+    fn failed(error: &AppError, duration_ms: u64) -> Self {
+        ExecutionResult {
+            success: false,
+            output: error.to_string(),
+            stdout: String::new(),
+            stderr: error.to_string(),
+            exit_code: None,
+            duration_ms,
+            metadata: None,
+            metric: None,
+        }
+    }
+}
+
+// How worrying a run's elapsed time is, for coloring the running label
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum RunLength {
+    Normal,
+    // Past LONG_RUN_WARNING_SECONDS, or 80% of the task's timeout
+    Long,
+    // Past the timeout, i.e. in the kill grace period
+    OverTimeout,
+}
+
+impl RunLength {
+    fn classify(elapsed_seconds: u64, timeout: Option<u64>) -> Self {
+        match timeout {
+            Some(limit) if elapsed_seconds >= limit => RunLength::OverTimeout,
+            Some(limit) if elapsed_seconds * 5 >= limit * 4 => RunLength::Long,
+            None if elapsed_seconds >= LONG_RUN_WARNING_SECONDS => RunLength::Long,
+            _ => RunLength::Normal,
+        }
+    }
+    
+    fn color(self) -> Color {
+        match self {
+            RunLength::Normal => Color::from_rgb(0.2, 0.6, 0.9),
+            RunLength::Long => Color::from_rgb(0.95, 0.7, 0.2),
+            RunLength::OverTimeout => Color::from_rgb(0.9, 0.3, 0.3),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum TaskFilter {
+    All,
+    Active,
+    Inactive,
+    Running,
+    Failing,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -168,35 +1261,190 @@ enum Screen {
     Overview,
     Tasks,
     Logs(Option<Uuid>),
+    Failures,
+    Calendar,
+    Compare(Uuid, Uuid),
+    TaskDetail(Uuid),
+    Audit,
     Settings,
 }
 
+// Overview numbers, recomputed every refresh_interval rather than on each redraw
+#[derive(Debug, Clone, Copy, Default)]
+struct DashboardStats {
+    total: usize,
+    active: usize,
+    success_rate: f32,
+}
+
+// Per-task run durations and latest outcome over the retained logs, kept up to date as logs change
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+struct DurationStats {
+    total_ms: u64,
+    runs: u64,
+    last_ms: u64,
+    last_failed: bool,
+}
+
+// What one flush wrote, so a failed write can be marked dirty again
+#[derive(Debug, Clone, Default)]
+struct FlushedState {
+    tasks: Vec<Uuid>,
+    logs: bool,
+    events: bool,
+    activity: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum CalendarRange {
+    Day,
+    Week,
+}
+
 //Application State
 struct TaskWithMe {
     // Core data
     tasks: Vec<Task>,
-    logs: Vec<ExecutionLog>,
+    logs: VecDeque<ExecutionLog>,
+    events: VecDeque<AppEvent>,
     config: Config,
     
     // UI state
     screen: Screen,
+    calendar_range: CalendarRange,
     title_input: String,
     command_input: String,
     interval_input: String,
+    cron_mode_input: bool,
+    cron_input: String,
+    max_output_input: String,
+    output_file_input: String,
+    output_append_input: bool,
+    skip_prefix_input: bool,
+    watch_path_input: String,
+    failure_alert_input: String,
+    run_on_startup_input: bool,
+    run_on_activate_input: bool,
+    one_shot_input: bool,
+    notify_on_success_input: bool,
+    notify_on_failure_input: bool,
+    depends_on_input: Option<Uuid>,
+    command_preview: Option<CommandPreview>,
+    startup_order_input: String,
+    on_success_input: String,
+    webhook_input: String,
+    timeout_input: String,
+    grace_input: String,
+    run_elevated_input: bool,
+    retries_input: String,
+    retry_delay_input: String,
+    desktop_override_input: AlertOverride,
+    sound_override_input: AlertOverride,
+    verbose_logging_input: bool,
+    metric_regex_input: String,
+    login_shell_input: bool,
+    working_dir_input: String,
+    env_var_inputs: Vec<(String, String)>,
     search_query: String,
+    // What the search box shows; copied to search_query once typing pauses
+    search_input: String,
+    search_seq: u64,
     filter: TaskFilter,
+    task_sort: TaskSort,
+    selected_tasks: HashSet<Uuid>,
+    sort_descending: bool,
+    task_page: usize,
+    log_status_filter: LogStatusFilter,
+    log_range: LogRange,
+    log_search: String,
+    log_page: usize,
+    log_page_size: usize,
+    // Day groups folded shut in the Logs screen
+    collapsed_log_days: HashSet<chrono::NaiveDate>,
+    audit_search: String,
+    audit_kind: Option<AppEventKind>,
+    audit_range: LogRange,
+    compare_selection: Option<Uuid>,
+    editing_task: Option<Uuid>,
+    // Bumped for every tasks.json write; see write_tasks
+    tasks_generation: u64,
+    // Set by confirming the duplicate-title prompt, consumed by the next CreateTask
+    duplicate_title_confirmed: bool,
     
     // Runtime state
+    // Toasts: the newest few, dismissable
     notifications: VecDeque<Notification>,
+    // Every notification, newest last, capped at MAX_ACTIVITY_ENTRIES
+    activity: VecDeque<Notification>,
+    activity_dirty: bool,
     running_tasks: Vec<Uuid>,
+    run_started: HashMap<Uuid, Instant>,
+    // Retry number of the current or pending attempt, for tasks that are retrying
+    retry_attempts: HashMap<Uuid, u32>,
+    // Sending true asks the running command to terminate
+    cancel_signals: HashMap<Uuid, tokio::sync::watch::Sender<bool>>,
+    // Stdout of running tasks as it arrives
+    live_output: HashMap<Uuid, LiveOutput>,
+    // Waiting for a free slot under max_concurrent_tasks
+    run_queue: VecDeque<Uuid>,
+    // File operations in flight; their buttons stay disabled until the result arrives
+    saving_task: bool,
+    deleting_tasks: HashSet<Uuid>,
+    saving_settings: bool,
+    focus_task: Option<Uuid>,
+    stats: DashboardStats,
+    // Whether the OS is in dark mode; None until detected or when it can't be
+    system_dark: Option<bool>,
+    // None until read, and on platforms where the power source is unknown
+    on_battery: Option<bool>,
+    show_onboarding: bool,
+    duration_stats: HashMap<Uuid, DurationStats>,
+    dirty_tasks: HashSet<Uuid>,
+    // Set when logs.jsonl has to be rewritten (deletes, trims); new runs are
+    // only appended through pending_logs
+    logs_dirty: bool,
+    pending_logs: Vec<ExecutionLog>,
+    log_lines_on_disk: usize,
+    events_dirty: bool,
+    last_flush: Instant,
+    test_schedules: HashMap<Uuid, TestSchedule>,
+    pending_action: Option<PendingAction>,
+    trusted_session: bool,
     last_check: Instant,
     
-    // Settings inputs
+    // Startup runs; `startup_queue` is None until both tasks and config have loaded
+    config_loaded: bool,
+    tasks_loaded: bool,
+    startup_queue: Option<VecDeque<Uuid>>,
+    // Runs missed while closed are handled once, on the first check after loading
+    missed_runs_handled: bool,
+    // Extra back-to-back runs still owed under MissedRunPolicy::RunAll
+    catch_up_runs: HashMap<Uuid, u32>,
+    startup_current: Option<Uuid>,
+    
+    // Settings inputs; `settings_draft` is the editing buffer, `config` the saved baseline
+    settings_draft: Config,
     refresh_input: String,
     max_logs_input: String,
+    persist_interval_input: String,
+    task_page_size_input: String,
+    max_concurrent_input: String,
+    global_max_output_input: String,
+    update_url_input: String,
+    update_status: Option<String>,
+    command_prefix_input: String,
+    shell_input: String,
+    shell_args_input: String,
+    data_dir_input: String,
+    secret_name_input: String,
+    secret_value_input: String,
     
     // Templates
     templates: Vec<TaskTemplate>,
+    
+    // Crontab import
+    show_crontab_import: bool,
+    crontab_content: text_editor::Content,
 }
 
 impl Default for Config {
@@ -206,6 +1454,30 @@ impl Default for Config {
             max_logs: 500,
             theme: AppTheme::Dark,
             log_to_file: true,
+            success_rate_precision: 0,
+            ui_scale: default_ui_scale(),
+            max_output_bytes: default_max_output_bytes(),
+            start_at_login: false,
+            start_minimized: false,
+            confirm_before_run: false,
+            persist_interval: default_persist_interval(),
+            command_prefix: None,
+            shell: None,
+            shell_args: Vec::new(),
+            data_dir: None,
+            secret_names: Vec::new(),
+            task_page_size: default_task_page_size(),
+            update_check_url: None,
+            desktop_notifications: false,
+            alert_sound: false,
+            keep_orphaned_logs: false,
+            pause_on_battery: false,
+            persist_notifications: false,
+            max_concurrent_tasks: default_max_concurrent_tasks(),
+            missed_run_policy: MissedRunPolicy::default(),
+            duplicate_titles: DuplicateTitlePolicy::default(),
+            log_level: LogLevel::default(),
+            onboarded: false,
         }
     }
 }
@@ -214,66 +1486,162 @@ impl Default for TaskWithMe {
     fn default() -> Self {
         Self {
             tasks: Vec::new(),
-            logs: Vec::new(),
+            logs: VecDeque::new(),
+            events: VecDeque::new(),
             config: Config::default(),
             screen: Screen::Overview,
+            calendar_range: CalendarRange::Week,
             title_input: String::new(),
             command_input: String::new(),
             interval_input: String::new(),
+            cron_mode_input: false,
+            cron_input: String::new(),
+            max_output_input: String::new(),
+            output_file_input: String::new(),
+            output_append_input: false,
+            skip_prefix_input: false,
+            watch_path_input: String::new(),
+            failure_alert_input: String::new(),
+            run_on_startup_input: false,
+            run_on_activate_input: true,
+            one_shot_input: false,
+            notify_on_success_input: true,
+            notify_on_failure_input: true,
+            depends_on_input: None,
+            command_preview: None,
+            startup_order_input: String::new(),
+            on_success_input: String::new(),
+            webhook_input: String::new(),
+            timeout_input: String::new(),
+            grace_input: String::new(),
+            run_elevated_input: false,
+            retries_input: String::new(),
+            retry_delay_input: String::new(),
+            desktop_override_input: AlertOverride::Inherit,
+            sound_override_input: AlertOverride::Inherit,
+            verbose_logging_input: false,
+            metric_regex_input: String::new(),
+            login_shell_input: false,
+            working_dir_input: String::new(),
+            env_var_inputs: Vec::new(),
             search_query: String::new(),
+            search_input: String::new(),
+            search_seq: 0,
             filter: TaskFilter::All,
+            task_sort: TaskSort::Manual,
+            selected_tasks: HashSet::new(),
+            sort_descending: false,
+            task_page: 0,
+            log_status_filter: LogStatusFilter::All,
+            log_range: LogRange::All,
+            log_search: String::new(),
+            log_page: 0,
+            log_page_size: 50,
+            collapsed_log_days: HashSet::new(),
+            audit_search: String::new(),
+            audit_kind: None,
+            audit_range: LogRange::All,
+            compare_selection: None,
+            editing_task: None,
+            tasks_generation: 0,
+            duplicate_title_confirmed: false,
             notifications: VecDeque::new(),
+            activity: VecDeque::new(),
+            activity_dirty: false,
             running_tasks: Vec::new(),
+            run_started: HashMap::new(),
+            retry_attempts: HashMap::new(),
+            cancel_signals: HashMap::new(),
+            live_output: HashMap::new(),
+            run_queue: VecDeque::new(),
+            saving_task: false,
+            deleting_tasks: HashSet::new(),
+            saving_settings: false,
+            focus_task: None,
+            stats: DashboardStats::default(),
+            system_dark: None,
+            on_battery: None,
+            show_onboarding: false,
+            duration_stats: HashMap::new(),
+            dirty_tasks: HashSet::new(),
+            logs_dirty: false,
+            pending_logs: Vec::new(),
+            log_lines_on_disk: 0,
+            events_dirty: false,
+            last_flush: Instant::now(),
+            test_schedules: HashMap::new(),
+            pending_action: None,
+            trusted_session: false,
             last_check: Instant::now(),
+            config_loaded: false,
+            tasks_loaded: false,
+            startup_queue: None,
+            missed_runs_handled: false,
+            catch_up_runs: HashMap::new(),
+            startup_current: None,
+            settings_draft: Config::default(),
             refresh_input: "5".to_string(),
             max_logs_input: "500".to_string(),
+            persist_interval_input: default_persist_interval().to_string(),
+            task_page_size_input: default_task_page_size().to_string(),
+            max_concurrent_input: default_max_concurrent_tasks().to_string(),
+            global_max_output_input: default_max_output_bytes().map(|n| n.to_string()).unwrap_or_default(),
+            update_url_input: String::new(),
+            update_status: None,
+            command_prefix_input: String::new(),
+            shell_input: String::new(),
+            shell_args_input: String::new(),
+            data_dir_input: String::new(),
+            secret_name_input: String::new(),
+            secret_value_input: String::new(),
             templates: get_templates(),
+            show_crontab_import: false,
+            crontab_content: text_editor::Content::new(),
         }
     }
 }
 
+// The defaults seeded into templates.json
 fn get_templates() -> Vec<TaskTemplate> {
+    let template = |name: &str, description: &str, command: &str, interval| TaskTemplate {
+        name: name.to_string(),
+        description: description.to_string(),
+        command: command.to_string(),
+        interval,
+    };
     vec![
-        TaskTemplate {
-            name: "System Cleanup",
-            description: "Remove temp files",
-            command: if cfg!(target_os = "macos") || cfg!(target_os = "linux") {
+        template(
+            "System Cleanup",
+            "Remove temp files",
+            if cfg!(target_os = "macos") || cfg!(target_os = "linux") {
                 "find /tmp -name '*.tmp' -mtime +7 -delete"
             } else {
                 "del /q /s %TEMP%\\*.tmp"
             },
-            interval: 3600,
-        },
-        TaskTemplate {
-            name: "Backup Documents",
-            description: "Create backup archive",
-            command: if cfg!(target_os = "macos") || cfg!(target_os = "linux") {
+            3600,
+        ),
+        template(
+            "Backup Documents",
+            "Create backup archive",
+            if cfg!(target_os = "macos") || cfg!(target_os = "linux") {
                 "tar -czf ~/backups/docs-$(date +%Y%m%d).tar.gz ~/Documents"
             } else {
                 "echo Backup complete"
             },
-            interval: 86400,
-        },
-        TaskTemplate {
-            name: "Check Disk Space",
-            description: "Monitor disk usage",
-            command: if cfg!(target_os = "windows") {
-                 "wmic logicaldisk get size,freespace" 
-                } else 
-                { "df -h" 
-            },
-            interval: 300,
-        },
-        TaskTemplate {
-            name: "Health Ping",
-            description: "Test network connectivity",
-            command: if cfg!(target_os = "windows") {
-                "ping -n 4 8.8.8.8" 
-            } else { 
-                "ping -c 4 8.8.8.8" 
-            },
-            interval: 60,
-        },
+            86400,
+        ),
+        template(
+            "Check Disk Space",
+            "Monitor disk usage",
+            if cfg!(target_os = "windows") { "wmic logicaldisk get size,freespace" } else { "df -h" },
+            300,
+        ),
+        template(
+            "Health Ping",
+            "Test network connectivity",
+            if cfg!(target_os = "windows") { "ping -n 4 8.8.8.8" } else { "ping -c 4 8.8.8.8" },
+            60,
+        ),
     ]
 }
 
@@ -286,6 +1654,12 @@ impl TaskWithMe {
             timestamp: Local::now(),
         };
         
+        self.activity.push_back(notification.clone());
+        if self.activity.len() > MAX_ACTIVITY_ENTRIES {
+            self.activity.pop_front();
+        }
+        self.activity_dirty |= self.config.persist_notifications;
+        
         self.notifications.push_back(notification);
         if self.notifications.len() > 10 {
             self.notifications.pop_front();
@@ -293,23 +1667,667 @@ impl TaskWithMe {
     }
     
     fn filtered_tasks(&self) -> Vec<&Task> {
-        self.tasks.iter()
+        let query = self.search_query.to_lowercase();
+        let mut tasks: Vec<&Task> = self.tasks.iter()
             .filter(|task| {
-                let matches_search = self.search_query.is_empty() ||
-                    task.title.to_lowercase().contains(&self.search_query.to_lowercase()) ||
-                    task.command.to_lowercase().contains(&self.search_query.to_lowercase());
+                let matches_search = query.is_empty() ||
+                    task.title.to_lowercase().contains(&query) ||
+                    task.command.to_lowercase().contains(&query);
                 
                 let matches_filter = match self.filter {
                     TaskFilter::All => true,
                     TaskFilter::Active => task.is_active,
                     TaskFilter::Inactive => !task.is_active,
+                    TaskFilter::Running => self.running_tasks.contains(&task.id),
+                    TaskFilter::Failing => self.is_failing(task.id),
                 };
                 
                 matches_search && matches_filter
             })
+            .collect();
+        
+        // Stable, so ties keep the manual order
+        let descending = self.sort_descending;
+        match self.task_sort {
+            TaskSort::Manual => {}
+            TaskSort::Title => tasks.sort_by(|a, b| {
+                compare_optional(Some(a.title.to_lowercase()), Some(b.title.to_lowercase()), descending)
+            }),
+            TaskSort::NextRun => tasks.sort_by(|a, b| {
+                compare_optional(a.next_run.filter(|_| a.is_active), b.next_run.filter(|_| b.is_active), descending)
+            }),
+            TaskSort::SuccessRate => {
+                let rate = |t: &Task| (t.success_count + t.failure_count > 0).then(|| self.success_rate(t));
+                tasks.sort_by(|a, b| compare_optional(rate(a), rate(b), descending));
+            }
+            TaskSort::LastRun => tasks.sort_by(|a, b| compare_optional(a.last_run, b.last_run, descending)),
+            TaskSort::Created => tasks.sort_by(|a, b| compare_optional(Some(a.created_at), Some(b.created_at), descending)),
+        }
+        tasks
+    }
+    
+    // Swaps with the neighbouring task in the visible (filtered) list and writes
+    // the whole list, since the file order is the display order
+    fn move_task(&mut self, id: Uuid, up: bool) -> Command<Message> {
+        let visible: Vec<Uuid> = self.filtered_tasks().iter().map(|t| t.id).collect();
+        let Some(pos) = visible.iter().position(|&v| v == id) else {
+            return Command::none();
+        };
+        let neighbour = if up { pos.checked_sub(1) } else { Some(pos + 1) };
+        let Some(&other) = neighbour.and_then(|n| visible.get(n)) else {
+            return Command::none();
+        };
+        
+        let a = self.tasks.iter().position(|t| t.id == id);
+        let b = self.tasks.iter().position(|t| t.id == other);
+        if let (Some(a), Some(b)) = (a, b) {
+            self.tasks.swap(a, b);
+        }
+        Command::perform(self.save_all_tasks(), Message::TaskSaved)
+    }
+    
+    // Startup tasks with an order run one at a time in that order, waiting for
+    // each to finish; the rest run in parallel afterwards
+    fn start_startup_runs(&mut self) -> Command<Message> {
+        if !self.config_loaded || !self.tasks_loaded || self.startup_queue.is_some() {
+            return Command::none();
+        }
+        
+        let now = Local::now();
+        let mut ordered: Vec<&Task> = self.tasks.iter()
+            .filter(|t| t.run_on_startup && t.startup_order.is_some() && !t.is_paused_at(now))
+            .collect();
+        ordered.sort_by_key(|t| t.startup_order);
+        self.startup_queue = Some(ordered.iter().map(|t| t.id).collect());
+        
+        self.advance_startup_runs()
+    }
+    
+    fn advance_startup_runs(&mut self) -> Command<Message> {
+        if let Some(id) = self.startup_queue.as_mut().and_then(|q| q.pop_front()) {
+            self.startup_current = Some(id);
+            return self.update(Message::ExecuteTask(id));
+        }
+        self.startup_current = None;
+        
+        let now = Local::now();
+        let unordered: Vec<Uuid> = self.tasks.iter()
+            .filter(|t| t.run_on_startup && t.startup_order.is_none() && !t.is_paused_at(now))
+            .map(|t| t.id)
+            .collect();
+        Command::batch(unordered.into_iter().map(|id| self.update(Message::ExecuteTask(id))).collect::<Vec<_>>())
+    }
+    
+    // Skip moves overdue tasks to their next future slot; RunOnce leaves them due
+    // for the normal check; RunAll also queues the remaining runs back to back
+    fn handle_missed_runs(&mut self, now: DateTime<Local>) {
+        let policy = self.config.missed_run_policy;
+        let mut caught_up = vec![];
+        
+        for task in self.tasks.iter_mut() {
+            if !task.is_active || task.is_paused_at(now) {
+                continue;
+            }
+            let missed = missed_runs(task, now);
+            if missed == 0 {
+                continue;
+            }
+            
+            match policy {
+                MissedRunPolicy::Skip => {
+                    task.next_run = next_scheduled_run(task, None, task.next_run, now);
+                    self.dirty_tasks.insert(task.id);
+                }
+                MissedRunPolicy::RunOnce => {}
+                MissedRunPolicy::RunAll if missed > 1 => {
+                    self.catch_up_runs.insert(task.id, missed - 1);
+                }
+                MissedRunPolicy::RunAll => {}
+            }
+            let more = if missed >= MAX_CATCH_UP_RUNS { "+" } else { "" };
+            caught_up.push(format!("{} ({}{} missed)", task.title, missed, more));
+        }
+        
+        if caught_up.is_empty() {
+            return;
+        }
+        let action = match policy {
+            MissedRunPolicy::Skip => "skipped",
+            MissedRunPolicy::RunOnce => "running once each",
+            MissedRunPolicy::RunAll => "running each missed run",
+        };
+        self.notify(
+            format!("Runs missed while closed, {}: {}", action, caught_up.join(", ")),
+            NotificationLevel::Info
+        );
+    }
+    
+    // Tasks and logs load independently, so this runs after either arrives.
+    // Tasks saved before last_success existed get it from their newest successful log
+    fn restore_from_logs(&mut self) {
+        for task in self.tasks.iter_mut() {
+            if task.last_output.is_empty() {
+                if let Some(log) = self.logs.iter().rev().find(|l| l.task_id == task.id) {
+                    task.last_output = log.output_text().into_owned();
+                }
+            }
+            if task.last_success.is_none() {
+                task.last_success = self.logs.iter()
+                    .rev()
+                    .find(|l| l.task_id == task.id && l.success)
+                    .map(|l| l.timestamp);
+            }
+        }
+    }
+    
+    // The upstream task holding back scheduled runs: it must have succeeded after
+    // this task last ran, and not be failing since. A deleted upstream no longer blocks
+    fn blocking_dependency(&self, task: &Task) -> Option<&Task> {
+        let upstream = self.tasks.iter().find(|t| Some(t.id) == task.depends_on)?;
+        let satisfied = upstream.failing_since.is_none()
+            && upstream.last_success.is_some_and(|success| task.last_run.is_none_or(|last| success > last));
+        (!satisfied).then_some(upstream)
+    }
+    
+    fn filtered_logs(&self, task_id: Option<Uuid>) -> Vec<&ExecutionLog> {
+        let since = self.log_range.since(Local::now());
+        let query = self.log_search.to_lowercase();
+        self.logs.iter()
+            .filter(|l| task_id.is_none_or(|id| l.task_id == id))
+            .filter(|l| self.log_status_filter.matches(l))
+            .filter(|l| since.is_none_or(|since| l.timestamp >= since))
+            .filter(|l| query.is_empty()
+                || l.output_text().to_lowercase().contains(&query)
+                || self.tasks.iter()
+                    .find(|t| t.id == l.task_id)
+                    .is_some_and(|t| t.title.to_lowercase().contains(&query)))
+            .collect()
+    }
+    
+    // A task is failing while its most recent run failed
+    fn is_failing(&self, task_id: Uuid) -> bool {
+        self.duration_stats.get(&task_id).is_some_and(|stats| stats.last_failed)
+    }
+    
+    fn reset_settings_draft(&mut self) {
+        self.settings_draft = self.config.clone();
+        self.refresh_input = self.config.refresh_interval.to_string();
+        self.max_logs_input = self.config.max_logs.to_string();
+        self.persist_interval_input = self.config.persist_interval.to_string();
+        self.task_page_size_input = self.config.task_page_size.to_string();
+        self.max_concurrent_input = self.config.max_concurrent_tasks.to_string();
+        self.global_max_output_input = self.config.max_output_bytes.map(|n| n.to_string()).unwrap_or_default();
+        self.update_url_input = self.config.update_check_url.clone().unwrap_or_default();
+        self.command_prefix_input = self.config.command_prefix.clone().unwrap_or_default();
+        self.shell_input = self.config.shell.clone().unwrap_or_default();
+        self.shell_args_input = self.config.shell_args.join(" ");
+        self.data_dir_input = self.config.data_dir.as_ref().map(|d| d.display().to_string()).unwrap_or_default();
+    }
+    
+    // The config that Save would write: the draft plus the parsed text inputs
+    fn draft_config(&self) -> Config {
+        let mut config = self.settings_draft.clone();
+        if let Ok(interval) = self.refresh_input.parse::<u64>() {
+            config.refresh_interval = interval.max(1);
+        }
+        if let Ok(max_logs) = self.max_logs_input.parse::<usize>() {
+            config.max_logs = max_logs.max(10);
+        }
+        if let Ok(persist_interval) = self.persist_interval_input.parse::<u64>() {
+            config.persist_interval = persist_interval.max(1);
+        }
+        if let Ok(page_size) = self.task_page_size_input.parse::<usize>() {
+            config.task_page_size = page_size.max(1);
+        }
+        if let Ok(limit) = self.max_concurrent_input.parse::<usize>() {
+            config.max_concurrent_tasks = limit.max(1);
+        }
+        match self.global_max_output_input.trim() {
+            "" => config.max_output_bytes = None,
+            s => if let Ok(limit) = s.parse::<usize>() {
+                config.max_output_bytes = Some(limit.max(1));
+            },
+        }
+        let prefix = self.command_prefix_input.trim();
+        config.command_prefix = (!prefix.is_empty()).then(|| prefix.to_string());
+        let shell = self.shell_input.trim();
+        config.shell = (!shell.is_empty()).then(|| shell.to_string());
+        config.shell_args = self.shell_args_input.split_whitespace().map(String::from).collect();
+        let data_dir = self.data_dir_input.trim();
+        config.data_dir = (!data_dir.is_empty()).then(|| PathBuf::from(data_dir));
+        let update_url = self.update_url_input.trim();
+        config.update_check_url = (!update_url.is_empty()).then(|| update_url.to_string());
+        config
+    }
+    
+    fn unsaved_settings(&self) -> Vec<&'static str> {
+        let draft = self.draft_config();
+        let saved = &self.config;
+        let mut changed = vec![];
+        
+        if draft.refresh_interval != saved.refresh_interval { changed.push("Refresh Interval"); }
+        if draft.max_logs != saved.max_logs { changed.push("Max Log Entries"); }
+        if draft.persist_interval != saved.persist_interval { changed.push("Save to Disk Interval"); }
+        if draft.task_page_size != saved.task_page_size { changed.push("Tasks per Page"); }
+        if draft.max_concurrent_tasks != saved.max_concurrent_tasks { changed.push("Max Concurrent Tasks"); }
+        if draft.missed_run_policy != saved.missed_run_policy { changed.push("Missed Runs"); }
+        if draft.duplicate_titles != saved.duplicate_titles { changed.push("Duplicate Titles"); }
+        if draft.log_level != saved.log_level { changed.push("Log Level"); }
+        if draft.max_output_bytes != saved.max_output_bytes { changed.push("Max Output Size"); }
+        if draft.command_prefix != saved.command_prefix { changed.push("Command Prefix"); }
+        if draft.shell != saved.shell || draft.shell_args != saved.shell_args { changed.push("Shell"); }
+        if draft.data_dir != saved.data_dir { changed.push("Data Directory"); }
+        if draft.update_check_url != saved.update_check_url { changed.push("Update URL"); }
+        if draft.start_at_login != saved.start_at_login { changed.push("Start at Login"); }
+        if draft.start_minimized != saved.start_minimized { changed.push("Start Minimized"); }
+        if draft.confirm_before_run != saved.confirm_before_run { changed.push("Confirm Before Run"); }
+        if draft.log_to_file != saved.log_to_file { changed.push("Log to File"); }
+        if draft.keep_orphaned_logs != saved.keep_orphaned_logs { changed.push("Keep Deleted Tasks' Logs"); }
+        if draft.pause_on_battery != saved.pause_on_battery { changed.push("Pause on Battery"); }
+        if draft.persist_notifications != saved.persist_notifications { changed.push("Keep Activity History"); }
+        if draft.desktop_notifications != saved.desktop_notifications { changed.push("Desktop Notifications"); }
+        if draft.alert_sound != saved.alert_sound { changed.push("Alert Sound"); }
+        if draft.success_rate_precision != saved.success_rate_precision { changed.push("Success Rate Precision"); }
+        if draft.ui_scale != saved.ui_scale { changed.push("UI Scale"); }
+        
+        changed
+    }
+    
+    fn start_execution(&mut self, id: Uuid) -> Command<Message> {
+        let task_info = self.tasks.iter().find(|t| t.id == id).map(|task| {
+            (task.clone(), task.title.clone())
+        });
+        
+        if let Some((task_clone, task_title)) = task_info {
+            self.running_tasks.push(id);
+            self.run_started.insert(id, Instant::now());
+            let (cancel_tx, cancel_rx) = tokio::sync::watch::channel(false);
+            self.cancel_signals.insert(id, cancel_tx);
+            let live = LiveOutput::default();
+            self.live_output.insert(id, live.clone());
+            self.notify(format!("Executing '{}'...", task_title), NotificationLevel::Info);
+            
+            return Command::perform(
+                execute_task(task_clone, self.config.clone(), cancel_rx, live),
+                move |result| Message::TaskExecuted(id, result)
+            );
+        }
+        Command::none()
+    }
+    
+    fn at_concurrency_limit(&self) -> bool {
+        self.running_tasks.len() >= self.config.max_concurrent_tasks.max(1)
+    }
+    
+    // Starts queued tasks while there are free slots
+    fn drain_run_queue(&mut self) -> Command<Message> {
+        let mut commands = vec![];
+        while !self.at_concurrency_limit() {
+            let Some(id) = self.run_queue.pop_front() else {
+                break;
+            };
+            if self.running_tasks.contains(&id) {
+                continue;
+            }
+            commands.push(self.start_execution(id));
+        }
+        Command::batch(commands)
+    }
+    
+    fn warn_if_restored<T>(&mut self, loaded: &Loaded<T>) {
+        let Some(moved) = &loaded.moved_aside else {
+            return;
+        };
+        match &loaded.restored_from {
+            Some(backup) => self.notify(
+                format!(
+                    "A data file was unreadable and was moved to {}; restored the last good copy from {}",
+                    moved.display(),
+                    backup.display()
+                ),
+                NotificationLevel::Warning
+            ),
+            None => self.notify(
+                format!("A data file was unreadable and was moved to {}; starting fresh", moved.display()),
+                NotificationLevel::Error
+            ),
+        }
+    }
+    
+    fn clear_task_form(&mut self) {
+        self.title_input.clear();
+        self.command_input.clear();
+        self.interval_input.clear();
+        self.cron_mode_input = false;
+        self.cron_input.clear();
+        self.max_output_input.clear();
+        self.output_file_input.clear();
+        self.output_append_input = false;
+        self.skip_prefix_input = false;
+        self.watch_path_input.clear();
+        self.failure_alert_input.clear();
+        self.run_on_startup_input = false;
+        self.run_on_activate_input = true;
+        self.one_shot_input = false;
+        self.notify_on_success_input = true;
+        self.notify_on_failure_input = true;
+        self.depends_on_input = None;
+        self.command_preview = None;
+        self.startup_order_input.clear();
+        self.on_success_input.clear();
+        self.webhook_input.clear();
+        self.timeout_input.clear();
+        self.grace_input.clear();
+        self.run_elevated_input = false;
+        self.retries_input.clear();
+        self.retry_delay_input.clear();
+        self.desktop_override_input = AlertOverride::Inherit;
+        self.sound_override_input = AlertOverride::Inherit;
+        self.verbose_logging_input = false;
+        self.metric_regex_input.clear();
+        self.login_shell_input = false;
+        self.working_dir_input.clear();
+        self.env_var_inputs.clear();
+    }
+    
+    // Applies the edited form fields to an existing task, keeping its identity,
+    // history and runtime state
+    fn update_task(&mut self, id: Uuid, edited: Task) -> Command<Message> {
+        let Some(existing) = self.tasks.iter().find(|t| t.id == id).cloned() else {
+            self.saving_task = false;
+            self.notify("The task being edited no longer exists".to_string(), NotificationLevel::Warning);
+            return Command::none();
+        };
+        
+        let mut task = Task {
+            id: existing.id,
+            is_active: existing.is_active,
+            last_run: existing.last_run,
+            next_run: existing.next_run,
+            created_at: existing.created_at,
+            success_count: existing.success_count,
+            failure_count: existing.failure_count,
+            last_output: existing.last_output.clone(),
+            paused_until: existing.paused_until,
+            failing_since: existing.failing_since,
+            last_success: existing.last_success,
+            completed_at: existing.completed_at.filter(|_| edited.one_shot),
+            ..edited
+        };
+        if task.is_active && task.schedule != existing.schedule {
+            let test_interval = self.test_schedules.get(&id).map(|t| t.interval_seconds);
+            task.next_run = next_run_for(&task, test_interval, Local::now());
+        }
+        
+        let mut changes = vec![];
+        if task.title != existing.title {
+            changes.push(format!("title '{}' -> '{}'", existing.title, task.title));
+        }
+        if task.command != existing.command {
+            changes.push(format!("command `{}` -> `{}`", existing.command, task.command));
+        }
+        if task.schedule != existing.schedule {
+            changes.push(format!("schedule '{}' -> '{}'", existing.schedule.describe(), task.schedule.describe()));
+        }
+        if changes.is_empty() {
+            changes.push("options changed".to_string());
+        }
+        
+        self.notify(format!("Task '{}' updated", task.title), NotificationLevel::Success);
+        self.record_event(
+            AppEventKind::TaskUpdated,
+            Some(id),
+            format!("'{}' updated: {}", task.title, changes.join(", "))
+        );
+        
+        if let Some(slot) = self.tasks.iter_mut().find(|t| t.id == id) {
+            *slot = task;
+        }
+        Command::perform(self.save_all_tasks(), Message::TaskFormSaved)
+    }
+    
+    fn has_unflushed_changes(&self) -> bool {
+        self.logs_dirty || self.events_dirty || self.activity_dirty
+            || !self.dirty_tasks.is_empty() || !self.pending_logs.is_empty()
+    }
+    
+    fn push_log(&mut self, log: ExecutionLog) {
+        // With log_to_file off, logs only live in memory for the session
+        if self.config.log_to_file {
+            self.pending_logs.push(log.clone());
+        }
+        let stats = self.duration_stats.entry(log.task_id).or_default();
+        stats.total_ms += log.duration_ms;
+        stats.runs += 1;
+        stats.last_ms = log.duration_ms;
+        stats.last_failed = !log.success;
+        self.logs.push_back(log);
+        if self.logs.len() > self.config.max_logs {
+            let Some(oldest) = self.logs.pop_front() else {
+                return;
+            };
+            if let Some(stats) = self.duration_stats.get_mut(&oldest.task_id) {
+                stats.total_ms -= oldest.duration_ms;
+                stats.runs -= 1;
+                if stats.runs == 0 {
+                    self.duration_stats.remove(&oldest.task_id);
+                }
+            }
+        }
+    }
+    
+    // For wholesale changes to the logs; single runs go through push_log
+    fn rebuild_duration_stats(&mut self) {
+        self.duration_stats.clear();
+        for log in &self.logs {
+            let stats = self.duration_stats.entry(log.task_id).or_default();
+            stats.total_ms += log.duration_ms;
+            stats.runs += 1;
+            stats.last_ms = log.duration_ms;
+            stats.last_failed = !log.success;
+        }
+    }
+    
+    fn record_event(&mut self, kind: AppEventKind, task_id: Option<Uuid>, detail: String) {
+        self.events.push_back(AppEvent {
+            id: Uuid::new_v4(),
+            timestamp: Local::now(),
+            kind,
+            task_id,
+            detail,
+        });
+        if self.events.len() > MAX_APP_EVENTS {
+            self.events.pop_front();
+        }
+        self.events_dirty = true;
+    }
+    
+    fn filtered_events(&self) -> Vec<&AppEvent> {
+        let since = self.audit_range.since(Local::now());
+        let query = self.audit_search.to_lowercase();
+        self.events.iter()
+            .filter(|e| self.audit_kind.is_none_or(|kind| e.kind == kind))
+            .filter(|e| since.is_none_or(|since| e.timestamp >= since))
+            .filter(|e| query.is_empty() || e.detail.to_lowercase().contains(&query))
             .collect()
     }
     
+    // Snapshots dirty state and clears the flags; the returned future writes it out
+    fn flush_state(&mut self) -> impl std::future::Future<Output = (FlushedState, Result<(), AppError>)> {
+        // Until tasks.json is loaded a snapshot would replace it with a partial list
+        let dirty_tasks: Vec<Uuid> = if self.tasks_loaded {
+            self.dirty_tasks.drain().collect()
+        } else {
+            vec![]
+        };
+        let tasks = (!dirty_tasks.is_empty()).then(|| self.tasks_snapshot());
+        // Appending is cheap, but trimmed entries stay in the file until the
+        // next rewrite, so compact once it has grown LOG_COMPACT_SLACK past max_logs.
+        // Each run's log is copied once (into pending_logs, moved out below); the
+        // full history is only copied for a rewrite, i.e. once per LOG_COMPACT_SLACK
+        // runs instead of on every run (with max_logs 1000: ~10 entries a run, not 1000)
+        let appended = self.log_lines_on_disk + self.pending_logs.len();
+        let logs = if !self.config.log_to_file {
+            None
+        } else if self.logs_dirty || appended > self.config.max_logs + LOG_COMPACT_SLACK {
+            self.log_lines_on_disk = self.logs.len();
+            Some(LogWrite::Rewrite(self.logs.iter().cloned().collect()))
+        } else if !self.pending_logs.is_empty() {
+            self.log_lines_on_disk = appended;
+            Some(LogWrite::Append(std::mem::take(&mut self.pending_logs)))
+        } else {
+            None
+        };
+        let events = self.events_dirty.then(|| self.events.iter().cloned().collect());
+        let activity = (self.activity_dirty && self.config.persist_notifications)
+            .then(|| self.activity.iter().cloned().collect());
+        
+        self.logs_dirty = false;
+        self.pending_logs.clear();
+        self.events_dirty = false;
+        self.activity_dirty = false;
+        self.last_flush = Instant::now();
+        
+        let flushed = FlushedState {
+            tasks: dirty_tasks,
+            logs: logs.is_some(),
+            events: events.is_some(),
+            activity: activity.is_some(),
+        };
+        let write = persist_state(tasks, logs, events, activity);
+        async move { (flushed, write.await) }
+    }
+    
+    // Every write of tasks.json is the whole in-memory list, minus tasks being
+    // deleted, tagged with a generation so an older write can't land after a newer one
+    fn tasks_snapshot(&mut self) -> (u64, Vec<Task>) {
+        self.tasks_generation += 1;
+        let tasks = self.tasks.iter()
+            .filter(|t| !self.deleting_tasks.contains(&t.id))
+            .cloned()
+            .collect();
+        (self.tasks_generation, tasks)
+    }
+    
+    fn save_all_tasks(&mut self) -> impl std::future::Future<Output = Result<(), AppError>> {
+        let loaded = self.tasks_loaded;
+        let (generation, tasks) = self.tasks_snapshot();
+        async move {
+            if !loaded {
+                return Err(AppError::Io("tasks.json has not been loaded".to_string()));
+            }
+            write_tasks(generation, tasks).await
+        }
+    }
+    
+    // The in-memory state is still the truth, so queue it for the next flush
+    // rather than letting the UI show results that never reached disk
+    fn requeue_flush(&mut self, flushed: FlushedState) {
+        let tasks = &self.tasks;
+        self.dirty_tasks.extend(flushed.tasks.into_iter().filter(|id| tasks.iter().any(|t| t.id == *id)));
+        // A failed append may have left a partial line, so rewrite the whole file
+        self.logs_dirty |= flushed.logs;
+        self.events_dirty |= flushed.events;
+        self.activity_dirty |= flushed.activity;
+    }
+    
+    // Flips a task to the given state and records the event; None if it is already there
+    fn set_task_active(&mut self, id: Uuid, active: bool) -> Option<Task> {
+        let test_interval = self.test_schedules.get(&id).map(|t| t.interval_seconds);
+        let task = self.tasks.iter_mut().find(|t| t.id == id && t.is_active != active)?;
+        task.is_active = active;
+        if active {
+            task.completed_at = None;
+            // Pausing clears next_run, so activation starts a fresh grid from now
+            task.next_run = next_scheduled_run(task, test_interval, task.next_run, Local::now());
+        } else {
+            task.next_run = None;
+        }
+        let task = task.clone();
+        
+        let (kind, status) = if active {
+            (AppEventKind::TaskActivated, "activated")
+        } else {
+            (AppEventKind::TaskDeactivated, "paused")
+        };
+        self.record_event(kind, Some(id), format!("Task '{}' {}", task.title, status));
+        Some(task)
+    }
+    
+    fn purge_task_logs(&mut self, ids: &[Uuid]) {
+        if self.config.keep_orphaned_logs {
+            return;
+        }
+        let before = self.logs.len();
+        self.logs.retain(|log| !ids.contains(&log.task_id));
+        for id in ids {
+            self.duration_stats.remove(id);
+        }
+        // Persisted with the next batched flush
+        self.logs_dirty |= self.logs.len() != before;
+    }
+    
+    // Trimmed and case-insensitive; the task being edited does not count
+    fn duplicate_title(&self, title: &str) -> Option<&Task> {
+        let title = title.trim().to_lowercase();
+        self.tasks.iter()
+            .filter(|t| Some(t.id) != self.editing_task)
+            .find(|t| t.title.trim().to_lowercase() == title)
+    }
+    
+    // What the live view shows so far, or None when the task is not running
+    fn live_output_text(&self, id: Uuid) -> Option<String> {
+        self.live_output.get(&id).map(|live| live.lock().map(|out| out.clone()).unwrap_or_default())
+    }
+    
+    // The focus view or the open detail screen belongs to a running task
+    fn live_output_visible(&self) -> bool {
+        let shown = match (self.focus_task, &self.screen) {
+            (Some(id), _) | (None, &Screen::TaskDetail(id)) => Some(id),
+            _ => None,
+        };
+        shown.is_some_and(|id| self.live_output.contains_key(&id))
+    }
+    
+    fn battery_suspended(&self) -> bool {
+        self.config.pause_on_battery && self.on_battery == Some(true)
+    }
+    
+    fn notify_battery_transition(&mut self, was_suspended: bool) {
+        match (was_suspended, self.battery_suspended()) {
+            (false, true) => self.notify(
+                "On battery power: scheduled runs paused".to_string(),
+                NotificationLevel::Warning
+            ),
+            (true, false) => self.notify(
+                "Scheduled runs resumed".to_string(),
+                NotificationLevel::Info
+            ),
+            _ => {}
+        }
+    }
+    
+    fn needs_confirmation(&self, action: &PendingAction) -> bool {
+        match action {
+            // Elevated runs always confirm, even in a trusted session
+            PendingAction::RunTask(id) if self.tasks.iter().any(|t| t.id == *id && t.run_elevated) => true,
+            PendingAction::RunTask(_) => self.config.confirm_before_run && !self.trusted_session,
+            // Destructive actions always confirm, even in a trusted session
+            PendingAction::DeleteTask(_) | PendingAction::DeleteTasks(_) | PendingAction::ResetTaskStats(_) => true,
+            PendingAction::SaveDuplicateTitle(_) => true,
+        }
+    }
+    
+    fn effective_interval(&self, task: &Task) -> u64 {
+        self.test_schedules
+            .get(&task.id)
+            .map(|t| t.interval_seconds)
+            .unwrap_or(match task.schedule {
+                Schedule::Interval(seconds) => seconds,
+                Schedule::Cron(_) => 0,
+            })
+    }
+    
     fn format_duration(seconds: u64) -> String {
         if seconds < 60 {
             format!("{}s", seconds)
@@ -322,6 +2340,34 @@ impl TaskWithMe {
         }
     }
     
+    // Finer than format_duration for anything under a day, e.g. "3m 12s"
+    fn format_countdown(seconds: u64) -> String {
+        if seconds < 60 {
+            format!("{}s", seconds)
+        } else if seconds < 3600 {
+            format!("{}m {}s", seconds / 60, seconds % 60)
+        } else if seconds < 86400 {
+            format!("{}h {}m", seconds / 3600, (seconds % 3600) / 60)
+        } else {
+            Self::format_duration(seconds)
+        }
+    }
+    
+    fn compute_stats(&self) -> DashboardStats {
+        let total_success: u32 = self.tasks.iter().map(|t| t.success_count).sum();
+        let total_failure: u32 = self.tasks.iter().map(|t| t.failure_count).sum();
+        let total_runs = total_success + total_failure;
+        DashboardStats {
+            total: self.tasks.len(),
+            active: self.tasks.iter().filter(|t| t.is_active).count(),
+            success_rate: if total_runs > 0 {
+                total_success as f32 / total_runs as f32 * 100.0
+            } else {
+                0.0
+            },
+        }
+    }
+    
     fn success_rate(&self, task: &Task) -> f32 {
         let total = task.success_count + task.failure_count;
         if total == 0 {
@@ -330,6 +2376,109 @@ impl TaskWithMe {
             (task.success_count as f32 / total as f32) * 100.0
         }
     }
+    
+    fn avg_duration(&self, task_id: Uuid) -> Option<u64> {
+        self.duration_stats.get(&task_id)
+            .filter(|stats| stats.runs > 0)
+            .map(|stats| stats.total_ms / stats.runs)
+    }
+    
+    fn last_duration(&self, task_id: Uuid) -> Option<u64> {
+        self.duration_stats.get(&task_id).map(|stats| stats.last_ms)
+    }
+    
+    // Outcomes of the task's last `count` runs, oldest first
+    fn recent_results(&self, task_id: Uuid, count: usize) -> Vec<bool> {
+        let mut results: Vec<bool> = self.logs.iter()
+            .rev()
+            .filter(|l| l.task_id == task_id)
+            .take(count)
+            .map(|l| l.success)
+            .collect();
+        results.reverse();
+        results
+    }
+    
+    // A readable summary for sharing: settings and stats, recent runs, latest outputs
+    fn task_report(&self, task: &Task, now: DateTime<Local>) -> String {
+        let logs: Vec<&ExecutionLog> = self.logs.iter().rev().filter(|l| l.task_id == task.id).collect();
+        let or_dash = |value: Option<String>| value.unwrap_or_else(|| "-".to_string());
+        
+        let status = if task.completed_at.is_some() {
+            "Completed"
+        } else if task.is_active {
+            "Active"
+        } else {
+            "Inactive"
+        };
+        let settings = [
+            ("Command", format!("`{}`", task.command)),
+            ("Schedule", task.schedule.describe()),
+            ("Status", status.to_string()),
+            ("Created", task.created_at.format("%Y-%m-%d %H:%M").to_string()),
+            ("Working directory", or_dash(task.working_dir.as_ref().map(|d| d.display().to_string()))),
+            ("Timeout", or_dash(task.timeout_seconds.map(|s| format!("{}s", s)))),
+            ("Retries", task.max_retries.to_string()),
+            ("Depends on", or_dash(task.depends_on
+                .and_then(|id| self.tasks.iter().find(|t| t.id == id))
+                .map(|t| t.title.clone()))),
+        ];
+        
+        let mut md = format!("# Task report: {}\n\nGenerated {}\n\n## Configuration\n\n", task.title, now.format("%Y-%m-%d %H:%M:%S"));
+        md.push_str("| Setting | Value |\n| --- | --- |\n");
+        for (name, value) in settings {
+            md.push_str(&format!("| {} | {} |\n", name, md_cell(&value)));
+        }
+        
+        md.push_str("\n## Stats\n\n");
+        md.push_str(&format!("- Runs: {} ok / {} failed\n", task.success_count, task.failure_count));
+        md.push_str(&format!("- Success rate: {}\n", self.format_success_rate(self.success_rate(task))));
+        md.push_str(&format!("- Average duration: {}\n", or_dash(self.avg_duration(task.id).map(format_millis))));
+        md.push_str(&format!("- Last run: {}\n", or_dash(task.last_run.map(|t| t.format("%Y-%m-%d %H:%M:%S").to_string()))));
+        
+        md.push_str(&format!("\n## Recent runs\n\nNewest first, up to {}.\n\n", REPORT_RECENT_RUNS));
+        if logs.is_empty() {
+            md.push_str("No runs recorded.\n");
+        } else {
+            md.push_str("| Time | Result | Exit | Duration | Attempt |\n| --- | --- | --- | --- | --- |\n");
+            for log in logs.iter().take(REPORT_RECENT_RUNS) {
+                md.push_str(&format!(
+                    "| {} | {} | {} | {} | {} |\n",
+                    log.timestamp.format("%Y-%m-%d %H:%M:%S"),
+                    if log.success { "OK" } else { "FAIL" },
+                    or_dash(log.exit_code.map(|c| c.to_string())),
+                    format_millis(log.duration_ms),
+                    log.attempt,
+                ));
+            }
+        }
+        
+        for log in logs.iter().take(REPORT_OUTPUTS) {
+            let output = log.output_text();
+            let fence = code_fence(&output);
+            md.push_str(&format!(
+                "\n### Output of {} ({})\n\n{}\n{}\n{}\n",
+                log.timestamp.format("%Y-%m-%d %H:%M:%S"),
+                if log.success { "OK" } else { "FAIL" },
+                fence,
+                output,
+                fence,
+            ));
+        }
+        md
+    }
+    
+    // "Running 12s", redrawn by the 1s Tick; None when the task isn't running
+    fn running_label(&self, task: &Task) -> Option<(String, Color)> {
+        let elapsed = self.run_started.get(&task.id)?.elapsed().as_secs();
+        let length = RunLength::classify(elapsed, task.timeout_seconds);
+        Some((format!("Running {}", Self::format_countdown(elapsed)), length.color()))
+    }
+    
+    fn format_success_rate(&self, rate: f32) -> String {
+        let precision = self.config.success_rate_precision.min(1) as usize;
+        format!("{:.*}%", precision, rate)
+    }
 }
 
 //Application Implement
@@ -341,14 +2490,10 @@ impl Application for TaskWithMe {
 
     fn new(_flags: ()) -> (Self, Command<Message>) {
         let mut app = TaskWithMe::default();
-        app.refresh_input = app.config.refresh_interval.to_string();
-        app.max_logs_input = app.config.max_logs.to_string();
+        app.reset_settings_draft();
         
-        let load_config = Command::perform(load_config(), Message::ConfigLoaded);
-        let load_tasks = Command::perform(load_tasks(), Message::TasksLoaded);
-        let load_logs = Command::perform(load_logs(), Message::LogsLoaded);
-        
-        (app, Command::batch(vec![load_config, load_tasks, load_logs]))
+        // The config decides where everything else is loaded from
+        (app, Command::perform(load_config(), Message::ConfigLoaded))
     }
 
     fn title(&self) -> String {
@@ -356,6 +2501,11 @@ impl Application for TaskWithMe {
             Screen::Overview => "Overview - Task with Me".to_string(),
             Screen::Tasks => "Tasks - Task with Me".to_string(),
             Screen::Logs(_) => "Logs - Task with Me".to_string(),
+            Screen::Failures => "Failures - Task with Me".to_string(),
+            Screen::Calendar => "Calendar - Task with Me".to_string(),
+            Screen::Compare(..) => "Compare - Task with Me".to_string(),
+            Screen::TaskDetail(_) => "Task Details - Task with Me".to_string(),
+            Screen::Audit => "Audit - Task with Me".to_string(),
             Screen::Settings => "Settings - Task with Me".to_string(),
         }
     }
@@ -363,10 +2513,18 @@ impl Application for TaskWithMe {
     fn update(&mut self, message: Message) -> Command<Message> {
         match message {
             Message::ChangeScreen(screen) => {
+                if matches!(screen, Screen::Logs(_)) {
+                    self.log_page = 0;
+                }
                 self.screen = screen;
                 Command::none()
             }
             
+            Message::FocusNewTask => {
+                self.screen = Screen::Tasks;
+                text_input::focus(title_input_id())
+            }
+            
             Message::TitleInput(s) => {
                 self.title_input = s;
                 Command::none()
@@ -382,1182 +2540,9123 @@ impl Application for TaskWithMe {
                 Command::none()
             }
             
-            Message::CreateTask => {
-                if self.title_input.trim().is_empty() {
-                    self.notify("Task title cannot be empty".to_string(), NotificationLevel::Warning);
-                    return Command::none();
-                }
-                
-                if self.command_input.trim().is_empty() {
-                    self.notify("Command cannot be empty".to_string(), NotificationLevel::Warning);
-                    return Command::none();
-                }
-                
-                let interval = match self.interval_input.parse::<u64>() {
-                    Ok(n) if n > 0 => n,
-                    _ => {
-                        self.notify("Invalid interval".to_string(), NotificationLevel::Warning);
-                        return Command::none();
-                    }
-                };
-                
-                let task = Task {
-                    id: Uuid::new_v4(),
-                    title: std::mem::take(&mut self.title_input),
-                    command: std::mem::take(&mut self.command_input),
-                    interval_seconds: interval,
-                    is_active: false,
-                    last_run: None,
-                    next_run: None,
-                    created_at: Local::now(),
-                    success_count: 0,
-                    failure_count: 0,
-                    last_output: String::new(),
-                };
-                
-                self.interval_input.clear();
-                
-                println!("Creating task: {} (ID: {})", task.title, task.id);
-                self.notify(format!("Task '{}' created", task.title), NotificationLevel::Success);
-                
-                Command::perform(save_task(task), Message::TaskSaved)
+            Message::CronModeToggled(enabled) => {
+                self.cron_mode_input = enabled;
+                Command::none()
             }
             
-            Message::DeleteTask(id) => {
-                if let Some(task) = self.tasks.iter().find(|t| t.id == id) {
-                    self.notify(format!("Deleted task '{}'", task.title), NotificationLevel::Info);
-                }
-                Command::perform(delete_task(id), Message::TaskDeleted)
+            Message::CronInput(s) => {
+                self.cron_input = s;
+                Command::none()
             }
             
-            Message::ToggleTask(id) => {
-                let mut task_to_save = None;
-                let mut notification_msg = String::new();
-                
-                if let Some(task) = self.tasks.iter_mut().find(|t| t.id == id) {
-                    task.is_active = !task.is_active;
-                    if task.is_active {
-                        task.next_run = Some(Local::now() + chrono::Duration::seconds(task.interval_seconds as i64));
-                    } else {
-                        task.next_run = None;
-                    }
-                    
-                    let status = if task.is_active { "activated" } else { "paused" };
-                    notification_msg = format!("Task '{}' {}", task.title, status);
-                    task_to_save = Some(task.clone());
-                }
-                
-                if let Some(task) = task_to_save {
-                    self.notify(notification_msg, NotificationLevel::Info);
-                    return Command::perform(save_task(task), Message::TaskSaved);
-                }
+            Message::MaxOutputInput(s) => {
+                self.max_output_input = s;
                 Command::none()
             }
             
-            Message::ExecuteTask(id) => {
-                if self.running_tasks.contains(&id) {
-                    self.notify("Task is already running".to_string(), NotificationLevel::Warning);
-                    return Command::none();
-                }
-                
-                let task_info = self.tasks.iter().find(|t| t.id == id).map(|task| {
-                    (task.clone(), task.title.clone())
-                });
-                
-                if let Some((task_clone, task_title)) = task_info {
-                    self.running_tasks.push(id);
-                    self.notify(format!("Executing '{}'...", task_title), NotificationLevel::Info);
-                    
-                    return Command::perform(
-                        execute_task(task_clone),
-                        move |result| Message::TaskExecuted(id, result)
-                    );
-                }
+            Message::OutputFileInput(s) => {
+                self.output_file_input = s;
                 Command::none()
             }
             
-            Message::TaskExecuted(id, result) => {
-                self.running_tasks.retain(|&tid| tid != id);
-                
-                let mut commands = vec![];
-                
-                match result {
-                    Ok(exec_result) => {
-                        if let Some(task) = self.tasks.iter_mut().find(|t| t.id == id) {
-                            task.last_run = Some(Local::now());
-                            task.last_output = exec_result.output.clone();
-                            
-                            let success = exec_result.success;
-                            let task_title = task.title.clone();
-                            
-                            if success {
-                                task.success_count += 1;
-                            } else {
-                                task.failure_count += 1;
-                            }
-                            
-                            if task.is_active {
-                                task.next_run = Some(Local::now() + chrono::Duration::seconds(task.interval_seconds as i64));
-                            }
-                            
-                            let log = ExecutionLog {
-                                id: Uuid::new_v4(),
-                                task_id: id,
-                                timestamp: Local::now(),
-                                success: exec_result.success,
-                                output: exec_result.output,
-                                duration_ms: exec_result.duration_ms,
-                            };
-                            
-                            self.logs.push(log);
-                            if self.logs.len() > self.config.max_logs {
-                                self.logs.remove(0);
-                            }
-                            
-                            let task_clone = task.clone();
-                            let logs_clone = self.logs.clone();
-                            
-                            if success {
-                                self.notify(
-                                    format!("Task '{}' completed successfully", task_title),
-                                    NotificationLevel::Success
-                                );
-                            } else {
-                                self.notify(
-                                    format!("Task '{}' failed", task_title),
-                                    NotificationLevel::Error
-                                );
-                            }
-                            
-                            commands.push(Command::perform(save_task(task_clone), Message::TaskSaved));
-                            commands.push(Command::perform(save_logs(logs_clone), |_| Message::Tick));
-                        }
-                    }
-                    Err(e) => {
-                        self.notify(format!("Execution error: {}", e), NotificationLevel::Error);
-                    }
-                }
-                Command::batch(commands)
+            Message::OutputAppendToggled(append) => {
+                self.output_append_input = append;
+                Command::none()
             }
             
-            Message::TasksLoaded(Ok(tasks)) => {
-                println!("Tasks loaded: {} tasks", tasks.len());
-                for task in &tasks {
-                    println!("  - {} ({})", task.title, task.id);
-                }
-                self.tasks = tasks;
+            Message::SkipPrefixToggled(skip) => {
+                self.skip_prefix_input = skip;
                 Command::none()
             }
             
-            Message::TasksLoaded(Err(e)) => {
-                self.notify(format!("Failed to load tasks: {}", e), NotificationLevel::Error);
+            Message::WatchPathInput(s) => {
+                self.watch_path_input = s;
                 Command::none()
             }
             
-            Message::LogsLoaded(Ok(logs)) => {
-                self.logs = logs;
+            Message::FailureAlertInput(s) => {
+                self.failure_alert_input = s;
                 Command::none()
             }
             
-            Message::LogsLoaded(Err(_)) => {
+            Message::RunOnStartupToggled(enabled) => {
+                self.run_on_startup_input = enabled;
                 Command::none()
             }
             
-            Message::TaskSaved(Ok(())) => {
-                println!("Task saved successfully, reloading...");
-                Command::perform(load_tasks(), Message::TasksLoaded)
+            Message::RunOnActivateToggled(enabled) => {
+                self.run_on_activate_input = enabled;
+                Command::none()
             }
             
-            Message::TaskSaved(Err(e)) => {
-                self.notify(format!("Failed to save: {}", e), NotificationLevel::Error);
+            Message::OneShotToggled(enabled) => {
+                self.one_shot_input = enabled;
                 Command::none()
             }
             
-            Message::TaskDeleted(Ok(())) => {
-                Command::perform(load_tasks(), Message::TasksLoaded)
+            Message::NotifyOnSuccessToggled(enabled) => {
+                self.notify_on_success_input = enabled;
+                Command::none()
             }
             
-            Message::TaskDeleted(Err(e)) => {
-                self.notify(format!("Failed to delete: {}", e), NotificationLevel::Error);
+            Message::NotifyOnFailureToggled(enabled) => {
+                self.notify_on_failure_input = enabled;
                 Command::none()
             }
             
-            Message::ConfigLoaded(Ok(config)) => {
-                self.config = config;
-                self.refresh_input = self.config.refresh_interval.to_string();
-                self.max_logs_input = self.config.max_logs.to_string();
+            // From the detail view; saved right away like the other per-task actions there
+            Message::SetTaskNotifications(id, on_success, on_failure) => {
+                let Some(task) = self.tasks.iter_mut().find(|t| t.id == id) else {
+                    return Command::none();
+                };
+                task.notify_on_success = on_success;
+                task.notify_on_failure = on_failure;
+                Command::perform(self.save_all_tasks(), Message::TaskSaved)
+            }
+            
+            Message::DependsOnSelected(choice) => {
+                self.depends_on_input = choice.id;
                 Command::none()
             }
             
-            Message::ConfigLoaded(Err(_)) => {
+            // Nothing is run or looked up in the keyring here
+            Message::PreviewCommand => {
+                if self.command_input.trim().is_empty() {
+                    self.notify("Command cannot be empty".to_string(), NotificationLevel::Warning);
+                    return Command::none();
+                }
+                
+                let options = ShellOptions {
+                    login: self.login_shell_input,
+                    shell: self.config.shell.clone(),
+                    shell_args: self.config.shell_args.clone(),
+                    timeout: None,
+                    grace: 0,
+                    working_dir: Some(self.working_dir_input.trim())
+                        .filter(|p| !p.is_empty())
+                        .map(PathBuf::from),
+                    env_vars: self.env_var_inputs.iter()
+                        .map(|(key, value)| (key.trim().to_string(), value.clone()))
+                        .filter(|(key, value)| !key.is_empty() || !value.is_empty())
+                        .collect(),
+                    live_output: None,
+                    secrets: vec![],
+                };
+                self.command_preview = Some(preview_command(
+                    &self.command_input,
+                    &options,
+                    &self.config,
+                    self.skip_prefix_input,
+                    self.run_elevated_input,
+                ));
                 Command::none()
             }
             
-            Message::ConfigSaved(Ok(())) => {
-                self.notify("Settings saved".to_string(), NotificationLevel::Success);
-                self.screen = Screen::Overview;
+            Message::ClosePreview => {
+                self.command_preview = None;
                 Command::none()
             }
             
-            Message::ConfigSaved(Err(e)) => {
-                self.notify(format!("Failed to save settings: {}", e), NotificationLevel::Error);
+            Message::StartupOrderInput(s) => {
+                self.startup_order_input = s;
                 Command::none()
             }
             
-            Message::SelectTemplate(idx) => {
-                if let Some(template) = self.templates.get(idx) {
-                    self.title_input = template.name.to_string();
-                    self.command_input = template.command.to_string();
-                    self.interval_input = template.interval.to_string();
-                    self.notify(format!("Template loaded: {}", template.name), NotificationLevel::Info);
-                }
+            Message::OnSuccessInput(s) => {
+                self.on_success_input = s;
                 Command::none()
             }
             
-            Message::SearchInput(s) => {
-                self.search_query = s;
+            Message::WebhookInput(s) => {
+                self.webhook_input = s;
                 Command::none()
             }
             
-            Message::FilterChanged(filter) => {
-                self.filter = filter;
+            Message::TimeoutInput(s) => {
+                self.timeout_input = s;
                 Command::none()
             }
             
-            Message::ViewTaskLogs(id) => {
-                self.screen = Screen::Logs(Some(id));
+            Message::GraceInput(s) => {
+                self.grace_input = s;
                 Command::none()
             }
             
-            Message::CloseNotification(id) => {
-                self.notifications.retain(|n| n.id != id);
+            Message::RunElevatedToggled(enabled) => {
+                self.run_elevated_input = enabled;
                 Command::none()
             }
             
-            Message::ClearNotifications => {
-                self.notifications.clear();
+            Message::RetriesInput(s) => {
+                self.retries_input = s;
                 Command::none()
             }
             
-            Message::ThemeChanged(theme) => {
-                self.config.theme = theme;
+            Message::RetryDelayInput(s) => {
+                self.retry_delay_input = s;
                 Command::none()
             }
             
-            Message::RefreshIntervalChanged(s) => {
-                self.refresh_input = s;
+            Message::DesktopOverrideChanged(choice) => {
+                self.desktop_override_input = choice;
                 Command::none()
             }
             
-            Message::MaxLogsChanged(s) => {
-                self.max_logs_input = s;
+            Message::SoundOverrideChanged(choice) => {
+                self.sound_override_input = choice;
                 Command::none()
             }
             
-            Message::SaveSettings => {
-                if let Ok(interval) = self.refresh_input.parse::<u64>() {
-                    self.config.refresh_interval = interval.max(1);
+            Message::VerboseLoggingToggled(enabled) => {
+                self.verbose_logging_input = enabled;
+                Command::none()
+            }
+            
+            Message::MetricRegexInput(s) => {
+                self.metric_regex_input = s;
+                Command::none()
+            }
+            
+            Message::LoginShellToggled(enabled) => {
+                self.login_shell_input = enabled;
+                Command::none()
+            }
+            
+            Message::WorkingDirInput(s) => {
+                self.working_dir_input = s;
+                Command::none()
+            }
+            
+            Message::AddEnvVar => {
+                self.env_var_inputs.push((String::new(), String::new()));
+                Command::none()
+            }
+            
+            Message::RemoveEnvVar(idx) => {
+                if idx < self.env_var_inputs.len() {
+                    self.env_var_inputs.remove(idx);
                 }
-                if let Ok(max_logs) = self.max_logs_input.parse::<usize>() {
-                    self.config.max_logs = max_logs.max(10);
+                Command::none()
+            }
+            
+            Message::EnvKeyInput(idx, s) => {
+                if let Some(row) = self.env_var_inputs.get_mut(idx) {
+                    row.0 = s;
                 }
-                
-                Command::perform(save_config(self.config.clone()), Message::ConfigSaved)
+                Command::none()
             }
             
-            Message::Tick => Command::none(),
+            Message::EnvValueInput(idx, s) => {
+                if let Some(row) = self.env_var_inputs.get_mut(idx) {
+                    row.1 = s;
+                }
+                Command::none()
+            }
             
-            Message::CheckScheduledTasks => {
-                let now = Local::now();
-                let mut commands = vec![];
+            Message::CreateTask => {
+                if self.saving_task {
+                    return Command::none();
+                }
+                let duplicate_confirmed = std::mem::take(&mut self.duplicate_title_confirmed);
                 
-                for task in &self.tasks {
-                    if task.is_active {
-                        if let Some(next_run) = task.next_run {
-                            if now >= next_run && !self.running_tasks.contains(&task.id) {
-                                let task_id = task.id;
-                                commands.push(Command::perform(
-                                    async move { task_id },
-                                    Message::ExecuteTask
-                                ));
-                            }
+                if self.title_input.trim().is_empty() {
+                    self.notify("Task title cannot be empty".to_string(), NotificationLevel::Warning);
+                    return Command::none();
+                }
+                
+                if self.command_input.trim().is_empty() {
+                    self.notify("Command cannot be empty".to_string(), NotificationLevel::Warning);
+                    return Command::none();
+                }
+                
+                if let Some(existing) = self.duplicate_title(&self.title_input).map(|t| t.title.clone()) {
+                    match self.config.duplicate_titles {
+                        DuplicateTitlePolicy::Allow => {}
+                        DuplicateTitlePolicy::Block => {
+                            self.notify(format!("A task named '{}' already exists", existing), NotificationLevel::Warning);
+                            return Command::none();
                         }
+                        DuplicateTitlePolicy::Warn if !duplicate_confirmed => {
+                            self.notify(format!("A task named '{}' already exists", existing), NotificationLevel::Warning);
+                            self.pending_action = Some(PendingAction::SaveDuplicateTitle(existing));
+                            return Command::none();
+                        }
+                        DuplicateTitlePolicy::Warn => {}
                     }
                 }
                 
-                Command::batch(commands)
-            }
-        }
-    }
-
-    fn view(&self) -> Element<Message> {
-        let content = match &self.screen {
-            Screen::Overview => self.view_overview(),
-            Screen::Tasks => self.view_tasks(),
-            Screen::Logs(task_id) => self.view_logs(*task_id),
-            Screen::Settings => self.view_settings(),
-        };
-
-        column![
-            self.view_header(),
-            Space::with_height(20),
-            content,
-            self.view_notifications(),
-        ]
-        .padding(20)
-        .into()
-    }
-
-    fn subscription(&self) -> Subscription<Message> {
-        time::every(Duration::from_secs(self.config.refresh_interval))
-            .map(|_| Message::CheckScheduledTasks)
-    }
-
-    fn theme(&self) -> Theme {
-        match self.config.theme {
-            AppTheme::Light => Theme::Light,
-            AppTheme::Dark => Theme::Dark,
-        }
-    }
-}
-
-//View Components
-impl TaskWithMe {
-    fn view_header(&self) -> Element<Message> {
-        let nav_button = |label: &str, screen: Screen, is_active: bool| {
-            button(text(label).size(14))
-                .on_press(Message::ChangeScreen(screen))
-                .padding([10, 16])
-                .style(if is_active {
-                    iced::theme::Button::Primary
+                let schedule = if self.cron_mode_input {
+                    let expr = self.cron_input.split_whitespace().collect::<Vec<_>>().join(" ");
+                    if let Err(e) = parse_cron(&expr) {
+                        self.notify(format!("Invalid cron expression: {}", e), NotificationLevel::Warning);
+                        return Command::none();
+                    }
+                    Schedule::Cron(expr)
                 } else {
-                    iced::theme::Button::Secondary
-                })
-        };
-
-        container(
-            row![
-                text("[Task with Me]").size(22),
-                Space::with_width(Length::Fill),
-                row![
-                    nav_button("Overview", Screen::Overview, 
-                        matches!(self.screen, Screen::Overview)),
-                    nav_button("Tasks", Screen::Tasks, 
-                        matches!(self.screen, Screen::Tasks)),
+                    match self.interval_input.parse::<u64>() {
+                        Ok(n) if n > MAX_INTERVAL_SECONDS => {
+                            self.notify(
+                                format!("Interval capped at {}", Self::format_duration(MAX_INTERVAL_SECONDS)),
+                                NotificationLevel::Warning
+                            );
+                            Schedule::Interval(MAX_INTERVAL_SECONDS)
+                        }
+                        Ok(n) if n > 0 => Schedule::Interval(n),
+                        _ => {
+                            self.notify("Invalid interval".to_string(), NotificationLevel::Warning);
+                            return Command::none();
+                        }
+                    }
+                };
+                
+                let max_output_bytes = if self.max_output_input.trim().is_empty() {
+                    None
+                } else {
+                    match self.max_output_input.trim().parse::<usize>() {
+                        Ok(n) if n > 0 => Some(n),
+                        _ => {
+                            self.notify("Invalid max output size".to_string(), NotificationLevel::Warning);
+                            return Command::none();
+                        }
+                    }
+                };
+                
+                let failure_alert_after_seconds = if self.failure_alert_input.trim().is_empty() {
+                    None
+                } else {
+                    match self.failure_alert_input.trim().parse::<u64>() {
+                        Ok(n) if n > 0 => Some(n),
+                        _ => {
+                            self.notify("Invalid failure alert delay".to_string(), NotificationLevel::Warning);
+                            return Command::none();
+                        }
+                    }
+                };
+                
+                let startup_order = if self.startup_order_input.trim().is_empty() {
+                    None
+                } else {
+                    match self.startup_order_input.trim().parse::<u32>() {
+                        Ok(n) => Some(n),
+                        _ => {
+                            self.notify("Invalid startup order".to_string(), NotificationLevel::Warning);
+                            return Command::none();
+                        }
+                    }
+                };
+                
+                let parse_seconds = |input: &str| -> Result<Option<u64>, ()> {
+                    match input.trim() {
+                        "" => Ok(None),
+                        s => s.parse::<u64>().ok().filter(|n| *n > 0).map(Some).ok_or(()),
+                    }
+                };
+                let Ok(timeout_seconds) = parse_seconds(&self.timeout_input) else {
+                    self.notify("Invalid timeout".to_string(), NotificationLevel::Warning);
+                    return Command::none();
+                };
+                let Ok(timeout_grace_seconds) = parse_seconds(&self.grace_input) else {
+                    self.notify("Invalid grace period".to_string(), NotificationLevel::Warning);
+                    return Command::none();
+                };
+                
+                let metric_regex = match self.metric_regex_input.trim() {
+                    "" => None,
+                    pattern => match regex::Regex::new(pattern) {
+                        Ok(_) => Some(pattern.to_string()),
+                        Err(e) => {
+                            self.notify(format!("Invalid metric regex: {}", e), NotificationLevel::Warning);
+                            return Command::none();
+                        }
+                    },
+                };
+                
+                // Rows with neither name nor value are ignored
+                let env_vars: Vec<(String, String)> = self.env_var_inputs.iter()
+                    .map(|(key, value)| (key.trim().to_string(), value.clone()))
+                    .filter(|(key, value)| !key.is_empty() || !value.is_empty())
+                    .collect();
+                if let Some((key, _)) = env_vars.iter().find(|(key, _)| !is_valid_env_name(key)) {
+                    self.notify(format!("Invalid environment variable name '{}'", key), NotificationLevel::Warning);
+                    return Command::none();
+                }
+                
+                let webhook_url = Some(self.webhook_input.trim())
+                    .filter(|u| !u.is_empty())
+                    .map(str::to_string);
+                if webhook_url.as_ref().is_some_and(|u| !u.starts_with("http://") && !u.starts_with("https://")) {
+                    self.notify("Webhook URL must start with http:// or https://".to_string(), NotificationLevel::Warning);
+                    return Command::none();
+                }
+                
+                let max_retries = match self.retries_input.trim() {
+                    "" => 0,
+                    s => match s.parse::<u32>() {
+                        Ok(n) => n,
+                        Err(_) => {
+                            self.notify("Invalid retry count".to_string(), NotificationLevel::Warning);
+                            return Command::none();
+                        }
+                    },
+                };
+                let retry_delay_seconds = match self.retry_delay_input.trim() {
+                    "" => DEFAULT_RETRY_DELAY_SECONDS,
+                    s => match s.parse::<u64>() {
+                        Ok(n) => n.min(MAX_INTERVAL_SECONDS),
+                        Err(_) => {
+                            self.notify("Invalid retry delay".to_string(), NotificationLevel::Warning);
+                            return Command::none();
+                        }
+                    },
+                };
+                
+                // Only an edited task can already have dependents
+                if let (Some(id), Some(depends_on)) = (self.editing_task, self.depends_on_input) {
+                    if creates_dependency_cycle(&self.tasks, id, depends_on) {
+                        self.notify(
+                            "That dependency would create a cycle; pick a different upstream task".to_string(),
+                            NotificationLevel::Warning
+                        );
+                        return Command::none();
+                    }
+                }
+                
+                let task = Task {
+                    id: Uuid::new_v4(),
+                    title: std::mem::take(&mut self.title_input),
+                    command: std::mem::take(&mut self.command_input),
+                    schedule,
+                    is_active: false,
+                    last_run: None,
+                    next_run: None,
+                    created_at: Local::now(),
+                    success_count: 0,
+                    failure_count: 0,
+                    last_output: String::new(),
+                    max_output_bytes,
+                    output_file: Some(self.output_file_input.trim())
+                        .filter(|p| !p.is_empty())
+                        .map(PathBuf::from),
+                    output_file_append: self.output_append_input,
+                    paused_until: None,
+                    skip_command_prefix: self.skip_prefix_input,
+                    watch_path: Some(self.watch_path_input.trim())
+                        .filter(|p| !p.is_empty())
+                        .map(PathBuf::from),
+                    failure_alert_after_seconds,
+                    failing_since: None,
+                    run_on_startup: self.run_on_startup_input,
+                    startup_order,
+                    on_success_command: Some(self.on_success_input.trim())
+                        .filter(|c| !c.is_empty())
+                        .map(str::to_string),
+                    timeout_seconds,
+                    timeout_grace_seconds,
+                    run_elevated: self.run_elevated_input,
+                    max_retries,
+                    retry_delay_seconds,
+                    desktop_override: self.desktop_override_input,
+                    sound_override: self.sound_override_input,
+                    verbose_logging: self.verbose_logging_input,
+                    metric_regex,
+                    login_shell: self.login_shell_input,
+                    working_dir: Some(self.working_dir_input.trim())
+                        .filter(|p| !p.is_empty())
+                        .map(PathBuf::from),
+                    env_vars,
+                    run_on_activate: self.run_on_activate_input,
+                    depends_on: self.depends_on_input,
+                    one_shot: self.one_shot_input,
+                    completed_at: None,
+                    notify_on_success: self.notify_on_success_input,
+                    notify_on_failure: self.notify_on_failure_input,
+                    webhook_url,
+                    last_success: None,
+                };
+                
+                self.clear_task_form();
+                self.saving_task = true;
+                
+                if let Some(id) = self.editing_task.take() {
+                    return self.update_task(id, task);
+                }
+                
+                log::debug!("Creating task: {} (ID: {})", task.title, task.id);
+                self.notify(format!("Task '{}' created", task.title), NotificationLevel::Success);
+                self.record_event(
+                    AppEventKind::TaskCreated,
+                    Some(task.id),
+                    format!("'{}' created with command `{}` ({})", task.title, task.command, task.schedule.describe())
+                );
+                
+                self.tasks.push(task);
+                Command::perform(self.save_all_tasks(), Message::TaskFormSaved)
+            }
+            
+            Message::EditTask(id) => {
+                let Some(task) = self.tasks.iter().find(|t| t.id == id).cloned() else {
+                    return Command::none();
+                };
+                let optional = |value: Option<String>| value.unwrap_or_default();
+                
+                // The form lives on the Tasks screen; Edit is also offered from task details
+                self.screen = Screen::Tasks;
+                self.title_input = task.title;
+                self.command_input = task.command;
+                (self.interval_input, self.cron_input) = match task.schedule {
+                    Schedule::Interval(seconds) => (seconds.to_string(), String::new()),
+                    Schedule::Cron(expr) => (String::new(), expr),
+                };
+                self.cron_mode_input = !self.cron_input.is_empty();
+                self.max_output_input = optional(task.max_output_bytes.map(|n| n.to_string()));
+                self.output_file_input = optional(task.output_file.map(|p| p.display().to_string()));
+                self.output_append_input = task.output_file_append;
+                self.skip_prefix_input = task.skip_command_prefix;
+                self.watch_path_input = optional(task.watch_path.map(|p| p.display().to_string()));
+                self.failure_alert_input = optional(task.failure_alert_after_seconds.map(|n| n.to_string()));
+                self.run_on_startup_input = task.run_on_startup;
+                self.run_on_activate_input = task.run_on_activate;
+                self.one_shot_input = task.one_shot;
+                self.notify_on_success_input = task.notify_on_success;
+                self.notify_on_failure_input = task.notify_on_failure;
+                self.depends_on_input = task.depends_on;
+                self.startup_order_input = optional(task.startup_order.map(|n| n.to_string()));
+                self.on_success_input = optional(task.on_success_command);
+                self.webhook_input = optional(task.webhook_url);
+                self.timeout_input = optional(task.timeout_seconds.map(|n| n.to_string()));
+                self.grace_input = optional(task.timeout_grace_seconds.map(|n| n.to_string()));
+                self.run_elevated_input = task.run_elevated;
+                self.retries_input = optional((task.max_retries > 0).then(|| task.max_retries.to_string()));
+                self.retry_delay_input = optional((task.max_retries > 0).then(|| task.retry_delay_seconds.to_string()));
+                self.desktop_override_input = task.desktop_override;
+                self.sound_override_input = task.sound_override;
+                self.verbose_logging_input = task.verbose_logging;
+                self.metric_regex_input = optional(task.metric_regex);
+                self.login_shell_input = task.login_shell;
+                self.working_dir_input = optional(task.working_dir.map(|p| p.display().to_string()));
+                self.env_var_inputs = task.env_vars;
+                self.editing_task = Some(id);
+                Command::none()
+            }
+            
+            Message::EnterFocus(id) => {
+                self.focus_task = Some(id);
+                Command::none()
+            }
+            
+            Message::ExitFocus => {
+                self.focus_task = None;
+                Command::none()
+            }
+            
+            Message::CancelEdit => {
+                self.editing_task = None;
+                self.clear_task_form();
+                Command::none()
+            }
+            
+            Message::DeleteTask(id) => {
+                if !self.deleting_tasks.insert(id) {
+                    return Command::none();
+                }
+                if let Some(title) = self.tasks.iter().find(|t| t.id == id).map(|t| t.title.clone()) {
+                    self.notify(format!("Deleted task '{}'", title), NotificationLevel::Info);
+                    self.record_event(AppEventKind::TaskDeleted, Some(id), format!("'{}' deleted", title));
+                }
+                Command::perform(self.save_all_tasks(), move |result| Message::TaskDeleted(id, result))
+            }
+            
+            Message::ToggleTask(id) => {
+                let Some(active) = self.tasks.iter().find(|t| t.id == id).map(|t| !t.is_active) else {
+                    return Command::none();
+                };
+                
+                if let Some(task) = self.set_task_active(id, active) {
+                    let status = if task.is_active { "activated" } else { "paused" };
+                    self.notify(format!("Task '{}' {}", task.title, status), NotificationLevel::Info);
+                    let run_now = task.is_active && task.run_on_activate && !self.running_tasks.contains(&id);
+                    let save = Command::perform(self.save_all_tasks(), Message::TaskSaved);
+                    // next_run is already an interval ahead, so this run leaves the schedule alone
+                    if run_now {
+                        return Command::batch([save, self.update(Message::ExecuteTask(id))]);
+                    }
+                    return save;
+                }
+                Command::none()
+            }
+            
+            Message::ExecuteTask(id) => {
+                if self.running_tasks.contains(&id) {
+                    self.notify("Task is already running".to_string(), NotificationLevel::Warning);
+                    return Command::none();
+                }
+                // A fresh run supersedes any pending retry
+                self.retry_attempts.remove(&id);
+                if self.at_concurrency_limit() {
+                    if !self.run_queue.contains(&id) {
+                        self.run_queue.push_back(id);
+                    }
+                    return Command::none();
+                }
+                self.start_execution(id)
+            }
+            
+            Message::RunAllActive => {
+                let now = Local::now();
+                let ids: Vec<Uuid> = self.tasks.iter()
+                    .filter(|t| t.is_active && !t.is_paused_at(now))
+                    .filter(|t| !self.running_tasks.contains(&t.id) && !self.run_queue.contains(&t.id))
+                    .map(|t| t.id)
+                    .collect();
+                if ids.is_empty() {
+                    self.notify("No active tasks to run".to_string(), NotificationLevel::Info);
+                    return Command::none();
+                }
+                
+                let total = ids.len();
+                for id in &ids {
+                    self.retry_attempts.remove(id);
+                }
+                self.run_queue.extend(ids);
+                let before = self.running_tasks.len();
+                let command = self.drain_run_queue();
+                let started = self.running_tasks.len() - before;
+                self.notify(
+                    format!("Started {} of {} active task(s), {} queued", started, total, total - started),
+                    NotificationLevel::Info
+                );
+                command
+            }
+            
+            Message::CancelTask(id) => {
+                if let Some(signal) = self.cancel_signals.get(&id) {
+                    let _ = signal.send(true);
+                    self.notify("Cancelling task...".to_string(), NotificationLevel::Info);
+                }
+                Command::none()
+            }
+            
+            Message::WebhookSent(title, Err(e)) => {
+                self.notify(format!("Webhook for '{}' failed: {}", title, e), NotificationLevel::Warning);
+                Command::none()
+            }
+            
+            Message::WebhookSent(_, Ok(())) => Command::none(),
+            
+            Message::RunAlertSent(result) => {
+                if let Err(e) = result {
+                    log::warn!("Failed to send run alert: {}", e);
+                }
+                Command::none()
+            }
+            
+            Message::RetryTask(id) => {
+                if !self.retry_attempts.contains_key(&id) || self.running_tasks.contains(&id) {
+                    return Command::none();
+                }
+                // Retries go ahead of other waiting runs
+                if self.at_concurrency_limit() {
+                    if !self.run_queue.contains(&id) {
+                        self.run_queue.push_front(id);
+                    }
+                    return Command::none();
+                }
+                self.start_execution(id)
+            }
+            
+            Message::RunTask(id) => {
+                if self.needs_confirmation(&PendingAction::RunTask(id)) {
+                    self.pending_action = Some(PendingAction::RunTask(id));
+                    return Command::none();
+                }
+                self.update(Message::ExecuteTask(id))
+            }
+            
+            Message::RequestDeleteTask(id) => {
+                self.pending_action = Some(PendingAction::DeleteTask(id));
+                Command::none()
+            }
+            
+            Message::ToggleTaskSelected(id, selected) => {
+                if selected {
+                    self.selected_tasks.insert(id);
+                } else {
+                    self.selected_tasks.remove(&id);
+                }
+                Command::none()
+            }
+            
+            // Every match of the current filter and search, not just the visible page
+            Message::SelectAllFiltered => {
+                let ids: Vec<Uuid> = self.filtered_tasks().iter().map(|t| t.id).collect();
+                self.selected_tasks.extend(ids);
+                Command::none()
+            }
+            
+            Message::ClearSelection => {
+                self.selected_tasks.clear();
+                Command::none()
+            }
+            
+            Message::RequestDeleteSelected => {
+                let ids: Vec<Uuid> = self.tasks.iter()
+                    .filter(|t| self.selected_tasks.contains(&t.id))
+                    .map(|t| t.id)
+                    .collect();
+                if !ids.is_empty() {
+                    self.pending_action = Some(PendingAction::DeleteTasks(ids));
+                }
+                Command::none()
+            }
+            
+            Message::DeleteTasks(ids) => {
+                let ids: Vec<Uuid> = ids.into_iter().filter(|id| self.deleting_tasks.insert(*id)).collect();
+                if ids.is_empty() {
+                    return Command::none();
+                }
+                for id in &ids {
+                    if let Some(title) = self.tasks.iter().find(|t| t.id == *id).map(|t| t.title.clone()) {
+                        self.record_event(AppEventKind::TaskDeleted, Some(*id), format!("'{}' deleted", title));
+                    }
+                }
+                self.notify(format!("Deleted {} task(s)", ids.len()), NotificationLevel::Info);
+                // One write for the whole batch
+                Command::perform(self.save_all_tasks(), move |result| Message::TasksDeleted(ids, result))
+            }
+            
+            Message::SetSelectedActive(active) => {
+                let ids: Vec<Uuid> = self.tasks.iter()
+                    .filter(|t| self.selected_tasks.contains(&t.id) && t.is_active != active)
+                    .map(|t| t.id)
+                    .collect();
+                let changed: Vec<Task> = ids.into_iter().filter_map(|id| self.set_task_active(id, active)).collect();
+                let verb = if active { "Activated" } else { "Paused" };
+                if changed.is_empty() {
+                    self.notify(format!("{} 0 tasks: all selected tasks already are", verb), NotificationLevel::Info);
+                    return Command::none();
+                }
+                self.notify(format!("{} {} task(s)", verb, changed.len()), NotificationLevel::Info);
+                
+                let run_now: Vec<Uuid> = changed.iter()
+                    .filter(|t| t.is_active && t.run_on_activate && !self.running_tasks.contains(&t.id))
+                    .map(|t| t.id)
+                    .collect();
+                let mut commands = vec![Command::perform(self.save_all_tasks(), Message::TaskSaved)];
+                commands.extend(run_now.into_iter().map(|id| self.update(Message::ExecuteTask(id))));
+                Command::batch(commands)
+            }
+            
+            Message::RequestResetTaskStats(id) => {
+                self.pending_action = Some(PendingAction::ResetTaskStats(id));
+                Command::none()
+            }
+            
+            // Only the aggregate counters; the execution logs stay as the audit trail
+            Message::ResetTaskStats(id) => {
+                let Some(task) = self.tasks.iter_mut().find(|t| t.id == id) else {
+                    return Command::none();
+                };
+                let detail = format!(
+                    "'{}' run counts reset (were {} ok / {} failed)",
+                    task.title, task.success_count, task.failure_count
+                );
+                task.success_count = 0;
+                task.failure_count = 0;
+                let task = task.clone();
+                
+                self.notify(format!("Stats reset for '{}'", task.title), NotificationLevel::Success);
+                self.record_event(AppEventKind::StatsReset, Some(id), detail);
+                self.stats = self.compute_stats();
+                Command::perform(self.save_all_tasks(), Message::TaskSaved)
+            }
+            
+            Message::ConfirmPendingAction => {
+                match self.pending_action.take() {
+                    Some(PendingAction::RunTask(id)) => self.update(Message::ExecuteTask(id)),
+                    Some(PendingAction::DeleteTask(id)) => self.update(Message::DeleteTask(id)),
+                    Some(PendingAction::DeleteTasks(ids)) => self.update(Message::DeleteTasks(ids)),
+                    Some(PendingAction::ResetTaskStats(id)) => self.update(Message::ResetTaskStats(id)),
+                    Some(PendingAction::SaveDuplicateTitle(_)) => {
+                        self.duplicate_title_confirmed = true;
+                        self.update(Message::CreateTask)
+                    }
+                    None => Command::none(),
+                }
+            }
+            
+            Message::CancelPendingAction => {
+                self.pending_action = None;
+                Command::none()
+            }
+            
+            Message::ToggleTestSchedule(id) => {
+                let now = Local::now();
+                let enabling = !self.test_schedules.contains_key(&id);
+                
+                if enabling {
+                    self.test_schedules.insert(id, TestSchedule {
+                        interval_seconds: TEST_SCHEDULE_INTERVAL,
+                        expires_at: now + chrono::Duration::seconds(TEST_SCHEDULE_DURATION),
+                    });
+                } else {
+                    self.test_schedules.remove(&id);
+                }
+                
+                let mut notification_msg = None;
+                if let Some(task) = self.tasks.iter_mut().find(|t| t.id == id) {
+                    let interval_override = enabling.then_some(TEST_SCHEDULE_INTERVAL);
+                    if task.is_active {
+                        task.next_run = next_run_for(task, interval_override, now);
+                    }
+                    notification_msg = Some(if enabling {
+                        format!("Test schedule enabled for '{}' (every {}s for {} min)",
+                            task.title, TEST_SCHEDULE_INTERVAL, TEST_SCHEDULE_DURATION / 60)
+                    } else {
+                        format!("Test schedule ended for '{}'", task.title)
+                    });
+                }
+                
+                if let Some(msg) = notification_msg {
+                    self.notify(msg, NotificationLevel::Info);
+                }
+                Command::none()
+            }
+            
+            Message::PauseTaskUntil(id, pause) => {
+                let until = pause.resolve(Local::now());
+                let Some(task) = self.tasks.iter_mut().find(|t| t.id == id) else {
+                    return Command::none();
+                };
+                task.paused_until = Some(until);
+                let task_clone = task.clone();
+                
+                let msg = format!("Task '{}' paused until {}", task_clone.title, until.format("%a %b %d, %H:%M"));
+                self.record_event(AppEventKind::TaskPaused, Some(id), msg.clone());
+                self.notify(msg, NotificationLevel::Info);
+                Command::perform(self.save_all_tasks(), Message::TaskSaved)
+            }
+            
+            Message::ResumeTask(id) => {
+                let now = Local::now();
+                let Some(task) = self.tasks.iter_mut().find(|t| t.id == id) else {
+                    return Command::none();
+                };
+                task.paused_until = None;
+                if task.is_active && task.next_run.is_none_or(|next| next < now) {
+                    task.next_run = next_run_for(task, None, now);
+                }
+                let task_clone = task.clone();
+                
+                let msg = format!("Task '{}' resumed", task_clone.title);
+                self.record_event(AppEventKind::TaskResumed, Some(id), msg.clone());
+                self.notify(msg, NotificationLevel::Info);
+                Command::perform(self.save_all_tasks(), Message::TaskSaved)
+            }
+            
+            Message::RecomputeSchedules => {
+                let now = Local::now();
+                let mut updated = Vec::new();
+                for task in self.tasks.iter_mut().filter(|t| t.is_active) {
+                    let interval_override = self.test_schedules.get(&task.id).map(|s| s.interval_seconds);
+                    task.next_run = next_run_for(task, interval_override, now);
+                    updated.push(task.clone());
+                }
+                
+                if updated.is_empty() {
+                    self.notify("No active tasks to reschedule".to_string(), NotificationLevel::Info);
+                    return Command::none();
+                }
+                
+                let msg = format!("Recomputed schedules for {} active task(s)", updated.len());
+                self.record_event(AppEventKind::SchedulesRecomputed, None, msg.clone());
+                self.notify(msg, NotificationLevel::Success);
+                Command::perform(self.save_all_tasks(), Message::TaskSaved)
+            }
+            
+            Message::ToggleCrontabImport => {
+                self.show_crontab_import = !self.show_crontab_import;
+                Command::none()
+            }
+            
+            Message::CrontabEdited(action) => {
+                self.crontab_content.perform(action);
+                Command::none()
+            }
+            
+            Message::ImportCrontab => {
+                let (tasks, errors) = parse_crontab(&self.crontab_content.text());
+                
+                for error in &errors {
+                    self.notify(format!("Skipped crontab {}", error), NotificationLevel::Warning);
+                }
+                
+                if tasks.is_empty() {
+                    self.notify("No crontab entries imported".to_string(), NotificationLevel::Warning);
+                    return Command::none();
+                }
+                
+                self.notify(
+                    format!("Imported {} task(s) from crontab ({} line(s) skipped)", tasks.len(), errors.len()),
+                    NotificationLevel::Success
+                );
+                self.crontab_content = text_editor::Content::new();
+                self.show_crontab_import = false;
+                self.record_event(
+                    AppEventKind::TasksImported,
+                    None,
+                    format!("Imported {} task(s) from crontab: {}", tasks.len(),
+                        tasks.iter().map(|t| t.title.as_str()).collect::<Vec<_>>().join(", "))
+                );
+                
+                self.tasks.extend(tasks);
+                Command::perform(self.save_all_tasks(), Message::TaskSaved)
+            }
+            
+            Message::TaskExecuted(id, result) => {
+                self.running_tasks.retain(|&tid| tid != id);
+                // Runs that end in an error have no result to take the duration from
+                let elapsed_ms = self.run_started.remove(&id)
+                    .map(|started| started.elapsed().as_millis() as u64)
+                    .unwrap_or(0);
+                self.cancel_signals.remove(&id);
+                self.live_output.remove(&id);
+                let attempt = self.retry_attempts.remove(&id).unwrap_or(0);
+                let mut retry_delay = None;
+                let mut alert = Command::none();
+                let mut webhook = Command::none();
+                
+                // Timeouts ("timed out after Ns") and runs that could not start (missing
+                // working directory, unresolvable secret, spawn failure) are failed runs
+                // like any other, retries included. Only cancellation is left as an error below
+                let result = match result {
+                    Err(e) if !matches!(e, AppError::Cancelled) => Ok(ExecutionResult::failed(&e, elapsed_ms)),
+                    other => other,
+                };
+                
+                match result {
+                    Ok(exec_result) => {
+                        if let Some(task) = self.tasks.iter_mut().find(|t| t.id == id) {
+                            let now = Local::now();
+                            task.last_run = Some(now);
+                            task.last_output = exec_result.output.clone();
+                            
+                            let success = exec_result.success;
+                            let task_title = task.title.clone();
+                            let (notify_success, notify_failure) = (task.notify_on_success, task.notify_on_failure);
+                            let max_retries = task.max_retries;
+                            
+                            // Failed attempts with retries left are logged but not counted
+                            let will_retry = !success && attempt < max_retries;
+                            if will_retry {
+                                retry_delay = Some(task.retry_delay_seconds);
+                            }
+                            
+                            let mut recovered = false;
+                            if success {
+                                task.success_count += 1;
+                                task.last_success = Some(now);
+                                recovered = task.failing_since.take().is_some();
+                            } else if !will_retry {
+                                task.failure_count += 1;
+                                task.failing_since.get_or_insert(now);
+                            }
+                            let failure_alert = !success && !will_retry && task.failure_alert_due(now);
+                            
+                            if !will_retry {
+                                let desktop = task.desktop_override.resolve(self.config.desktop_notifications);
+                                let sound = task.sound_override.resolve(self.config.alert_sound);
+                                if desktop || sound {
+                                    alert = Command::perform(
+                                        send_run_alert(task_title.clone(), success, desktop, sound),
+                                        Message::RunAlertSent
+                                    );
+                                }
+                            }
+                            
+                            let completed = success && task.one_shot;
+                            if completed {
+                                task.is_active = false;
+                                task.next_run = None;
+                                task.completed_at = Some(now);
+                            } else if task.is_active {
+                                let interval_override = self.test_schedules.get(&id).map(|t| t.interval_seconds);
+                                task.next_run = next_scheduled_run(task, interval_override, task.next_run, now);
+                            }
+                            
+                            let log = ExecutionLog {
+                                id: Uuid::new_v4(),
+                                task_id: id,
+                                timestamp: Local::now(),
+                                success: exec_result.success,
+                                // Derived from the two streams; see ExecutionLog::output_text
+                                output: String::new(),
+                                duration_ms: exec_result.duration_ms,
+                                attempt,
+                                metadata: exec_result.metadata,
+                                metric: exec_result.metric,
+                                stdout: exec_result.stdout,
+                                stderr: exec_result.stderr,
+                                exit_code: exec_result.exit_code,
+                            };
+                            
+                            // Fired for every run, retries included; its outcome never changes the run's
+                            if let Some(url) = task.webhook_url.clone() {
+                                let payload = WebhookPayload::from_log(&log, &task_title);
+                                let title = task_title.clone();
+                                webhook = Command::perform(
+                                    send_webhook(url, payload),
+                                    move |result| Message::WebhookSent(title, result)
+                                );
+                            }
+                            
+                            // Persisted in batches by the Tick-driven flush
+                            self.push_log(log);
+                            self.dirty_tasks.insert(id);
+                            if completed {
+                                self.record_event(
+                                    AppEventKind::TaskDeactivated,
+                                    Some(id),
+                                    format!("'{}' completed (one-shot)", task_title)
+                                );
+                            }
+                            
+                            if let Some(delay) = retry_delay {
+                                self.retry_attempts.insert(id, attempt + 1);
+                                if notify_failure {
+                                    self.notify(
+                                        format!("Task '{}' failed, retry {}/{} in {}s", task_title, attempt + 1, max_retries, delay),
+                                        NotificationLevel::Warning
+                                    );
+                                }
+                            } else if recovered && notify_success {
+                                self.notify(
+                                    format!("Task '{}' recovered", task_title),
+                                    NotificationLevel::Success
+                                );
+                            } else if success && notify_success {
+                                self.notify(
+                                    format!("Task '{}' completed successfully", task_title),
+                                    NotificationLevel::Success
+                                );
+                            } else if failure_alert && notify_failure {
+                                self.notify(
+                                    format!("Task '{}' failed", task_title),
+                                    NotificationLevel::Error
+                                );
+                            }
+                            
+                        }
+                    }
+                    // Logged as a failed run but not counted against the task, and no retry
+                    Err(_) => {
+                        self.catch_up_runs.remove(&id);
+                        let now = Local::now();
+                        let interval_override = self.test_schedules.get(&id).map(|t| t.interval_seconds);
+                        if let Some(task) = self.tasks.iter_mut().find(|t| t.id == id) {
+                            if task.is_active {
+                                task.next_run = next_scheduled_run(task, interval_override, task.next_run, now);
+                            }
+                            let title = task.title.clone();
+                            self.push_log(ExecutionLog {
+                                id: Uuid::new_v4(),
+                                task_id: id,
+                                timestamp: now,
+                                success: false,
+                                output: AppError::Cancelled.to_string(),
+                                duration_ms: elapsed_ms,
+                                attempt,
+                                metadata: None,
+                                metric: None,
+                                stdout: String::new(),
+                                stderr: String::new(),
+                                exit_code: None,
+                            });
+                            self.dirty_tasks.insert(id);
+                            self.notify(format!("Task '{}' cancelled", title), NotificationLevel::Info);
+                        }
+                    }
+                }
+                
+                let mut commands = vec![alert, webhook, self.drain_run_queue()];
+                if let Some(delay) = retry_delay {
+                    commands.push(Command::perform(
+                        async move { tokio::time::sleep(Duration::from_secs(delay)).await },
+                        move |_| Message::RetryTask(id)
+                    ));
+                } else {
+                    if self.startup_current == Some(id) {
+                        commands.push(self.advance_startup_runs());
+                    }
+                    let active = self.tasks.iter().any(|t| t.id == id && t.is_active);
+                    if let Some(remaining) = self.catch_up_runs.remove(&id).filter(|_| active) {
+                        if remaining > 1 {
+                            self.catch_up_runs.insert(id, remaining - 1);
+                        }
+                        commands.push(self.update(Message::ExecuteTask(id)));
+                    }
+                }
+                Command::batch(commands)
+            }
+            
+            Message::TasksLoaded(Ok(loaded)) => {
+                self.warn_if_restored(&loaded);
+                let mut tasks = loaded.value;
+                log::debug!("Tasks loaded: {} tasks", tasks.len());
+                for task in &tasks {
+                    log::trace!("  - {} ({})", task.title, task.id);
+                }
+                let reassigned = dedupe_task_ids(&mut tasks);
+                // Keep unflushed in-memory changes instead of the stale copies on disk
+                let dirty: Vec<Task> = self.tasks.iter()
+                    .filter(|t| self.dirty_tasks.contains(&t.id))
+                    .cloned()
+                    .collect();
+                self.tasks = tasks;
+                for task in dirty {
+                    if let Some(existing) = self.tasks.iter_mut().find(|t| t.id == task.id) {
+                        *existing = task;
+                    }
+                }
+                self.tasks_loaded = true;
+                self.stats = self.compute_stats();
+                self.restore_from_logs();
+                let startup = self.start_startup_runs();
+                
+                if reassigned.is_empty() {
+                    return startup;
+                }
+                self.notify(
+                    format!("Found duplicate task ids; assigned new ids to: {}", reassigned.join(", ")),
+                    NotificationLevel::Warning
+                );
+                Command::batch(vec![
+                    startup,
+                    Command::perform(self.save_all_tasks(), Message::TaskSaved),
+                ])
+            }
+            
+            Message::TasksLoaded(Err(e)) => {
+                self.notify(format!("Failed to load tasks: {}", e), NotificationLevel::Error);
+                Command::none()
+            }
+            
+            Message::LogsLoaded(Ok(loaded)) => {
+                self.warn_if_restored(&loaded);
+                self.logs = loaded.value.into();
+                self.log_lines_on_disk = self.logs.len();
+                if self.logs.len() > self.config.max_logs {
+                    let excess = self.logs.len() - self.config.max_logs;
+                    self.logs.drain(..excess);
+                }
+                self.rebuild_duration_stats();
+                self.restore_from_logs();
+                Command::none()
+            }
+            
+            Message::LogsLoaded(Err(_)) => {
+                Command::none()
+            }
+            
+            Message::EventsLoaded(Ok(loaded)) => {
+                self.warn_if_restored(&loaded);
+                // Keep anything recorded before the file finished loading
+                let recorded = std::mem::replace(&mut self.events, loaded.value.into());
+                self.events.extend(recorded);
+                Command::none()
+            }
+            
+            Message::EventsLoaded(Err(_)) => {
+                Command::none()
+            }
+            
+            // The file only exists if the feed was persisted; older entries
+            // go first so anything notified during startup stays newest
+            Message::NotificationsLoaded(Ok(loaded)) => {
+                self.warn_if_restored(&loaded);
+                let recorded = std::mem::replace(&mut self.activity, loaded.value.into());
+                self.activity.extend(recorded);
+                while self.activity.len() > MAX_ACTIVITY_ENTRIES {
+                    self.activity.pop_front();
+                }
+                Command::none()
+            }
+            
+            Message::NotificationsLoaded(Err(e)) => {
+                log::error!("Failed to load notifications: {}", e);
+                Command::none()
+            }
+            
+            // The in-memory list is what was written, so there is nothing to reload
+            Message::TaskSaved(Ok(())) => {
+                self.stats = self.compute_stats();
+                Command::none()
+            }
+            
+            Message::TaskSaved(Err(e)) => {
+                self.notify(format!("Failed to save: {}", e), NotificationLevel::Error);
+                Command::none()
+            }
+            
+            Message::TaskFormSaved(result) => {
+                self.saving_task = false;
+                self.update(Message::TaskSaved(result))
+            }
+            
+            Message::TaskDeleted(id, Ok(())) => {
+                self.deleting_tasks.remove(&id);
+                self.selected_tasks.remove(&id);
+                self.tasks.retain(|t| t.id != id);
+                self.stats = self.compute_stats();
+                self.purge_task_logs(&[id]);
+                Command::none()
+            }
+            
+            Message::TasksDeleted(ids, Ok(())) => {
+                for id in &ids {
+                    self.deleting_tasks.remove(id);
+                    self.selected_tasks.remove(id);
+                }
+                self.tasks.retain(|t| !ids.contains(&t.id));
+                self.stats = self.compute_stats();
+                self.purge_task_logs(&ids);
+                Command::none()
+            }
+            
+            Message::TasksDeleted(ids, Err(e)) => {
+                for id in &ids {
+                    self.deleting_tasks.remove(id);
+                }
+                self.notify(format!("Failed to delete tasks: {}", e), NotificationLevel::Error);
+                Command::none()
+            }
+            
+            Message::TaskDeleted(id, Err(e)) => {
+                self.deleting_tasks.remove(&id);
+                self.notify(format!("Failed to delete: {}", e), NotificationLevel::Error);
+                Command::none()
+            }
+            
+            Message::ConfigLoaded(Ok(loaded)) => {
+                self.warn_if_restored(&loaded);
+                self.config = loaded.value;
+                self.reset_settings_draft();
+                self.config_loaded = true;
+                apply_log_level(self.config.log_level);
+                if let Some(dir) = self.config.data_dir.clone() {
+                    let _ = CONFIGURED_DATA_DIR.set(dir);
+                }
+                
+                let mut commands = vec![self.start_startup_runs(), load_data_files()];
+                if self.config.start_minimized {
+                    commands.push(window::minimize(window::Id::MAIN, true));
+                }
+                if self.config.theme == AppTheme::System {
+                    commands.push(Command::perform(detect_system_theme(), Message::SystemThemeDetected));
+                }
+                if self.config.pause_on_battery {
+                    commands.push(Command::perform(detect_on_battery(), Message::PowerSourceDetected));
+                }
+                if !self.config.onboarded {
+                    commands.push(Command::perform(tasks_file_missing(), Message::OnboardingChecked));
+                }
+                Command::batch(commands)
+            }
+            
+            Message::ConfigLoaded(Err(_)) => {
+                self.config_loaded = true;
+                Command::batch(vec![
+                    self.start_startup_runs(),
+                    load_data_files(),
+                    Command::perform(tasks_file_missing(), Message::OnboardingChecked),
+                ])
+            }
+            
+            // Only a brand-new install gets the panel; upgrading users already have tasks.json
+            Message::OnboardingChecked(missing) => {
+                self.show_onboarding = missing && !self.config.onboarded;
+                Command::none()
+            }
+            
+            Message::SeedTemplates => {
+                let tasks: Vec<Task> = self.templates.iter()
+                    .filter(|tpl| !self.tasks.iter().any(|t| t.title == tpl.name && t.command == tpl.command))
+                    .map(TaskTemplate::to_task)
+                    .collect();
+                let onboarded = self.update(Message::DismissOnboarding);
+                if tasks.is_empty() {
+                    return onboarded;
+                }
+                
+                self.notify(
+                    format!("Added {} template task(s), paused until you start them", tasks.len()),
+                    NotificationLevel::Success
+                );
+                self.record_event(
+                    AppEventKind::TasksImported,
+                    None,
+                    format!("Added {} template task(s): {}", tasks.len(),
+                        tasks.iter().map(|t| t.title.as_str()).collect::<Vec<_>>().join(", "))
+                );
+                self.tasks.extend(tasks);
+                Command::batch(vec![onboarded, Command::perform(self.save_all_tasks(), Message::TaskSaved)])
+            }
+            
+            // Also on the draft, or the next Save Settings would write it back as false
+            Message::DismissOnboarding => {
+                self.show_onboarding = false;
+                self.config.onboarded = true;
+                self.settings_draft.onboarded = true;
+                Command::perform(save_config(self.config.clone()), Message::OnboardingSaved)
+            }
+            
+            Message::OnboardingSaved(Ok(())) => Command::none(),
+            
+            Message::OnboardingSaved(Err(e)) => {
+                self.notify(format!("Failed to save settings: {}", e), NotificationLevel::Error);
+                Command::none()
+            }
+            
+            Message::ConfigSaved(Ok(())) => {
+                self.saving_settings = false;
+                self.notify("Settings saved".to_string(), NotificationLevel::Success);
+                self.screen = Screen::Overview;
+                Command::none()
+            }
+            
+            Message::ConfigSaved(Err(e)) => {
+                self.saving_settings = false;
+                self.notify(format!("Failed to save settings: {}", e), NotificationLevel::Error);
+                Command::none()
+            }
+            
+            Message::LoginItemUpdated(Ok(())) => Command::none(),
+            
+            Message::LoginItemUpdated(Err(e)) => {
+                self.notify(format!("Failed to update login item: {}", e), NotificationLevel::Error);
+                Command::none()
+            }
+            
+            Message::SelectTemplate(idx) => {
+                if let Some(template) = self.templates.get(idx) {
+                    self.title_input = template.name.clone();
+                    self.command_input = template.command.clone();
+                    self.interval_input = template.interval.to_string();
+                    self.cron_mode_input = false;
+                    self.notify(format!("Template loaded: {}", template.name), NotificationLevel::Info);
+                }
+                Command::none()
+            }
+            
+            // A template with the same name is replaced rather than duplicated
+            Message::SaveFormAsTemplate => {
+                let name = self.title_input.trim().to_string();
+                let command = self.command_input.trim().to_string();
+                if name.is_empty() || command.is_empty() {
+                    self.notify("Enter a title and command to save as a template".to_string(), NotificationLevel::Warning);
+                    return Command::none();
+                }
+                if self.cron_mode_input {
+                    self.notify("Templates keep an interval schedule; untick Cron to save one".to_string(), NotificationLevel::Warning);
+                    return Command::none();
+                }
+                let interval = match self.interval_input.parse::<u64>() {
+                    Ok(n) if n > 0 => n.min(MAX_INTERVAL_SECONDS),
+                    _ => {
+                        self.notify("Invalid interval".to_string(), NotificationLevel::Warning);
+                        return Command::none();
+                    }
+                };
+                
+                let template = TaskTemplate {
+                    description: format!("Every {}", Self::format_duration(interval)),
+                    name,
+                    command,
+                    interval,
+                };
+                if let Some(existing) = self.templates.iter_mut().find(|t| t.name == template.name) {
+                    let message = format!("Template '{}' updated", template.name);
+                    *existing = template;
+                    self.notify(message, NotificationLevel::Success);
+                } else {
+                    self.notify(format!("Saved template '{}'", template.name), NotificationLevel::Success);
+                    self.templates.push(template);
+                }
+                Command::perform(save_templates(self.templates.clone()), Message::TemplatesSaved)
+            }
+            
+            Message::DeleteTemplate(idx) => {
+                if idx >= self.templates.len() {
+                    return Command::none();
+                }
+                let removed = self.templates.remove(idx);
+                self.notify(format!("Deleted template '{}'", removed.name), NotificationLevel::Info);
+                Command::perform(save_templates(self.templates.clone()), Message::TemplatesSaved)
+            }
+            
+            Message::TemplatesLoaded(Ok(loaded)) => {
+                self.warn_if_restored(&loaded);
+                self.templates = loaded.value;
+                Command::none()
+            }
+            
+            Message::TemplatesLoaded(Err(e)) => {
+                self.notify(format!("Failed to load templates: {}", e), NotificationLevel::Error);
+                Command::none()
+            }
+            
+            Message::TemplatesSaved(Ok(())) => Command::none(),
+            
+            Message::TemplatesSaved(Err(e)) => {
+                self.notify(format!("Failed to save templates: {}", e), NotificationLevel::Error);
+                Command::none()
+            }
+            
+            // Each keystroke restarts the timer; only the newest one applies
+            Message::SearchInput(s) => {
+                self.search_input = s;
+                self.search_seq += 1;
+                let seq = self.search_seq;
+                Command::perform(
+                    async { tokio::time::sleep(SEARCH_DEBOUNCE).await },
+                    move |_| Message::ApplySearch(seq)
+                )
+            }
+            
+            Message::ApplySearch(seq) => {
+                if seq == self.search_seq && self.search_query != self.search_input {
+                    self.search_query = self.search_input.clone();
+                    self.task_page = 0;
+                }
+                Command::none()
+            }
+            
+            Message::ShowTasksFiltered(filter) => {
+                self.filter = filter;
+                self.task_page = 0;
+                self.update(Message::ChangeScreen(Screen::Tasks))
+            }
+            
+            Message::CloneTask(id) => {
+                let Some(original) = self.tasks.iter().find(|t| t.id == id).cloned() else {
+                    return Command::none();
+                };
+                
+                let mut task = original.clone();
+                task.id = Uuid::new_v4();
+                task.title = format!("{} (copy)", original.title);
+                task.is_active = false;
+                task.last_run = None;
+                task.next_run = None;
+                task.created_at = Local::now();
+                task.success_count = 0;
+                task.failure_count = 0;
+                task.last_output.clear();
+                task.paused_until = None;
+                task.failing_since = None;
+                task.last_success = None;
+                task.completed_at = None;
+                
+                self.notify(format!("Task '{}' created", task.title), NotificationLevel::Success);
+                self.record_event(
+                    AppEventKind::TaskCreated,
+                    Some(task.id),
+                    format!("'{}' cloned from '{}'", task.title, original.title)
+                );
+                self.tasks.push(task);
+                
+                Command::perform(self.save_all_tasks(), Message::TaskSaved)
+            }
+            
+            Message::MoveTaskUp(id) => self.move_task(id, true),
+            
+            Message::MoveTaskDown(id) => self.move_task(id, false),
+            
+            Message::CompareTask(id) => {
+                match self.compare_selection.take() {
+                    None => self.compare_selection = Some(id),
+                    Some(first) if first == id => {}
+                    Some(first) => self.screen = Screen::Compare(first, id),
+                }
+                Command::none()
+            }
+            
+            Message::TaskPageChanged(page) => {
+                self.task_page = page;
+                Command::none()
+            }
+            
+            Message::FilterChanged(filter) => {
+                self.task_page = 0;
+                self.filter = filter;
+                Command::none()
+            }
+            
+            Message::TaskSortChanged(sort) => {
+                self.task_page = 0;
+                self.task_sort = sort;
+                Command::none()
+            }
+            
+            Message::ToggleSortDirection => {
+                self.task_page = 0;
+                self.sort_descending = !self.sort_descending;
+                Command::none()
+            }
+            
+            Message::ViewTaskLogs(id) => {
+                self.log_page = 0;
+                self.screen = Screen::Logs(Some(id));
+                Command::none()
+            }
+            
+            Message::SaveLogOutput(log_id) => {
+                let Some(log) = self.logs.iter().find(|l| l.id == log_id) else {
+                    return Command::none();
+                };
+                let task_title = self.tasks.iter()
+                    .find(|t| t.id == log.task_id)
+                    .map(|t| t.title.as_str())
+                    .unwrap_or("task");
+                let file_name = format!(
+                    "{}-{}.txt",
+                    sanitize_file_name(task_title),
+                    log.timestamp.format("%Y%m%d-%H%M%S")
+                );
+                Command::perform(save_text_file(file_name, log.output_text().into_owned()), Message::LogOutputSaved)
+            }
+            
+            Message::LogOutputSaved(Ok(Some(path))) => {
+                self.notify(format!("Output saved to {}", path.display()), NotificationLevel::Success);
+                Command::none()
+            }
+            
+            Message::LogOutputSaved(Ok(None)) => Command::none(),
+            
+            Message::LogOutputSaved(Err(e)) => {
+                self.notify(format!("Failed to save output: {}", e), NotificationLevel::Error);
+                Command::none()
+            }
+            
+            Message::LogStatusFilterChanged(filter) => {
+                self.log_status_filter = filter;
+                self.log_page = 0;
+                Command::none()
+            }
+            
+            Message::LogRangeChanged(range) => {
+                self.log_range = range;
+                self.log_page = 0;
+                Command::none()
+            }
+            
+            Message::LogSearchInput(s) => {
+                self.log_search = s;
+                self.log_page = 0;
+                Command::none()
+            }
+            
+            Message::LogPageChanged(page) => {
+                self.log_page = page;
+                Command::none()
+            }
+            
+            Message::ToggleLogDay(day) => {
+                if !self.collapsed_log_days.remove(&day) {
+                    self.collapsed_log_days.insert(day);
+                }
+                Command::none()
+            }
+            
+            Message::LogPageSizeChanged(size) => {
+                self.log_page_size = size;
+                self.log_page = 0;
+                Command::none()
+            }
+            
+            Message::ExportLogBundle(task_id) => {
+                let logs: Vec<ExecutionLog> = self.filtered_logs(task_id).into_iter().cloned().collect();
+                if logs.is_empty() {
+                    self.notify("No logs match the current filters".to_string(), NotificationLevel::Warning);
+                    return Command::none();
+                }
+                
+                let task_title = task_id.and_then(|id| self.tasks.iter().find(|t| t.id == id))
+                    .map(|t| t.title.clone());
+                let readme = format!(
+                    "Task with Me log export\n\nApp version: {}\nExported: {}\nTask: {}\nStatus: {}\nDate range: {}\nSearch: {}\nEntries: {}\n",
+                    env!("CARGO_PKG_VERSION"),
+                    Local::now().format("%Y-%m-%d %H:%M:%S"),
+                    task_title.as_deref().unwrap_or("All tasks"),
+                    self.log_status_filter,
+                    self.log_range,
+                    if self.log_search.is_empty() { "(none)" } else { self.log_search.as_str() },
+                    logs.len(),
+                );
+                let file_name = format!(
+                    "{}-logs-{}.zip",
+                    sanitize_file_name(task_title.as_deref().unwrap_or("all")),
+                    Local::now().format("%Y%m%d-%H%M%S")
+                );
+                Command::perform(export_log_bundle(file_name, logs, readme), Message::LogBundleExported)
+            }
+            
+            Message::ExportLogsCsv(task_id) => {
+                let logs: Vec<ExecutionLog> = self.filtered_logs(task_id).into_iter().cloned().collect();
+                if logs.is_empty() {
+                    self.notify("No logs match the current filters".to_string(), NotificationLevel::Warning);
+                    return Command::none();
+                }
+                
+                let task_title = task_id.and_then(|id| self.tasks.iter().find(|t| t.id == id))
+                    .map(|t| t.title.clone());
+                let csv = logs_to_csv(&logs, |id| {
+                    self.tasks.iter().find(|t| t.id == id).map(|t| t.title.clone()).unwrap_or_default()
+                });
+                let file_name = format!(
+                    "{}-logs-{}.csv",
+                    sanitize_file_name(task_title.as_deref().unwrap_or("all")),
+                    Local::now().format("%Y%m%d-%H%M%S")
+                );
+                Command::perform(save_export_file(file_name, "CSV", "csv", csv), Message::LogBundleExported)
+            }
+            
+            Message::ExportTaskReport(id) => {
+                let Some(task) = self.tasks.iter().find(|t| t.id == id) else {
+                    return Command::none();
+                };
+                let report = self.task_report(task, Local::now());
+                let file_name = format!(
+                    "{}-report-{}.md",
+                    sanitize_file_name(&task.title),
+                    Local::now().format("%Y%m%d-%H%M%S")
+                );
+                Command::perform(save_export_file(file_name, "Markdown", "md", report), Message::LogBundleExported)
+            }
+            
+            Message::LogBundleExported(Ok(Some(path))) => {
+                self.notify(format!("Logs exported to {}", path.display()), NotificationLevel::Success);
+                Command::none()
+            }
+            
+            Message::LogBundleExported(Ok(None)) => Command::none(),
+            
+            Message::LogBundleExported(Err(e)) => {
+                self.notify(format!("Failed to export logs: {}", e), NotificationLevel::Error);
+                Command::none()
+            }
+            
+            Message::AuditSearchInput(s) => {
+                self.audit_search = s;
+                Command::none()
+            }
+            
+            Message::AuditKindChanged(kind) => {
+                self.audit_kind = kind;
+                Command::none()
+            }
+            
+            Message::AuditRangeChanged(range) => {
+                self.audit_range = range;
+                Command::none()
+            }
+            
+            Message::ExportAuditEvents { csv } => {
+                let events: Vec<AppEvent> = self.filtered_events().into_iter().cloned().collect();
+                if events.is_empty() {
+                    self.notify("No audit events match the current filters".to_string(), NotificationLevel::Warning);
+                    return Command::none();
+                }
+                
+                let stamp = Local::now().format("%Y%m%d-%H%M%S");
+                let export = if csv {
+                    save_export_file(format!("audit-{}.csv", stamp), "CSV", "csv", events_to_csv(&events))
+                } else {
+                    let content = match serde_json::to_string_pretty(&events) {
+                        Ok(content) => content,
+                        Err(e) => {
+                            self.notify(format!("Failed to export audit log: {}", e), NotificationLevel::Error);
+                            return Command::none();
+                        }
+                    };
+                    save_export_file(format!("audit-{}.json", stamp), "JSON", "json", content)
+                };
+                Command::perform(export, Message::AuditExported)
+            }
+            
+            Message::AuditExported(Ok(Some(path))) => {
+                self.notify(format!("Audit log exported to {}", path.display()), NotificationLevel::Success);
+                Command::none()
+            }
+            
+            Message::AuditExported(Ok(None)) => Command::none(),
+            
+            Message::AuditExported(Err(e)) => {
+                self.notify(format!("Failed to export audit log: {}", e), NotificationLevel::Error);
+                Command::none()
+            }
+            
+            Message::ExportTasks => {
+                let content = match serde_json::to_string_pretty(&self.tasks) {
+                    Ok(content) => content,
+                    Err(e) => {
+                        self.notify(format!("Failed to export tasks: {}", e), NotificationLevel::Error);
+                        return Command::none();
+                    }
+                };
+                let file_name = format!("tasks-{}.json", Local::now().format("%Y%m%d-%H%M%S"));
+                Command::perform(save_export_file(file_name, "JSON", "json", content), Message::TasksExported)
+            }
+            
+            Message::TasksExported(Ok(Some(path))) => {
+                self.notify(format!("{} task(s) exported to {}", self.tasks.len(), path.display()), NotificationLevel::Success);
+                Command::none()
+            }
+            
+            Message::TasksExported(Ok(None)) => Command::none(),
+            
+            Message::TasksExported(Err(e)) => {
+                self.notify(format!("Failed to export tasks: {}", e), NotificationLevel::Error);
+                Command::none()
+            }
+            
+            Message::ImportTasks => {
+                Command::perform(read_tasks_file(), Message::TasksFileRead)
+            }
+            
+            Message::TasksFileRead(Ok(Some(incoming))) => {
+                let (tasks, skipped) = prepare_imported_tasks(incoming, &self.tasks);
+                
+                if tasks.is_empty() {
+                    self.notify(
+                        format!("No tasks imported ({} already exist)", skipped),
+                        NotificationLevel::Warning
+                    );
+                    return Command::none();
+                }
+                
+                self.notify(
+                    format!("Imported {} task(s) ({} duplicate(s) skipped)", tasks.len(), skipped),
+                    NotificationLevel::Success
+                );
+                self.record_event(
+                    AppEventKind::TasksImported,
+                    None,
+                    format!("Imported {} task(s) from file: {}", tasks.len(),
+                        tasks.iter().map(|t| t.title.as_str()).collect::<Vec<_>>().join(", "))
+                );
+                
+                self.tasks.extend(tasks);
+                Command::perform(self.save_all_tasks(), Message::TaskSaved)
+            }
+            
+            Message::TasksFileRead(Ok(None)) => Command::none(),
+            
+            Message::TasksFileRead(Err(e)) => {
+                self.notify(format!("Failed to import tasks: {}", e), NotificationLevel::Error);
+                Command::none()
+            }
+            
+            Message::CloseNotification(id) => {
+                self.notifications.retain(|n| n.id != id);
+                Command::none()
+            }
+            
+            Message::ClearNotifications => {
+                self.notifications.clear();
+                Command::none()
+            }
+            
+            // Saved right away on its own, leaving other unsaved settings alone
+            Message::ThemeChanged(theme) => {
+                self.config.theme = theme.clone();
+                self.settings_draft.theme = theme;
+                let save = Command::perform(save_config(self.config.clone()), Message::ThemeSaved);
+                if self.config.theme == AppTheme::System {
+                    Command::batch(vec![save, self.update(Message::CheckSystemTheme)])
+                } else {
+                    save
+                }
+            }
+            
+            Message::CheckSystemTheme => {
+                Command::perform(detect_system_theme(), Message::SystemThemeDetected)
+            }
+            
+            Message::SystemThemeDetected(dark) => {
+                self.system_dark = dark;
+                Command::none()
+            }
+            
+            Message::CheckPowerSource => {
+                Command::perform(detect_on_battery(), Message::PowerSourceDetected)
+            }
+            
+            Message::PowerSourceDetected(on_battery) => {
+                let was_suspended = self.battery_suspended();
+                self.on_battery = on_battery;
+                self.notify_battery_transition(was_suspended);
+                Command::none()
+            }
+            
+            Message::ThemeSaved(Ok(())) => Command::none(),
+            
+            Message::ThemeSaved(Err(e)) => {
+                self.notify(format!("Failed to save theme: {}", e), NotificationLevel::Error);
+                Command::none()
+            }
+            
+            Message::MissedRunPolicyChanged(policy) => {
+                self.settings_draft.missed_run_policy = policy;
+                Command::none()
+            }
+            
+            Message::DuplicateTitlePolicyChanged(policy) => {
+                self.settings_draft.duplicate_titles = policy;
+                Command::none()
+            }
+            
+            Message::LogLevelChanged(level) => {
+                self.settings_draft.log_level = level;
+                Command::none()
+            }
+            
+            Message::RefreshIntervalChanged(s) => {
+                self.refresh_input = s;
+                Command::none()
+            }
+            
+            Message::MaxLogsChanged(s) => {
+                self.max_logs_input = s;
+                Command::none()
+            }
+            
+            Message::PersistIntervalChanged(s) => {
+                self.persist_interval_input = s;
+                Command::none()
+            }
+            
+            Message::TaskPageSizeChanged(s) => {
+                self.task_page_size_input = s;
+                Command::none()
+            }
+            
+            Message::MaxConcurrentChanged(s) => {
+                self.max_concurrent_input = s;
+                Command::none()
+            }
+            
+            Message::GlobalMaxOutputChanged(s) => {
+                self.global_max_output_input = s;
+                Command::none()
+            }
+            
+            Message::UpdateUrlChanged(s) => {
+                self.update_url_input = s;
+                Command::none()
+            }
+            
+            Message::CheckForUpdates => {
+                let url = self.update_url_input.trim().to_string();
+                if url.is_empty() {
+                    self.update_status = Some("Set a release URL to check for updates".to_string());
+                    return Command::none();
+                }
+                self.update_status = Some("Checking...".to_string());
+                Command::perform(check_for_updates(url), Message::UpdateChecked)
+            }
+            
+            // Failures only update the status line; being offline is not worth a notification
+            Message::UpdateChecked(result) => {
+                self.update_status = Some(match result {
+                    Ok(Some(latest)) => format!("Version {} is available", latest),
+                    Ok(None) => "You are on the latest version".to_string(),
+                    Err(e) => format!("Could not check for updates ({})", e),
+                });
+                Command::none()
+            }
+            
+            Message::CommandPrefixChanged(s) => {
+                self.command_prefix_input = s;
+                Command::none()
+            }
+            
+            Message::DataDirChanged(s) => {
+                self.data_dir_input = s;
+                Command::none()
+            }
+            
+            Message::ShellChanged(s) => {
+                self.shell_input = s;
+                Command::none()
+            }
+            
+            Message::ShellArgsChanged(s) => {
+                self.shell_args_input = s;
+                Command::none()
+            }
+            
+            Message::ShellPresetSelected(shell) => {
+                self.shell_input = shell.to_string();
+                self.shell_args_input = default_shell_args(shell).join(" ");
+                Command::none()
+            }
+            
+            Message::SecretNameInput(s) => {
+                self.secret_name_input = s;
+                Command::none()
+            }
+            
+            Message::SecretValueInput(s) => {
+                self.secret_value_input = s;
+                Command::none()
+            }
+            
+            Message::SaveSecret => {
+                let name = self.secret_name_input.trim().to_string();
+                if !is_valid_secret_name(&name) {
+                    self.notify(
+                        "Secret names may only contain letters, digits, '_' and '-'".to_string(),
+                        NotificationLevel::Warning
+                    );
+                    return Command::none();
+                }
+                if self.secret_value_input.is_empty() {
+                    self.notify("Secret value cannot be empty".to_string(), NotificationLevel::Warning);
+                    return Command::none();
+                }
+                
+                let value = std::mem::take(&mut self.secret_value_input);
+                self.secret_name_input.clear();
+                Command::perform(store_secret(name, value), Message::SecretSaved)
+            }
+            
+            Message::DeleteSecret(name) => {
+                Command::perform(delete_secret(name), Message::SecretDeleted)
+            }
+            
+            Message::SecretSaved(Ok(name)) => {
+                self.notify(format!("Secret '{}' saved to the system keyring", name), NotificationLevel::Success);
+                self.record_event(AppEventKind::SecretChanged, None, format!("Secret '{}' saved", name));
+                if self.config.secret_names.contains(&name) {
+                    return Command::none();
+                }
+                self.config.secret_names.push(name.clone());
+                self.settings_draft.secret_names.push(name);
+                Command::perform(save_config(self.config.clone()), Message::SecretIndexSaved)
+            }
+            
+            Message::SecretDeleted(Ok(name)) => {
+                self.notify(format!("Secret '{}' deleted", name), NotificationLevel::Info);
+                self.record_event(AppEventKind::SecretChanged, None, format!("Secret '{}' deleted", name));
+                self.config.secret_names.retain(|n| *n != name);
+                self.settings_draft.secret_names.retain(|n| *n != name);
+                Command::perform(save_config(self.config.clone()), Message::SecretIndexSaved)
+            }
+            
+            Message::SecretSaved(Err(e)) | Message::SecretDeleted(Err(e)) => {
+                self.notify(format!("Keyring error: {}", e), NotificationLevel::Error);
+                Command::none()
+            }
+            
+            Message::SecretIndexSaved(Ok(())) => Command::none(),
+            
+            Message::SecretIndexSaved(Err(e)) => {
+                self.notify(format!("Failed to save secret list: {}", e), NotificationLevel::Error);
+                Command::none()
+            }
+            
+            // Rounded to the 10% step so repeated steps don't drift
+            Message::UiScaleChanged(scale) => {
+                self.settings_draft.ui_scale = ((scale * 10.0).round() / 10.0).clamp(MIN_UI_SCALE, MAX_UI_SCALE);
+                Command::none()
+            }
+            
+            Message::SuccessRatePrecisionChanged(precision) => {
+                self.settings_draft.success_rate_precision = precision;
+                Command::none()
+            }
+            
+            Message::StartAtLoginChanged(enabled) => {
+                self.settings_draft.start_at_login = enabled;
+                Command::none()
+            }
+            
+            Message::StartMinimizedChanged(enabled) => {
+                self.settings_draft.start_minimized = enabled;
+                Command::none()
+            }
+            
+            Message::LogToFileChanged(enabled) => {
+                self.settings_draft.log_to_file = enabled;
+                Command::none()
+            }
+            
+            Message::PauseOnBatteryChanged(enabled) => {
+                self.settings_draft.pause_on_battery = enabled;
+                Command::none()
+            }
+            
+            Message::KeepOrphanedLogsChanged(enabled) => {
+                self.settings_draft.keep_orphaned_logs = enabled;
+                Command::none()
+            }
+            
+            Message::PersistNotificationsChanged(enabled) => {
+                self.settings_draft.persist_notifications = enabled;
+                Command::none()
+            }
+            
+            Message::ClearActivity => {
+                self.activity.clear();
+                self.activity_dirty = self.config.persist_notifications;
+                Command::none()
+            }
+            
+            Message::DesktopNotificationsChanged(enabled) => {
+                self.settings_draft.desktop_notifications = enabled;
+                Command::none()
+            }
+            
+            Message::AlertSoundChanged(enabled) => {
+                self.settings_draft.alert_sound = enabled;
+                Command::none()
+            }
+            
+            Message::ConfirmBeforeRunChanged(enabled) => {
+                self.settings_draft.confirm_before_run = enabled;
+                Command::none()
+            }
+            
+            Message::ToggleTrustedSession => {
+                self.trusted_session = !self.trusted_session;
+                let msg = if self.trusted_session {
+                    "Trusted session enabled: run confirmations are skipped until restart"
+                } else {
+                    "Trusted session disabled"
+                };
+                self.notify(msg.to_string(), NotificationLevel::Info);
+                Command::none()
+            }
+            
+            Message::CalendarRangeChanged(range) => {
+                self.calendar_range = range;
+                Command::none()
+            }
+            
+            Message::SaveSettings => {
+                if self.saving_settings {
+                    return Command::none();
+                }
+                let changed = self.unsaved_settings();
+                if !changed.is_empty() {
+                    self.record_event(AppEventKind::SettingsChanged, None, format!("Changed: {}", changed.join(", ")));
+                }
+                let was_logging_to_file = self.config.log_to_file;
+                let was_keeping_activity = self.config.persist_notifications;
+                let was_suspended = self.battery_suspended();
+                self.config = self.draft_config();
+                self.reset_settings_draft();
+                self.saving_settings = true;
+                apply_log_level(self.config.log_level);
+                // Write out the logs collected while file logging was off
+                if self.config.log_to_file && !was_logging_to_file {
+                    self.logs_dirty = true;
+                }
+                if self.config.persist_notifications && !was_keeping_activity {
+                    self.activity_dirty = true;
+                }
+                // Apply a lowered limit now, keeping the newest entries
+                if self.logs.len() > self.config.max_logs {
+                    let excess = self.logs.len() - self.config.max_logs;
+                    self.logs.drain(..excess);
+                    self.logs_dirty = true;
+                    self.rebuild_duration_stats();
+                }
+                
+                self.notify_battery_transition(was_suspended);
+                
+                let mut commands = vec![
+                    Command::perform(save_config(self.config.clone()), Message::ConfigSaved),
+                    Command::perform(set_start_at_login(self.config.start_at_login), Message::LoginItemUpdated),
+                ];
+                // Read the power source now rather than at the next poll
+                if self.config.pause_on_battery {
+                    commands.push(Command::perform(detect_on_battery(), Message::PowerSourceDetected));
+                }
+                Command::batch(commands)
+            }
+            
+            Message::DiscardSettings => {
+                self.reset_settings_draft();
+                Command::none()
+            }
+            
+            Message::Tick => {
+                let due = self.last_flush.elapsed() >= Duration::from_secs(self.config.persist_interval);
+                if due && self.has_unflushed_changes() {
+                    return Command::perform(self.flush_state(), |(flushed, result)| Message::StateFlushed(flushed, result));
+                }
+                Command::none()
+            }
+            
+            // Nothing to update; the redraw after each message shows the new output
+            Message::RefreshLiveOutput => Command::none(),
+            
+            Message::StateFlushed(_, Ok(())) => Command::none(),
+            
+            Message::StateFlushed(flushed, Err(e)) => {
+                self.notify(
+                    format!("Run results were not saved ({}); retrying with the next flush", e),
+                    NotificationLevel::Error
+                );
+                self.requeue_flush(flushed);
+                Command::none()
+            }
+            
+            Message::WatchTriggered(id) => {
+                let now = Local::now();
+                let watching = self.tasks.iter()
+                    .any(|t| t.id == id && t.is_active && !t.is_paused_at(now));
+                if !watching || self.running_tasks.contains(&id) {
+                    return Command::none();
+                }
+                self.update(Message::ExecuteTask(id))
+            }
+            
+            Message::WatchFailed(id, error) => {
+                let title = self.tasks.iter()
+                    .find(|t| t.id == id)
+                    .map(|t| t.title.clone())
+                    .unwrap_or_else(|| "Unknown".to_string());
+                self.notify(format!("Cannot watch path for '{}': {}", title, error), NotificationLevel::Error);
+                Command::none()
+            }
+            
+            Message::CloseRequested => {
+                if self.has_unflushed_changes() {
+                    return Command::perform(self.flush_state(), |(_, result)| Message::ShutdownFlushed(result));
+                }
+                window::close(window::Id::MAIN)
+            }
+            
+            Message::ShutdownFlushed(result) => {
+                if let Err(e) = result {
+                    log::error!("Failed to persist state on shutdown: {}", e);
+                }
+                window::close(window::Id::MAIN)
+            }
+            
+            Message::CheckScheduledTasks => {
+                // Due runs stay overdue and fire once power is back
+                if self.battery_suspended() {
+                    return Command::none();
+                }
+                let now = Local::now();
+                let mut commands = vec![];
+                
+                let expired: Vec<Uuid> = self.test_schedules.iter()
+                    .filter(|(_, test)| now >= test.expires_at)
+                    .map(|(id, _)| *id)
+                    .collect();
+                
+                for id in expired {
+                    self.test_schedules.remove(&id);
+                    let mut title = None;
+                    if let Some(task) = self.tasks.iter_mut().find(|t| t.id == id) {
+                        if task.is_active {
+                            task.next_run = next_run_for(task, None, now);
+                        }
+                        title = Some(task.title.clone());
+                    }
+                    if let Some(title) = title {
+                        self.notify(format!("Test schedule for '{}' expired, restored normal interval", title), NotificationLevel::Info);
+                    }
+                }
+                
+                // Resume tasks whose pause has run out, skipping runs missed while paused
+                let mut resumed = vec![];
+                for task in self.tasks.iter_mut() {
+                    if task.paused_until.is_some_and(|until| now >= until) {
+                        task.paused_until = None;
+                        if task.is_active && task.next_run.is_none_or(|next| next < now) {
+                            let interval_override = self.test_schedules.get(&task.id).map(|t| t.interval_seconds);
+                            task.next_run = next_run_for(task, interval_override, now);
+                        }
+                        self.dirty_tasks.insert(task.id);
+                        resumed.push(task.title.clone());
+                    }
+                }
+                for title in resumed {
+                    self.notify(format!("Task '{}' resumed after pause", title), NotificationLevel::Info);
+                }
+                
+                if !self.missed_runs_handled && self.tasks_loaded && self.config_loaded {
+                    self.missed_runs_handled = true;
+                    self.handle_missed_runs(now);
+                }
+                
+                for task in &self.tasks {
+                    if task.is_active && !task.is_paused_at(now) {
+                        if let Some(next_run) = task.next_run {
+                            // A task blocked by its dependency stays due and fires once it clears
+                            if now >= next_run && !self.running_tasks.contains(&task.id)
+                                && !self.retry_attempts.contains_key(&task.id)
+                                && !self.run_queue.contains(&task.id)
+                                && self.blocking_dependency(task).is_none() {
+                                let task_id = task.id;
+                                commands.push(Command::perform(
+                                    async move { task_id },
+                                    Message::ExecuteTask
+                                ));
+                            }
+                        }
+                    }
+                }
+                
+                Command::batch(commands)
+            }
+            
+            Message::RefreshStats => {
+                self.stats = self.compute_stats();
+                Command::none()
+            }
+        }
+    }
+
+    fn view(&self) -> Element<Message> {
+        if let Some(task) = self.focus_task.and_then(|id| self.tasks.iter().find(|t| t.id == id)) {
+            return self.view_focus(task);
+        }
+        
+        let content = match &self.screen {
+            Screen::Overview => self.view_overview(),
+            Screen::Tasks => self.view_tasks(),
+            Screen::Logs(task_id) => self.view_logs(*task_id),
+            Screen::Failures => self.view_failures(),
+            Screen::Calendar => self.view_calendar(),
+            Screen::Compare(a, b) => self.view_compare(*a, *b),
+            Screen::TaskDetail(id) => self.view_task_detail(*id),
+            Screen::Audit => self.view_audit(),
+            Screen::Settings => self.view_settings(),
+        };
+
+        column![
+            self.view_header(),
+            Space::with_height(20),
+            self.view_pending_action(),
+            content,
+            self.view_notifications(),
+        ]
+        .padding(20)
+        .into()
+    }
+
+    fn subscription(&self) -> Subscription<Message> {
+        let now = Local::now();
+        let watchers = self.tasks.iter()
+            .filter(|t| t.is_active && !t.is_paused_at(now))
+            .filter_map(|t| t.watch_path.clone().map(|path| watch_path(t.id, path)));
+        
+        let power_source = if self.config.pause_on_battery {
+            time::every(POWER_SOURCE_POLL).map(|_| Message::CheckPowerSource)
+        } else {
+            Subscription::none()
+        };
+        
+        let system_theme = if self.config.theme == AppTheme::System {
+            time::every(SYSTEM_THEME_POLL).map(|_| Message::CheckSystemTheme)
+        } else {
+            Subscription::none()
+        };
+        
+        // Ctrl+S only applies while the Settings screen is open
+        let settings_shortcuts = if self.screen == Screen::Settings {
+            keyboard::on_key_press(settings_shortcut)
+        } else {
+            Subscription::none()
+        };
+        
+        let live_output = if self.live_output_visible() {
+            time::every(LIVE_OUTPUT_REFRESH).map(|_| Message::RefreshLiveOutput)
+        } else {
+            Subscription::none()
+        };
+        
+        Subscription::batch(watchers.chain([
+            keyboard::on_key_press(global_shortcut),
+            settings_shortcuts,
+            live_output,
+            system_theme,
+            power_source,
+            // Fixed so tasks fire on time whatever the refresh interval is
+            time::every(SCHEDULER_POLL).map(|_| Message::CheckScheduledTasks),
+            time::every(Duration::from_secs(self.config.refresh_interval))
+                .map(|_| Message::RefreshStats),
+            // Separate from the scheduling poll; also redraws the countdowns on task cards
+            time::every(Duration::from_secs(1)).map(|_| Message::Tick),
+            event::listen_with(|event, _status| match event {
+                Event::Window(id, window::Event::CloseRequested) if id == window::Id::MAIN => {
+                    Some(Message::CloseRequested)
+                }
+                _ => None,
+            }),
+        ]))
+    }
+
+    // A hand-edited config could hold anything, so it is clamped here too
+    fn scale_factor(&self) -> f64 {
+        self.config.ui_scale.clamp(MIN_UI_SCALE, MAX_UI_SCALE) as f64
+    }
+    
+    fn theme(&self) -> Theme {
+        match self.config.theme {
+            AppTheme::Light => Theme::Light,
+            AppTheme::Dark => Theme::Dark,
+            // Dark when the OS scheme is unknown
+            AppTheme::System => match self.system_dark {
+                Some(false) => Theme::Light,
+                _ => Theme::Dark,
+            },
+        }
+    }
+}
+
+// Some(true) on battery, Some(false) on AC; None where the power source can't be read
+async fn detect_on_battery() -> Option<bool> {
+    tokio::task::spawn_blocking(read_on_battery).await.ok().flatten()
+}
+
+fn read_on_battery() -> Option<bool> {
+    let manager = battery::Manager::new().ok()?;
+    let states = manager.batteries().ok()?
+        .map(|battery| battery.map(|b| b.state()))
+        .collect::<Result<Vec<_>, _>>()
+        .ok()?;
+    on_battery_from_states(&states)
+}
+
+// A machine without a battery is never on battery, one with any battery
+// discharging is; unknown when none of its batteries reports a state
+fn on_battery_from_states(states: &[battery::State]) -> Option<bool> {
+    if states.is_empty() {
+        return Some(false);
+    }
+    if states.iter().all(|state| matches!(state, battery::State::Unknown)) {
+        return None;
+    }
+    Some(states.iter().any(|state| matches!(state, battery::State::Discharging)))
+}
+
+// Some(true) for dark mode; None when the OS doesn't say. Detection can block
+// (D-Bus on Linux), so it runs off the UI thread
+async fn detect_system_theme() -> Option<bool> {
+    match tokio::task::spawn_blocking(dark_light::detect).await {
+        Ok(dark_light::Mode::Dark) => Some(true),
+        Ok(dark_light::Mode::Light) => Some(false),
+        Ok(dark_light::Mode::Default) | Err(_) => None,
+    }
+}
+
+fn title_input_id() -> text_input::Id {
+    text_input::Id::new("task-title")
+}
+
+// Focused text inputs capture key presses, and on_key_press only sees the
+// uncaptured ones, so these never fire while typing
+fn global_shortcut(key: keyboard::Key, modifiers: keyboard::Modifiers) -> Option<Message> {
+    if !modifiers.command() {
+        return None;
+    }
+    match key.as_ref() {
+        keyboard::Key::Character("1") => Some(Message::ChangeScreen(Screen::Overview)),
+        keyboard::Key::Character("2") => Some(Message::ChangeScreen(Screen::Tasks)),
+        keyboard::Key::Character("3") => Some(Message::ChangeScreen(Screen::Logs(None))),
+        keyboard::Key::Character("4") => Some(Message::ChangeScreen(Screen::Settings)),
+        keyboard::Key::Character("n") => Some(Message::FocusNewTask),
+        _ => None,
+    }
+}
+
+fn settings_shortcut(key: keyboard::Key, modifiers: keyboard::Modifiers) -> Option<Message> {
+    match key.as_ref() {
+        keyboard::Key::Character("s") if modifiers.command() => Some(Message::SaveSettings),
+        _ => None,
+    }
+}
+
+//View Components
+impl TaskWithMe {
+    fn view_header(&self) -> Element<Message> {
+        let nav_button = |label: &str, screen: Screen, is_active: bool| {
+            button(text(label).size(14))
+                .on_press(Message::ChangeScreen(screen))
+                .padding([10, 16])
+                .style(if is_active {
+                    iced::theme::Button::Primary
+                } else {
+                    iced::theme::Button::Secondary
+                })
+        };
+
+        let status_link = |label: String, filter: TaskFilter, color: Color| {
+            button(text(label).size(12).style(color))
+                .on_press(Message::ShowTasksFiltered(filter))
+                .padding([2, 4])
+                .style(iced::theme::Button::Text)
+        };
+        let active = self.tasks.iter().filter(|t| t.is_active).count();
+        let failing = self.tasks.iter().filter(|t| self.is_failing(t.id)).count();
+        let status = row![
+            status_link(format!("{} active", active), TaskFilter::Active,
+                Color::from_rgb(0.5, 0.5, 0.5)),
+            text("·").size(12),
+            status_link(format!("{} running", self.running_tasks.len()), TaskFilter::Running,
+                if self.running_tasks.is_empty() { Color::from_rgb(0.5, 0.5, 0.5) } else { Color::from_rgb(0.2, 0.6, 0.9) }),
+            text("·").size(12),
+            status_link(format!("{} failing", failing), TaskFilter::Failing,
+                if failing == 0 { Color::from_rgb(0.5, 0.5, 0.5) } else { Color::from_rgb(0.9, 0.3, 0.3) }),
+        ]
+        .spacing(2)
+        .align_items(alignment::Alignment::Center);
+        let status = if self.battery_suspended() {
+            status
+                .push(text("·").size(12))
+                .push(text("paused on battery").size(12).style(Color::from_rgb(0.95, 0.7, 0.2)))
+        } else {
+            status
+        };
+
+        container(
+            row![
+                text("[Task with Me]").size(22),
+                Space::with_width(16),
+                status,
+                Space::with_width(Length::Fill),
+                row![
+                    nav_button("Overview", Screen::Overview, 
+                        matches!(self.screen, Screen::Overview)),
+                    nav_button("Tasks", Screen::Tasks, 
+                        matches!(self.screen, Screen::Tasks | Screen::TaskDetail(_))),
                     nav_button("Logs", Screen::Logs(None), 
                         matches!(self.screen, Screen::Logs(_))),
+                    nav_button("Failures", Screen::Failures, 
+                        matches!(self.screen, Screen::Failures)),
+                    nav_button("Calendar", Screen::Calendar, 
+                        matches!(self.screen, Screen::Calendar)),
+                    nav_button("Audit", Screen::Audit, 
+                        matches!(self.screen, Screen::Audit)),
                     nav_button("Settings", Screen::Settings, 
                         matches!(self.screen, Screen::Settings)),
+                    button(text(if self.trusted_session { "Trusted Session: On" } else { "Trusted Session: Off" }).size(14))
+                        .on_press(Message::ToggleTrustedSession)
+                        .padding([10, 16])
+                        .style(if self.trusted_session {
+                            iced::theme::Button::Destructive
+                        } else {
+                            iced::theme::Button::Text
+                        }),
+                ]
+                .spacing(8),
+            ]
+            .align_items(alignment::Alignment::Center)
+        )
+        .width(Length::Fill)
+        .into()
+    }
+    
+    fn view_pending_action(&self) -> Element<'_, Message> {
+        let Some(action) = &self.pending_action else {
+            return Space::with_height(0).into();
+        };
+        
+        let task_title = |id: &Uuid| {
+            self.tasks.iter()
+                .find(|t| t.id == *id)
+                .map(|t| t.title.clone())
+                .unwrap_or_else(|| "Unknown".to_string())
+        };
+        
+        let (prompt, confirm_label) = match action {
+            PendingAction::RunTask(id) if self.tasks.iter().any(|t| t.id == *id && t.run_elevated) => (
+                format!("Run '{}' now with elevated privileges?", task_title(id)),
+                "Run Elevated",
+            ),
+            PendingAction::RunTask(id) => (format!("Run '{}' now?", task_title(id)), "Run"),
+            PendingAction::DeleteTask(id) => (
+                format!("Delete '{}'? This cannot be undone.", task_title(id)),
+                "Delete",
+            ),
+            PendingAction::DeleteTasks(ids) => (
+                format!("Delete {} selected task(s)? This cannot be undone.", ids.len()),
+                "Delete Selected",
+            ),
+            PendingAction::ResetTaskStats(id) => (
+                format!("Reset the run counts of '{}' to zero? Its logs are kept.", task_title(id)),
+                "Reset Stats",
+            ),
+            PendingAction::SaveDuplicateTitle(existing) => (
+                format!("A task named '{}' already exists. Save this one with the same name?", existing),
+                "Save Anyway",
+            ),
+        };
+        
+        container(
+            row![
+                text(prompt).size(14).width(Length::Fill),
+                button(confirm_label)
+                    .on_press(Message::ConfirmPendingAction)
+                    .padding(8)
+                    .style(if action.is_destructive() {
+                        iced::theme::Button::Destructive
+                    } else {
+                        iced::theme::Button::Primary
+                    }),
+                button("Cancel")
+                    .on_press(Message::CancelPendingAction)
+                    .padding(8)
+                    .style(iced::theme::Button::Secondary),
+            ]
+            .align_items(alignment::Alignment::Center)
+            .spacing(10)
+        )
+        .padding(12)
+        .width(Length::Fill)
+        .style(iced::theme::Container::Box)
+        .into()
+    }
+    
+    fn view_overview(&self) -> Element<Message> {
+        let DashboardStats { total, active, success_rate } = self.stats;
+        let running = self.running_tasks.len();
+        
+        let stats = row![
+            self.stat_card("Total Tasks", total.to_string(), Color::from_rgb(0.2, 0.6, 0.9)),
+            self.stat_card("Active", active.to_string(), Color::from_rgb(0.3, 0.8, 0.4)),
+            self.stat_card("Running", running.to_string(), Color::from_rgb(0.95, 0.7, 0.2)),
+            self.stat_card("Success Rate", self.format_success_rate(success_rate), Color::from_rgb(0.7, 0.4, 0.9)),
+        ]
+        .spacing(15);
+        
+        let quick_actions = container(
+            column![
+                text("Quick Actions").size(18),
+                Space::with_height(15),
+                row![
+                    button("+ New Task")
+                        .on_press(Message::ChangeScreen(Screen::Tasks))
+                        .padding(15)
+                        .style(iced::theme::Button::Primary),
+                    button("View All Tasks")
+                        .on_press(Message::ChangeScreen(Screen::Tasks))
+                        .padding(15),
+                    button("View Logs")
+                        .on_press(Message::ChangeScreen(Screen::Logs(None)))
+                        .padding(15),
+                    button("Run All Active")
+                        .on_press(Message::RunAllActive)
+                        .padding(15)
+                        .style(iced::theme::Button::Secondary),
+                ]
+                .spacing(10),
+            ]
+        )
+        .padding(20)
+        .style(iced::theme::Container::Box);
+        
+        let recent_tasks = self.view_recent_tasks();
+        let runs_chart = self.view_runs_per_day();
+        
+        let onboarding: Element<Message> = if self.show_onboarding {
+            let templates = self.templates.iter()
+                .map(|t| t.name.as_str())
+                .collect::<Vec<_>>()
+                .join(", ");
+            column![
+                container(
+                    column![
+                        text("Welcome to Task with Me").size(20),
+                        text("A task is a shell command run on a schedule: every N seconds, or on a cron expression. \
+                            Start a task to schedule it, pause it to stop. Every run is logged with its output, \
+                            exit code and duration, and the dashboard tracks how often each task succeeds.").size(13),
+                        text(format!("Not sure where to begin? Add the built-in templates ({}) as paused tasks \
+                            and adjust them on the Tasks screen.", templates)).size(13),
+                        row![
+                            button("Add Template Tasks")
+                                .on_press(Message::SeedTemplates)
+                                .padding(8)
+                                .style(iced::theme::Button::Primary),
+                            button("Create My Own")
+                                .on_press(Message::FocusNewTask)
+                                .padding(8)
+                                .style(iced::theme::Button::Secondary),
+                            Space::with_width(Length::Fill),
+                            button("Dismiss")
+                                .on_press(Message::DismissOnboarding)
+                                .padding(8)
+                                .style(iced::theme::Button::Text),
+                        ]
+                        .spacing(10),
+                    ]
+                    .spacing(10)
+                )
+                .padding(20)
+                .style(iced::theme::Container::Box),
+                Space::with_height(20),
+            ]
+            .into()
+        } else {
+            Space::with_height(0).into()
+        };
+        
+        column![
+            text("Dashboard").size(26),
+            Space::with_height(20),
+            onboarding,
+            stats,
+            Space::with_height(25),
+            runs_chart,
+            Space::with_height(25),
+            quick_actions,
+            Space::with_height(25),
+            recent_tasks,
+            Space::with_height(25),
+            self.view_activity(),
+        ]
+        .into()
+    }
+    
+    fn view_runs_per_day(&self) -> Element<'_, Message> {
+        let days = runs_per_day(&self.logs, Local::now().date_naive(), RUNS_CHART_DAYS);
+        let total: usize = days.iter().map(|d| d.succeeded + d.failed).sum();
+        
+        let content: Element<Message> = if total == 0 {
+            container(text(format!("No runs in the last {} days. Run a task and its results show up here.", RUNS_CHART_DAYS)).size(14))
+                .center_x()
+                .padding(40)
+                .into()
+        } else {
+            canvas(RunsPerDayChart { days })
+                .width(Length::Fill)
+                .height(Length::Fixed(RUNS_CHART_HEIGHT))
+                .into()
+        };
+        
+        container(
+            column![
+                row![
+                    text(format!("Runs per Day (last {} days)", RUNS_CHART_DAYS)).size(18),
+                    Space::with_width(Length::Fill),
+                    text(format!("{} runs", total)).size(13),
+                ]
+                .align_items(alignment::Alignment::Center),
+                Space::with_height(10),
+                content,
+            ]
+        )
+        .padding(20)
+        .style(iced::theme::Container::Box)
+        .into()
+    }
+    
+    fn stat_card(&self, label: &str, display: String, color: Color) -> Element<Message> {
+        container(
+            column![
+                text(label).size(13),
+                Space::with_height(8),
+                text(display).size(24).style(color),
+            ]
+        )
+        .padding(20)
+        .width(Length::FillPortion(1))
+        .style(iced::theme::Container::Box)
+        .into()
+    }
+    
+    // Full notification history; the toasts only show the newest few
+    fn view_activity(&self) -> Element<'_, Message> {
+        let content: Element<Message> = if self.activity.is_empty() {
+            container(text("No activity yet").size(14))
+                .center_x()
+                .padding(20)
+                .into()
+        } else {
+            let mut list = column![].spacing(6);
+            for notif in self.activity.iter().rev() {
+                let color = match notif.level {
+                    NotificationLevel::Info => Color::from_rgb(0.2, 0.6, 0.9),
+                    NotificationLevel::Success => Color::from_rgb(0.3, 0.8, 0.4),
+                    NotificationLevel::Warning => Color::from_rgb(0.95, 0.7, 0.2),
+                    NotificationLevel::Error => Color::from_rgb(0.9, 0.3, 0.3),
+                };
+                list = list.push(
+                    row![
+                        text(notif.timestamp.format("%b %d, %H:%M:%S").to_string())
+                            .size(11)
+                            .style(color)
+                            .width(Length::Fixed(120.0)),
+                        text(&notif.message).size(12).width(Length::Fill),
+                    ]
+                    .spacing(10)
+                );
+            }
+            scrollable(list).height(Length::Fixed(220.0)).into()
+        };
+        
+        container(
+            column![
+                row![
+                    text("Activity").size(18),
+                    Space::with_width(Length::Fill),
+                    button("Clear")
+                        .on_press_maybe((!self.activity.is_empty()).then_some(Message::ClearActivity))
+                        .padding(6)
+                        .style(iced::theme::Button::Secondary),
+                ]
+                .align_items(alignment::Alignment::Center),
+                Space::with_height(10),
+                content,
+            ]
+        )
+        .padding(20)
+        .style(iced::theme::Container::Box)
+        .into()
+    }
+    
+    fn view_recent_tasks(&self) -> Element<Message> {
+        let mut recent: Vec<&Task> = self.tasks.iter()
+            .filter(|t| t.last_run.is_some())
+            .collect();
+        recent.sort_by(|a, b| b.last_run.cmp(&a.last_run));
+        recent.truncate(5);
+        
+        let content: Element<Message> = if recent.is_empty() {
+            container(text("No tasks have run yet").size(14))
+                .center_x()
+                .padding(40)
+                .into()
+        } else {
+            let mut list = column![].spacing(8);
+            
+            for task in recent {
+                let success_rate = self.success_rate(task);
+                let card = container(
+                    row![
+                        column![
+                            text(&task.title).size(14),
+                            text(format!("Last run: {}", 
+                                task.last_run.unwrap().format("%b %d, %H:%M")))
+                                .size(11),
+                        ]
+                        .width(Length::Fill),
+                        text(self.format_success_rate(success_rate)).size(13),
+                        button("View Logs")
+                            .on_press(Message::ViewTaskLogs(task.id))
+                            .padding(8)
+                            .style(iced::theme::Button::Secondary),
+                    ]
+                    .align_items(alignment::Alignment::Center)
+                    .spacing(12)
+                )
+                .padding(12)
+                .style(iced::theme::Container::Box);
+                
+                list = list.push(card);
+            }
+            
+            scrollable(list).height(Length::Fixed(250.0)).into()
+        };
+        
+        container(
+            column![
+                text("Recent Activity").size(18),
+                Space::with_height(12),
+                content,
+            ]
+        )
+        .padding(20)
+        .style(iced::theme::Container::Box)
+        .into()
+    }
+    
+    fn view_tasks(&self) -> Element<Message> {
+        let dependency_choices: Vec<TaskChoice> = std::iter::once(TaskChoice { id: None, title: "None".to_string() })
+            .chain(self.tasks.iter()
+                .filter(|t| Some(t.id) != self.editing_task)
+                .map(|t| TaskChoice { id: Some(t.id), title: t.title.clone() }))
+            .collect();
+        let selected_dependency = self.depends_on_input
+            .and_then(|id| dependency_choices.iter().find(|c| c.id == Some(id)).cloned());
+        
+        let preview_panel: Element<Message> = match &self.command_preview {
+            Some(preview) => {
+                let mut panel = column![
+                    row![
+                        text("Command Preview (not run)").size(14),
+                        Space::with_width(Length::Fill),
+                        button("Close").on_press(Message::ClosePreview).padding(4),
+                    ]
+                    .align_items(alignment::Alignment::Center),
+                    text(&preview.invocation).size(12).font(iced::Font::MONOSPACE),
+                    text(format!("Working directory: {}", preview.working_dir)).size(12),
+                ]
+                .spacing(6);
+                if preview.env_vars.is_empty() {
+                    panel = panel.push(text("Environment: inherited only").size(12));
+                } else {
+                    for (key, value) in &preview.env_vars {
+                        panel = panel.push(text(format!("{}={}", key, value)).size(12).font(iced::Font::MONOSPACE));
+                    }
+                }
+                for warning in &preview.warnings {
+                    panel = panel.push(
+                        text(format!("⚠ {}", warning)).size(12).style(Color::from_rgb(0.9, 0.4, 0.1))
+                    );
+                }
+                column![
+                    Space::with_height(10),
+                    container(panel).padding(10).width(Length::Fill).style(iced::theme::Container::Box),
+                ]
+                .into()
+            }
+            None => Space::with_height(0).into(),
+        };
+        
+        let mut env_editor = column![
+            row![
+                text("Environment variables").size(12),
+                button(text("Add variable").size(11))
+                    .on_press(Message::AddEnvVar)
+                    .padding([4, 8])
+                    .style(iced::theme::Button::Secondary),
+            ]
+            .spacing(10)
+            .align_items(alignment::Alignment::Center),
+        ]
+        .spacing(6);
+        for (idx, (key, value)) in self.env_var_inputs.iter().enumerate() {
+            env_editor = env_editor.push(
+                row![
+                    text_input("NAME", key)
+                        .on_input(move |s| Message::EnvKeyInput(idx, s))
+                        .padding(6)
+                        .width(Length::Fixed(160.0)),
+                    text_input("value or {{secret:NAME}}", value)
+                        .on_input(move |s| Message::EnvValueInput(idx, s))
+                        .padding(6)
+                        .width(Length::Fixed(300.0)),
+                    button(text("Remove").size(11))
+                        .on_press(Message::RemoveEnvVar(idx))
+                        .padding([4, 8])
+                        .style(iced::theme::Button::Text),
+                ]
+                .spacing(8)
+                .align_items(alignment::Alignment::Center)
+            );
+        }
+        
+        // Task creation form
+        let form = container(
+            column![
+                text(if self.editing_task.is_some() { "Edit Task" } else { "Create New Task" }).size(18),
+                Space::with_height(12),
+                row![
+                    column![
+                        text("Title").size(12),
+                        text_input("Enter task title", &self.title_input)
+                            .id(title_input_id())
+                            .on_input(Message::TitleInput)
+                            .padding(8)
+                            .width(Length::Fixed(200.0)),
+                    ]
+                    .spacing(4),
+                    column![
+                        text("Command").size(12),
+                        text_input("Enter shell command", &self.command_input)
+                            .on_input(Message::CommandInput)
+                            .padding(8)
+                            .width(Length::Fixed(300.0)),
+                    ]
+                    .spacing(4),
+                    if self.cron_mode_input {
+                        column![
+                            text("Cron (min hour day month weekday)").size(12),
+                            text_input("0 9 * * 1-5", &self.cron_input)
+                                .on_input(Message::CronInput)
+                                .padding(8)
+                                .width(Length::Fixed(200.0)),
+                        ]
+                        .spacing(4)
+                    } else {
+                        column![
+                            text("Interval (sec)").size(12),
+                            text_input("60", &self.interval_input)
+                                .on_input(Message::IntervalInput)
+                                .padding(8)
+                                .width(Length::Fixed(120.0)),
+                        ]
+                        .spacing(4)
+                    },
+                    checkbox("Cron", self.cron_mode_input)
+                        .on_toggle(Message::CronModeToggled)
+                        .size(16),
+                    column![
+                        Space::with_height(12),
+                        button("Preview")
+                            .on_press(Message::PreviewCommand)
+                            .padding(8)
+                            .style(iced::theme::Button::Secondary),
+                    ],
+                    column![
+                        Space::with_height(12),
+                        button(text(if self.saving_task {
+                            format!("Saving {}", spinner_frame())
+                        } else if self.editing_task.is_some() {
+                            "Update Task".to_string()
+                        } else {
+                            "Create".to_string()
+                        }))
+                            .on_press_maybe((!self.saving_task).then_some(Message::CreateTask))
+                            .padding(8)
+                            .style(iced::theme::Button::Primary),
+                    ],
+                    if self.editing_task.is_some() {
+                        Element::from(
+                            button("Cancel")
+                                .on_press(Message::CancelEdit)
+                                .padding(8)
+                                .style(iced::theme::Button::Secondary)
+                        )
+                    } else {
+                        Space::with_width(0).into()
+                    },
+                ]
+                .spacing(10)
+                .align_items(alignment::Alignment::End),
+                Space::with_height(10),
+                row![
+                    column![
+                        text("Max output (bytes)").size(12),
+                        text_input("Global", &self.max_output_input)
+                            .on_input(Message::MaxOutputInput)
+                            .padding(8)
+                            .width(Length::Fixed(120.0)),
+                    ]
+                    .spacing(4),
+                    column![
+                        text("Working directory (optional)").size(12),
+                        text_input("App directory", &self.working_dir_input)
+                            .on_input(Message::WorkingDirInput)
+                            .padding(8)
+                            .width(Length::Fixed(200.0)),
+                    ]
+                    .spacing(4),
+                    column![
+                        text("Output file (optional)").size(12),
+                        text_input("/path/to/report.txt", &self.output_file_input)
+                            .on_input(Message::OutputFileInput)
+                            .padding(8)
+                            .width(Length::Fixed(300.0)),
+                    ]
+                    .spacing(4),
+                    checkbox("Append", self.output_append_input)
+                        .on_toggle(Message::OutputAppendToggled)
+                        .size(16),
+                    checkbox("Skip global prefix", self.skip_prefix_input)
+                        .on_toggle(Message::SkipPrefixToggled)
+                        .size(16),
+                    checkbox("Run elevated", self.run_elevated_input)
+                        .on_toggle(Message::RunElevatedToggled)
+                        .size(16),
+                    checkbox("Verbose log", self.verbose_logging_input)
+                        .on_toggle(Message::VerboseLoggingToggled)
+                        .size(16),
+                    checkbox("Login shell", self.login_shell_input)
+                        .on_toggle(Message::LoginShellToggled)
+                        .size(16),
+                ]
+                .spacing(10)
+                .align_items(alignment::Alignment::End),
+                if self.run_elevated_input {
+                    text("Elevated tasks run as root/administrator. You will be asked for your password on every run, \
+                        and only commands you fully trust should be elevated.")
+                        .size(11)
+                        .style(Color::from_rgb(0.95, 0.7, 0.2))
+                } else {
+                    text("").size(1)
+                },
+                Space::with_height(10),
+                row![
+                    column![
+                        text("Watch path (optional, runs on change)").size(12),
+                        text_input("/path/to/watch", &self.watch_path_input)
+                            .on_input(Message::WatchPathInput)
+                            .padding(8)
+                            .width(Length::Fixed(430.0)),
+                    ]
+                    .spacing(4),
+                    column![
+                        text("Alert after failing for (s)").size(12),
+                        text_input("immediately", &self.failure_alert_input)
+                            .on_input(Message::FailureAlertInput)
+                            .padding(8)
+                            .width(Length::Fixed(160.0)),
+                    ]
+                    .spacing(4),
+                    checkbox("Run on startup", self.run_on_startup_input)
+                        .on_toggle(Message::RunOnStartupToggled)
+                        .size(16),
+                    checkbox("Run when activated", self.run_on_activate_input)
+                        .on_toggle(Message::RunOnActivateToggled)
+                        .size(16),
+                    checkbox("One-shot (stop after first success)", self.one_shot_input)
+                        .on_toggle(Message::OneShotToggled)
+                        .size(16),
+                    checkbox("Notify on success", self.notify_on_success_input)
+                        .on_toggle(Message::NotifyOnSuccessToggled)
+                        .size(16),
+                    checkbox("Notify on failure", self.notify_on_failure_input)
+                        .on_toggle(Message::NotifyOnFailureToggled)
+                        .size(16),
+                    column![
+                        text("Depends on").size(12),
+                        pick_list(dependency_choices, selected_dependency, Message::DependsOnSelected)
+                            .placeholder("None")
+                            .padding(8)
+                            .width(Length::Fixed(180.0)),
+                    ]
+                    .spacing(4),
+                    column![
+                        text("Startup order").size(12),
+                        text_input("any", &self.startup_order_input)
+                            .on_input(Message::StartupOrderInput)
+                            .padding(8)
+                            .width(Length::Fixed(80.0)),
+                    ]
+                    .spacing(4),
+                ]
+                .spacing(10)
+                .align_items(alignment::Alignment::End),
+                Space::with_height(10),
+                row![
+                    column![
+                        text("On success command (optional, runs after a successful run)").size(12),
+                        text_input("e.g. touch /tmp/backup.done", &self.on_success_input)
+                            .on_input(Message::OnSuccessInput)
+                            .padding(8)
+                            .width(Length::Fixed(430.0)),
+                    ]
+                    .spacing(4),
+                    column![
+                        text("Timeout (s)").size(12),
+                        text_input("none", &self.timeout_input)
+                            .on_input(Message::TimeoutInput)
+                            .padding(8)
+                            .width(Length::Fixed(100.0)),
+                    ]
+                    .spacing(4),
+                    column![
+                        text("Kill grace (s)").size(12),
+                        text_input(&DEFAULT_TIMEOUT_GRACE_SECONDS.to_string(), &self.grace_input)
+                            .on_input(Message::GraceInput)
+                            .padding(8)
+                            .width(Length::Fixed(100.0)),
+                    ]
+                    .spacing(4),
+                    column![
+                        text("Retries").size(12),
+                        text_input("0", &self.retries_input)
+                            .on_input(Message::RetriesInput)
+                            .padding(8)
+                            .width(Length::Fixed(80.0)),
+                    ]
+                    .spacing(4),
+                    column![
+                        text("Retry delay (s)").size(12),
+                        text_input(&DEFAULT_RETRY_DELAY_SECONDS.to_string(), &self.retry_delay_input)
+                            .on_input(Message::RetryDelayInput)
+                            .padding(8)
+                            .width(Length::Fixed(100.0)),
+                    ]
+                    .spacing(4),
+                    column![
+                        text("Metric regex").size(12),
+                        text_input(r"(\d+)% used", &self.metric_regex_input)
+                            .on_input(Message::MetricRegexInput)
+                            .padding(8)
+                            .width(Length::Fixed(160.0)),
+                    ]
+                    .spacing(4),
+                    column![
+                        text("Desktop alert").size(12),
+                        pick_list(&AlertOverride::ALL[..], Some(self.desktop_override_input), Message::DesktopOverrideChanged)
+                            .padding(8),
+                    ]
+                    .spacing(4),
+                    column![
+                        text("Sound").size(12),
+                        pick_list(&AlertOverride::ALL[..], Some(self.sound_override_input), Message::SoundOverrideChanged)
+                            .padding(8),
+                    ]
+                    .spacing(4),
+                ]
+                .spacing(10),
+                Space::with_height(10),
+                column![
+                    text("Webhook URL (optional, receives a JSON POST after each run)").size(12),
+                    text_input("https://example.com/hooks/backup", &self.webhook_input)
+                        .on_input(Message::WebhookInput)
+                        .padding(8)
+                        .width(Length::Fixed(430.0)),
+                ]
+                .spacing(4),
+                Space::with_height(10),
+                env_editor,
+                preview_panel,
+            ]
+        )
+        .padding(20)
+        .style(iced::theme::Container::Box);
+        
+        // Templates
+        let mut templates_col = column![
+            row![
+                text("Quick Templates").size(16),
+                Space::with_width(Length::Fill),
+                button(text("Save Form as Template").size(12))
+                    .on_press(Message::SaveFormAsTemplate)
+                    .padding([4, 8])
+                    .style(iced::theme::Button::Secondary),
+            ]
+            .align_items(alignment::Alignment::Center),
+            Space::with_height(10),
+        ].spacing(6);
+        
+        for (idx, template) in self.templates.iter().enumerate() {
+            let btn = button(
+                column![
+                    text(&template.name).size(13),
+                    text(&template.description).size(11),
+                ]
+                .spacing(2)
+            )
+            .on_press(Message::SelectTemplate(idx))
+            .padding(10)
+            .width(Length::Fill)
+            .style(iced::theme::Button::Secondary);
+            
+            templates_col = templates_col.push(
+                row![
+                    btn,
+                    button(text("×").size(14))
+                        .on_press(Message::DeleteTemplate(idx))
+                        .padding([4, 8])
+                        .style(iced::theme::Button::Text),
+                ]
+                .spacing(4)
+                .align_items(alignment::Alignment::Center)
+            );
+        }
+        
+        let templates = container(templates_col)
+            .padding(15)
+            .style(iced::theme::Container::Box);
+        
+        // Crontab import
+        let crontab_import: Element<Message> = if self.show_crontab_import {
+            container(
+                column![
+                    row![
+                        text("Import from crontab").size(16),
+                        Space::with_width(Length::Fill),
+                        button("Close")
+                            .on_press(Message::ToggleCrontabImport)
+                            .padding(6)
+                            .style(iced::theme::Button::Secondary),
+                    ]
+                    .align_items(alignment::Alignment::Center),
+                    text("Paste crontab lines (5-field schedule + command). A comment line above an entry becomes its title.")
+                        .size(11),
+                    text_editor(&self.crontab_content)
+                        .on_action(Message::CrontabEdited)
+                        .height(Length::Fixed(120.0)),
+                    button("Import")
+                        .on_press(Message::ImportCrontab)
+                        .padding(8)
+                        .style(iced::theme::Button::Primary),
+                ]
+                .spacing(8)
+            )
+            .padding(15)
+            .style(iced::theme::Container::Box)
+            .into()
+        } else {
+            button("Import from crontab...")
+                .on_press(Message::ToggleCrontabImport)
+                .padding(8)
+                .style(iced::theme::Button::Secondary)
+                .into()
+        };
+        
+        // Search and filter
+        let controls = container(
+            row![
+                text_input("Search tasks...", &self.search_input)
+                    .on_input(Message::SearchInput)
+                    .padding(8)
+                    .width(Length::Fixed(250.0)),
+                pick_list(&TaskSort::ALL[..], Some(self.task_sort), Message::TaskSortChanged)
+                    .padding(6),
+                button(if self.sort_descending { "↓ Desc" } else { "↑ Asc" })
+                    .on_press_maybe((self.task_sort != TaskSort::Manual).then_some(Message::ToggleSortDirection))
+                    .padding([6, 12])
+                    .style(iced::theme::Button::Secondary),
+                Space::with_width(Length::Fill),
+                row![
+                    button("All")
+                        .on_press(Message::FilterChanged(TaskFilter::All))
+                        .style(if self.filter == TaskFilter::All {
+                            iced::theme::Button::Primary
+                        } else {
+                            iced::theme::Button::Secondary
+                        })
+                        .padding([6, 12]),
+                    button("Active")
+                        .on_press(Message::FilterChanged(TaskFilter::Active))
+                        .style(if self.filter == TaskFilter::Active {
+                            iced::theme::Button::Primary
+                        } else {
+                            iced::theme::Button::Secondary
+                        })
+                        .padding([6, 12]),
+                    button("Inactive")
+                        .on_press(Message::FilterChanged(TaskFilter::Inactive))
+                        .style(if self.filter == TaskFilter::Inactive {
+                            iced::theme::Button::Primary
+                        } else {
+                            iced::theme::Button::Secondary
+                        })
+                        .padding([6, 12]),
+                    button("Running")
+                        .on_press(Message::FilterChanged(TaskFilter::Running))
+                        .style(if self.filter == TaskFilter::Running {
+                            iced::theme::Button::Primary
+                        } else {
+                            iced::theme::Button::Secondary
+                        })
+                        .padding([6, 12]),
+                    button("Failing")
+                        .on_press(Message::FilterChanged(TaskFilter::Failing))
+                        .style(if self.filter == TaskFilter::Failing {
+                            iced::theme::Button::Primary
+                        } else {
+                            iced::theme::Button::Secondary
+                        })
+                        .padding([6, 12]),
+                ]
+                .spacing(6),
+            ]
+            .align_items(alignment::Alignment::Center)
+        )
+        .padding(12)
+        .style(iced::theme::Container::Box);
+        
+        // Task list; only the cards on the current page are built
+        let filtered = self.filtered_tasks();
+        let manual_order = self.task_sort == TaskSort::Manual;
+        let page_size = self.config.task_page_size.max(1);
+        let page_count = filtered.len().div_ceil(page_size).max(1);
+        let page = self.task_page.min(page_count - 1);
+        
+        let pager = row![
+            button("Prev")
+                .on_press_maybe((page > 0).then(|| Message::TaskPageChanged(page - 1)))
+                .padding([6, 12])
+                .style(iced::theme::Button::Secondary),
+            text(format!("Page {} of {}", page + 1, page_count)).size(12),
+            button("Next")
+                .on_press_maybe((page + 1 < page_count).then(|| Message::TaskPageChanged(page + 1)))
+                .padding([6, 12])
+                .style(iced::theme::Button::Secondary),
+        ]
+        .spacing(10)
+        .align_items(alignment::Alignment::Center);
+        
+        // Debug info
+        let debug_info = container(
+            text(format!("Total tasks: {} | Filtered: {} | Active filter: {:?}", 
+                self.tasks.len(), filtered.len(), self.filter))
+                .size(11)
+        )
+        .padding(8)
+        .style(iced::theme::Container::Box);
+        
+        // Bulk actions apply to every selected task, including ones the filter now hides
+        let selected = self.selected_tasks.len();
+        let bulk_bar = container(
+            row![
+                button("Select All (filtered)")
+                    .on_press_maybe((!filtered.is_empty()).then_some(Message::SelectAllFiltered))
+                    .padding([6, 12])
+                    .style(iced::theme::Button::Secondary),
+                text(format!("{} selected", selected)).size(12),
+                Space::with_width(Length::Fill),
+                button("Clear")
+                    .on_press_maybe((selected > 0).then_some(Message::ClearSelection))
+                    .padding([6, 12])
+                    .style(iced::theme::Button::Secondary),
+                button("Activate Selected")
+                    .on_press_maybe((selected > 0).then_some(Message::SetSelectedActive(true)))
+                    .padding([6, 12]),
+                button("Pause Selected")
+                    .on_press_maybe((selected > 0).then_some(Message::SetSelectedActive(false)))
+                    .padding([6, 12])
+                    .style(iced::theme::Button::Secondary),
+                button("Delete Selected")
+                    .on_press_maybe((selected > 0).then_some(Message::RequestDeleteSelected))
+                    .padding([6, 12])
+                    .style(iced::theme::Button::Destructive),
+            ]
+            .spacing(8)
+            .align_items(alignment::Alignment::Center)
+        )
+        .padding(8)
+        .style(iced::theme::Container::Box);
+        
+        let task_list: Element<Message> = if filtered.is_empty() {
+            container(
+                column![
+                    text("No tasks found").size(14),
+                    Space::with_height(10),
+                    text(format!("Total tasks in memory: {}", self.tasks.len())).size(11),
+                ]
+            )
+            .center_x()
+            .padding(40)
+            .into()
+        } else {
+            let mut list = column![].spacing(8);
+            
+            for (index, task) in filtered.iter().enumerate().skip(page * page_size).take(page_size) {
+                let is_running = self.running_tasks.contains(&task.id);
+                let success_rate = self.success_rate(task);
+                let test_schedule = self.test_schedules.get(&task.id);
+                
+                let status_color = if task.is_active {
+                    Color::from_rgb(0.3, 0.8, 0.4)
+                } else {
+                    Color::from_rgb(0.5, 0.5, 0.5)
+                };
+                
+                let task_id = task.id;
+                let card = container(
+                    row![
+                        checkbox("", self.selected_tasks.contains(&task.id))
+                            .on_toggle(move |on| Message::ToggleTaskSelected(task_id, on))
+                            .size(16),
+                        container(Space::with_width(4))
+                            .width(Length::Fixed(4.0))
+                            .height(Length::Fixed(80.0))
+                            .style(iced::theme::Container::Custom(Box::new(
+                                ColoredContainer(status_color)
+                            ))),
+                        column![
+                            row![
+                                button(text(&task.title).size(15))
+                                    .on_press(Message::ChangeScreen(Screen::TaskDetail(task.id)))
+                                    .padding(0)
+                                    .style(iced::theme::Button::Text),
+                                Space::with_width(Length::Fill),
+                                canvas(RunSparkline { results: self.recent_results(task.id, SPARKLINE_RUNS) })
+                                    .width(Length::Fixed(SPARKLINE_WIDTH))
+                                    .height(Length::Fixed(SPARKLINE_HEIGHT)),
+                                Space::with_width(8),
+                                text(self.format_success_rate(success_rate)).size(12),
+                            ]
+                            .align_items(alignment::Alignment::Center),
+                            text(&task.command).size(12),
+                            if let (Some(avg), Some(last)) = (self.avg_duration(task.id), self.last_duration(task.id)) {
+                                text(format!("avg {} · last {}", format_millis(avg), format_millis(last)))
+                                    .size(11)
+                                    .style(Color::from_rgb(0.6, 0.6, 0.6))
+                            } else {
+                                text("").size(1)
+                            },
+                            if let Some(path) = &task.watch_path {
+                                text(format!("Watching {}{}", path.display(),
+                                    if task.is_active { "" } else { " (starts when activated)" }))
+                                    .size(11)
+                                    .style(Color::from_rgb(0.2, 0.6, 0.9))
+                            } else {
+                                text("").size(1)
+                            },
+                            if self.run_queue.contains(&task.id) {
+                                text(format!("Queued (max {} running)", self.config.max_concurrent_tasks))
+                                    .size(11)
+                                    .style(Color::from_rgb(0.6, 0.6, 0.6))
+                            } else {
+                                text("").size(1)
+                            },
+                            if let Some(attempt) = self.retry_attempts.get(&task.id) {
+                                text(format!("Retry {}/{}", attempt, task.max_retries))
+                                    .size(11)
+                                    .style(Color::from_rgb(0.95, 0.7, 0.2))
+                            } else {
+                                text("").size(1)
+                            },
+                            if task.run_elevated {
+                                text("Runs elevated")
+                                    .size(11)
+                                    .style(Color::from_rgb(0.95, 0.7, 0.2))
+                            } else {
+                                text("").size(1)
+                            },
+                            if task.run_on_startup {
+                                text(match task.startup_order {
+                                    Some(order) => format!("Runs on startup (#{})", order),
+                                    None => "Runs on startup".to_string(),
+                                })
+                                .size(11)
+                                .style(Color::from_rgb(0.5, 0.5, 0.5))
+                            } else {
+                                text("").size(1)
+                            },
+                            row![
+                                if let Some(test) = test_schedule {
+                                    text(format!("TEST: every {} (normally {}, reverts at {})",
+                                        Self::format_duration(self.effective_interval(task)),
+                                        task.schedule.describe(),
+                                        test.expires_at.format("%H:%M")))
+                                        .size(11)
+                                        .style(Color::from_rgb(0.95, 0.7, 0.2))
+                                } else {
+                                    text(task.schedule.describe()).size(11)
+                                },
+                                Space::with_width(Length::Fill),
+                                if let Some(until) = task.paused_until {
+                                    text(format!("Paused until {}", until.format("%a %b %d, %H:%M")))
+                                        .size(11)
+                                        .style(Color::from_rgb(0.95, 0.7, 0.2))
+                                } else if let Some(done) = task.completed_at {
+                                    text(format!("Completed {}", done.format("%b %d, %H:%M")))
+                                        .size(11)
+                                        .style(Color::from_rgb(0.3, 0.8, 0.4))
+                                } else if let Some(upstream) = self.blocking_dependency(task) {
+                                    text(format!("Waiting for '{}' to succeed", upstream.title))
+                                        .size(11)
+                                        .style(Color::from_rgb(0.95, 0.7, 0.2))
+                                } else if let Some(next) = task.next_run.filter(|_| task.is_active) {
+                                    let remaining = (next - Local::now()).num_seconds();
+                                    if remaining > 0 {
+                                        text(format!("Next: {} (in {})",
+                                            next.format("%H:%M"),
+                                            Self::format_countdown(remaining as u64)))
+                                            .size(11)
+                                    } else {
+                                        text(format!("Next: {} (due now)", next.format("%H:%M")))
+                                            .size(11)
+                                            .style(Color::from_rgb(0.95, 0.7, 0.2))
+                                    }
+                                } else if let Some(next) = task.next_run {
+                                    text(format!("Next: {}", next.format("%H:%M"))).size(11)
+                                } else {
+                                    text("Not scheduled").size(11)
+                                },
+                            ],
+                        ]
+                        .spacing(4)
+                        .width(Length::Fill),
+                        row![
+                            column![
+                                button(text("↑").size(12))
+                                    .on_press_maybe((manual_order && index > 0).then_some(Message::MoveTaskUp(task.id)))
+                                    .padding([2, 8])
+                                    .style(iced::theme::Button::Text),
+                                button(text("↓").size(12))
+                                    .on_press_maybe((manual_order && index + 1 < filtered.len()).then_some(Message::MoveTaskDown(task.id)))
+                                    .padding([2, 8])
+                                    .style(iced::theme::Button::Text),
+                            ],
+                            if let Some((label, color)) = self.running_label(task) {
+                                text(label).size(12).style(color)
+                            } else {
+                                text("").size(1)
+                            },
+                            if is_running {
+                                let cancelling = self.cancel_signals.get(&task.id).is_some_and(|s| *s.borrow());
+                                button(if cancelling { "Cancelling..." } else { "Cancel" })
+                                    .on_press_maybe((!cancelling).then_some(Message::CancelTask(task.id)))
+                                    .padding(8)
+                                    .style(iced::theme::Button::Destructive)
+                            } else if self.run_queue.contains(&task.id) {
+                                button("Queued")
+                                    .padding(8)
+                                    .style(iced::theme::Button::Secondary)
+                            } else {
+                                button("Run Once")
+                                    .on_press(Message::RunTask(task.id))
+                                    .padding(8)
+                                    .style(iced::theme::Button::Primary)
+                            },
+                            button(if task.is_active { "Pause" } else { "Start" })
+                                .on_press(Message::ToggleTask(task.id))
+                                .padding(8)
+                                .style(iced::theme::Button::Secondary),
+                            button(if test_schedule.is_some() { "End Test" } else { "Test" })
+                                .on_press(Message::ToggleTestSchedule(task.id))
+                                .padding(8)
+                                .style(iced::theme::Button::Secondary),
+                            if task.paused_until.is_some() {
+                                Element::from(
+                                    button("Resume")
+                                        .on_press(Message::ResumeTask(task.id))
+                                        .padding(8)
+                                        .style(iced::theme::Button::Secondary)
+                                )
+                            } else {
+                                let id = task.id;
+                                pick_list(&PauseUntil::ALL[..], None::<PauseUntil>, move |pause| {
+                                    Message::PauseTaskUntil(id, pause)
+                                })
+                                .placeholder("Pause until...")
+                                .padding(8)
+                                .width(Length::Fixed(130.0))
+                                .into()
+                            },
+                            button("Logs")
+                                .on_press(Message::ViewTaskLogs(task.id))
+                                .padding(8)
+                                .style(iced::theme::Button::Secondary),
+                            button("Edit")
+                                .on_press(Message::EditTask(task.id))
+                                .padding(8)
+                                .style(if self.editing_task == Some(task.id) {
+                                    iced::theme::Button::Primary
+                                } else {
+                                    iced::theme::Button::Secondary
+                                }),
+                            button("Clone")
+                                .on_press(Message::CloneTask(task.id))
+                                .padding(8)
+                                .style(iced::theme::Button::Secondary),
+                            button(match self.compare_selection {
+                                Some(selected) if selected == task.id => "Comparing",
+                                Some(_) => "Compare with",
+                                None => "Compare",
+                            })
+                                .on_press(Message::CompareTask(task.id))
+                                .padding(8)
+                                .style(if self.compare_selection == Some(task.id) {
+                                    iced::theme::Button::Primary
+                                } else {
+                                    iced::theme::Button::Secondary
+                                }),
+                            button(text(if self.deleting_tasks.contains(&task.id) {
+                                format!("Deleting {}", spinner_frame())
+                            } else {
+                                "Delete".to_string()
+                            }))
+                                .on_press_maybe((!self.deleting_tasks.contains(&task.id))
+                                    .then_some(Message::RequestDeleteTask(task.id)))
+                                .padding(8)
+                                .style(iced::theme::Button::Destructive),
+                        ]
+                        .spacing(6),
+                    ]
+                    .align_items(alignment::Alignment::Center)
+                    .spacing(12)
+                )
+                .padding(12)
+                .width(Length::Fill)
+                .style(iced::theme::Container::Box);
+                
+                list = list.push(card);
+                
+                // While running, preview the tail of the previous run's output
+                if is_running {
+                    let previous = Some(std::borrow::Cow::Borrowed(task.last_output.as_str()))
+                        .filter(|o| !o.is_empty())
+                        .or_else(|| {
+                            self.logs.iter()
+                                .rev()
+                                .find(|l| l.task_id == task.id)
+                                .map(|l| l.output_text())
+                        })
+                        .filter(|o| !o.trim().is_empty());
+                    let preview = match previous {
+                        Some(output) => format!("Previous run:\n{}", output_tail(&output, PREVIEW_LINES)),
+                        None => "Waiting for output...".to_string(),
+                    };
+                    list = list.push(
+                        container(
+                            row![
+                                text(preview).size(11).font(Font::MONOSPACE).width(Length::Fill),
+                                button(text("Focus").size(11))
+                                    .on_press(Message::EnterFocus(task.id))
+                                    .padding([4, 8])
+                                    .style(iced::theme::Button::Secondary),
+                            ]
+                            .align_items(alignment::Alignment::Center)
+                            .spacing(10)
+                        )
+                        .padding([6, 12])
+                        .width(Length::Fill)
+                        .style(iced::theme::Container::Box)
+                    );
+                }
+            }
+            
+            container(
+                scrollable(list)
+                    .height(Length::Fill)
+            )
+            .height(Length::Fixed(400.0))
+            .into()
+        };
+        
+        column![
+            text("Task Management").size(26),
+            Space::with_height(20),
+            form,
+            Space::with_height(15),
+            templates,
+            Space::with_height(15),
+            crontab_import,
+            Space::with_height(15),
+            controls,
+            Space::with_height(12),
+            debug_info,
+            Space::with_height(8),
+            bulk_bar,
+            Space::with_height(8),
+            task_list,
+            Space::with_height(8),
+            pager,
+        ]
+        .into()
+    }
+    
+    fn view_logs(&self, task_id: Option<Uuid>) -> Element<Message> {
+        let filtered_logs = self.filtered_logs(task_id);
+        
+        let task_name = task_id.and_then(|id| {
+            self.tasks.iter().find(|t| t.id == id).map(|t| t.title.clone())
+        });
+        
+        let header = if let Some(name) = task_name {
+            text(format!("Logs for: {}", name)).size(20)
+        } else {
+            text("All Execution Logs").size(20)
+        };
+        
+        let metric_points: Vec<(DateTime<Local>, f64)> = filtered_logs.iter()
+            .filter_map(|log| log.metric.map(|m| (log.timestamp, m)))
+            .collect();
+        let metric_chart: Element<Message> = if task_id.is_some() && metric_points.len() >= 2 {
+            column![
+                container(
+                    canvas(MetricChart { points: metric_points })
+                        .width(Length::Fill)
+                        .height(Length::Fixed(METRIC_CHART_HEIGHT))
+                )
+                .padding(12)
+                .style(iced::theme::Container::Box),
+                Space::with_height(12),
+            ]
+            .into()
+        } else {
+            text("").size(1).into()
+        };
+        
+        // Newest first; only the entries on the current page are built
+        let page_size = self.log_page_size.max(1);
+        let page_count = filtered_logs.len().div_ceil(page_size).max(1);
+        let page = self.log_page.min(page_count - 1);
+        
+        let pager = row![
+            button("Previous")
+                .on_press_maybe((page > 0).then(|| Message::LogPageChanged(page - 1)))
+                .padding([6, 12])
+                .style(iced::theme::Button::Secondary),
+            text(format!("Page {} of {}", page + 1, page_count)).size(12),
+            button("Next")
+                .on_press_maybe((page + 1 < page_count).then(|| Message::LogPageChanged(page + 1)))
+                .padding([6, 12])
+                .style(iced::theme::Button::Secondary),
+            Space::with_width(Length::Fill),
+            text("Per page:").size(12),
+            pick_list(&LOG_PAGE_SIZES[..], Some(self.log_page_size), Message::LogPageSizeChanged)
+                .padding(6),
+        ]
+        .spacing(10)
+        .align_items(alignment::Alignment::Center);
+        
+        let content: Element<Message> = if filtered_logs.is_empty() {
+            container(text("No logs available").size(14))
+                .center_x()
+                .padding(40)
+                .into()
+        } else {
+            let mut list = column![].spacing(8);
+            
+            // Headers count the whole day, not just the part on this page
+            let mut day_counts: HashMap<chrono::NaiveDate, usize> = HashMap::new();
+            for log in &filtered_logs {
+                *day_counts.entry(log.timestamp.date_naive()).or_default() += 1;
+            }
+            let today = Local::now().date_naive();
+            let mut current_day = None;
+            
+            for log in filtered_logs.iter().rev().skip(page * page_size).take(page_size) {
+                let day = log.timestamp.date_naive();
+                let collapsed = self.collapsed_log_days.contains(&day);
+                if current_day != Some(day) {
+                    current_day = Some(day);
+                    list = list.push(
+                        button(
+                            text(format!(
+                                "{} {} ({})",
+                                if collapsed { "▸" } else { "▾" },
+                                day_label(day, today),
+                                day_counts.get(&day).copied().unwrap_or(0)
+                            ))
+                            .size(14)
+                        )
+                        .on_press(Message::ToggleLogDay(day))
+                        .padding([4, 0])
+                        .style(iced::theme::Button::Text)
+                    );
+                }
+                if collapsed {
+                    continue;
+                }
+                
+                let task_title = self.tasks.iter()
+                    .find(|t| t.id == log.task_id)
+                    .map(|t| t.title.as_str())
+                    .unwrap_or("Unknown");
+                
+                let status_color = if log.success {
+                    Color::from_rgb(0.3, 0.8, 0.4)
+                } else {
+                    Color::from_rgb(0.9, 0.3, 0.3)
+                };
+                
+                let card = container(
+                    column![
+                        row![
+                            container(
+                                text(if log.success { "OK" } else { "FAIL" })
+                                    .size(14)
+                            )
+                            .padding([4, 8])
+                            .style(iced::theme::Container::Custom(Box::new(
+                                ColoredContainer(status_color)
+                            ))),
+                            text(task_title).size(14),
+                            Space::with_width(Length::Fill),
+                            text(log.timestamp.format("%b %d, %H:%M:%S").to_string())
+                                .size(12),
+                            text(if log.attempt > 0 { format!("retry {}", log.attempt) } else { String::new() })
+                                .size(11),
+                            text(log.metric.map(|m| format!("metric {}", m)).unwrap_or_default())
+                                .size(11),
+                            text(log.exit_code.map(describe_exit_code).unwrap_or_default()).size(11),
+                            text(format!("{}ms", log.duration_ms)).size(11),
+                            button(text("Save output").size(11))
+                                .on_press(Message::SaveLogOutput(log.id))
+                                .padding([4, 8])
+                                .style(iced::theme::Button::Secondary),
+                        ]
+                        .align_items(alignment::Alignment::Center)
+                        .spacing(10),
+                        log_output_sections(log),
+                        if let Some(metadata) = &log.metadata {
+                            container(
+                                text(metadata.describe())
+                                    .size(10)
+                                    .style(Color::from_rgb(0.6, 0.6, 0.6))
+                            )
+                            .padding([8, 12])
+                            .style(iced::theme::Container::Box)
+                        } else {
+                            container(Space::with_height(0))
+                        },
+                    ]
+                    .spacing(8)
+                )
+                .padding(12)
+                .style(iced::theme::Container::Box);
+                
+                list = list.push(card);
+            }
+            
+            scrollable(list).height(Length::Fixed(500.0)).into()
+        };
+        
+        column![
+            text("Execution Logs").size(26),
+            Space::with_height(20),
+            container(
+                row![
+                    header,
+                    Space::with_width(Length::Fill),
+                    if task_id.is_some() {
+                        button("View All Logs")
+                            .on_press(Message::ChangeScreen(Screen::Logs(None)))
+                            .padding(8)
+                    } else {
+                        button("")
+                            .padding(0)
+                            .style(iced::theme::Button::Text)
+                    },
+                ]
+                .align_items(alignment::Alignment::Center)
+            )
+            .padding(15)
+            .style(iced::theme::Container::Box),
+            Space::with_height(12),
+            row![
+                text_input("Search output or task...", &self.log_search)
+                    .on_input(Message::LogSearchInput)
+                    .padding(8)
+                    .width(Length::Fixed(220.0)),
+                pick_list(&LogStatusFilter::ALL[..], Some(self.log_status_filter), Message::LogStatusFilterChanged)
+                    .padding(8),
+                pick_list(&LogRange::ALL[..], Some(self.log_range), Message::LogRangeChanged)
+                    .padding(8),
+                text(format!("{} matching", filtered_logs.len())).size(12),
+                Space::with_width(Length::Fill),
+                button("Export CSV")
+                    .on_press(Message::ExportLogsCsv(task_id))
+                    .padding(8)
+                    .style(iced::theme::Button::Secondary),
+                button("Export Zip")
+                    .on_press(Message::ExportLogBundle(task_id))
+                    .padding(8)
+                    .style(iced::theme::Button::Secondary),
+                if let Some(id) = task_id {
+                    Element::from(
+                        button("Export Report")
+                            .on_press(Message::ExportTaskReport(id))
+                            .padding(8)
+                            .style(iced::theme::Button::Secondary)
+                    )
+                } else {
+                    Space::with_width(0).into()
+                },
+            ]
+            .align_items(alignment::Alignment::Center)
+            .spacing(10),
+            Space::with_height(12),
+            metric_chart,
+            content,
+            Space::with_height(12),
+            pager,
+        ]
+        .into()
+    }
+    
+    // Replaces the whole layout with a single running task and its output
+    fn view_focus(&self, task: &Task) -> Element<'_, Message> {
+        let status: Element<Message> = match self.run_started.get(&task.id) {
+            Some(started) => text(format!("Running for {}", Self::format_duration(started.elapsed().as_secs())))
+                .size(16)
+                .style(Color::from_rgb(0.2, 0.6, 0.9))
+                .into(),
+            None => {
+                let last = self.logs.iter().rev().find(|l| l.task_id == task.id);
+                match last {
+                    Some(log) => text(format!(
+                        "Finished {} in {}ms",
+                        if log.success { "successfully" } else { "with failure" },
+                        log.duration_ms
+                    ))
+                    .size(16)
+                    .style(if log.success { Color::from_rgb(0.3, 0.8, 0.4) } else { Color::from_rgb(0.9, 0.3, 0.3) })
+                    .into(),
+                    None => text("Not running").size(16).into(),
+                }
+            }
+        };
+        
+        let output = if let Some(live) = self.live_output_text(task.id) {
+            if live.is_empty() { "Waiting for output...".to_string() } else { live }
+        } else if task.last_output.is_empty() {
+            "(no output)".to_string()
+        } else {
+            task.last_output.clone()
+        };
+        
+        let panel = container(
+            column![
+                row![
+                    text(&task.title).size(26),
+                    Space::with_width(Length::Fill),
+                    button("Exit Focus")
+                        .on_press(Message::ExitFocus)
+                        .padding(10)
+                        .style(iced::theme::Button::Secondary),
+                ]
+                .align_items(alignment::Alignment::Center),
+                text(&task.command).size(13).font(Font::MONOSPACE),
+                status,
+                Space::with_height(10),
+                container(
+                    scrollable(text(output).size(13).font(Font::MONOSPACE).width(Length::Fill))
+                        .height(Length::Fill)
+                )
+                .padding(12)
+                .height(Length::Fill)
+                .style(iced::theme::Container::Box),
+            ]
+            .spacing(10)
+        )
+        .padding(30)
+        .max_width(900)
+        .height(Length::Fill)
+        .style(iced::theme::Container::Box);
+        
+        container(panel)
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .center_x()
+            .padding(40)
+            .into()
+    }
+    
+    fn view_audit(&self) -> Element<'_, Message> {
+        let mut events = self.filtered_events();
+        events.sort_by_key(|e| std::cmp::Reverse(e.timestamp));
+        
+        let controls = container(
+            row![
+                text_input("Search events...", &self.audit_search)
+                    .on_input(Message::AuditSearchInput)
+                    .padding(8)
+                    .width(Length::Fixed(220.0)),
+                pick_list(&AppEventKind::ALL[..], self.audit_kind, |kind| Message::AuditKindChanged(Some(kind)))
+                    .placeholder("All event types")
+                    .padding(8),
+                button("All types")
+                    .on_press_maybe(self.audit_kind.is_some().then_some(Message::AuditKindChanged(None)))
+                    .padding(8)
+                    .style(iced::theme::Button::Secondary),
+                pick_list(&LogRange::ALL[..], Some(self.audit_range), Message::AuditRangeChanged)
+                    .padding(8),
+                text(format!("{} matching", events.len())).size(12),
+                Space::with_width(Length::Fill),
+                button("Export JSON")
+                    .on_press(Message::ExportAuditEvents { csv: false })
+                    .padding(8)
+                    .style(iced::theme::Button::Secondary),
+                button("Export CSV")
+                    .on_press(Message::ExportAuditEvents { csv: true })
+                    .padding(8)
+                    .style(iced::theme::Button::Secondary),
+            ]
+            .align_items(alignment::Alignment::Center)
+            .spacing(10)
+        )
+        .padding(12)
+        .style(iced::theme::Container::Box);
+        
+        let content: Element<Message> = if events.is_empty() {
+            container(text("No audit events").size(14))
+                .center_x()
+                .padding(40)
+                .into()
+        } else {
+            let mut list = column![].spacing(6);
+            for event in events {
+                list = list.push(
+                    container(
+                        row![
+                            text(event.timestamp.format("%b %d, %H:%M:%S").to_string())
+                                .size(12)
+                                .width(Length::Fixed(130.0)),
+                            text(event.kind.to_string())
+                                .size(12)
+                                .style(Color::from_rgb(0.2, 0.6, 0.9))
+                                .width(Length::Fixed(160.0)),
+                            text(&event.detail).size(12).width(Length::Fill),
+                        ]
+                        .align_items(alignment::Alignment::Center)
+                        .spacing(10)
+                    )
+                    .padding(10)
+                    .style(iced::theme::Container::Box)
+                );
+            }
+            scrollable(list).height(Length::Fill).into()
+        };
+        
+        column![
+            text("Audit Log").size(26),
+            Space::with_height(20),
+            controls,
+            Space::with_height(12),
+            content,
+        ]
+        .into()
+    }
+    
+    fn view_compare(&self, first: Uuid, second: Uuid) -> Element<'_, Message> {
+        let (Some(a), Some(b)) = (
+            self.tasks.iter().find(|t| t.id == first),
+            self.tasks.iter().find(|t| t.id == second),
+        ) else {
+            return container(text("One of the compared tasks no longer exists").size(14))
+                .center_x()
+                .padding(40)
+                .into();
+        };
+        
+        let recent_durations = |task: &Task| -> Vec<u64> {
+            self.logs.iter()
+                .rev()
+                .filter(|l| l.task_id == task.id)
+                .take(COMPARE_RECENT_RUNS)
+                .map(|l| l.duration_ms)
+                .collect()
+        };
+        let describe_durations = |durations: &[u64]| -> String {
+            if durations.is_empty() {
+                "No runs".to_string()
+            } else {
+                durations.iter().map(|d| format!("{}ms", d)).collect::<Vec<_>>().join(", ")
+            }
+        };
+        let average = |durations: &[u64]| -> String {
+            if durations.is_empty() {
+                "-".to_string()
+            } else {
+                format!("{}ms", durations.iter().sum::<u64>() / durations.len() as u64)
+            }
+        };
+        let (durations_a, durations_b) = (recent_durations(a), recent_durations(b));
+        
+        let rows = [
+            ("Command", a.command.clone(), b.command.clone()),
+            ("Schedule", a.schedule.describe(), b.schedule.describe()),
+            ("Status", 
+                if a.is_active { "Active" } else { "Inactive" }.to_string(),
+                if b.is_active { "Active" } else { "Inactive" }.to_string()),
+            ("Success rate",
+                self.format_success_rate(self.success_rate(a)),
+                self.format_success_rate(self.success_rate(b))),
+            ("Runs",
+                format!("{} ok / {} failed", a.success_count, a.failure_count),
+                format!("{} ok / {} failed", b.success_count, b.failure_count)),
+            ("Avg recent duration", average(&durations_a), average(&durations_b)),
+            ("Recent durations", describe_durations(&durations_a), describe_durations(&durations_b)),
+            ("Last run",
+                a.last_run.map(|t| t.format("%b %d, %H:%M:%S").to_string()).unwrap_or_else(|| "Never".to_string()),
+                b.last_run.map(|t| t.format("%b %d, %H:%M:%S").to_string()).unwrap_or_else(|| "Never".to_string())),
+        ];
+        
+        let mut table = column![
+            row![
+                text("").size(14).width(Length::Fixed(140.0)),
+                text(&a.title).size(16).width(Length::FillPortion(1)),
+                text(&b.title).size(16).width(Length::FillPortion(1)),
+            ]
+            .spacing(12),
+        ]
+        .spacing(10);
+        
+        for (label, left, right) in rows {
+            let color = if left != right {
+                Color::from_rgb(0.95, 0.7, 0.2)
+            } else {
+                Color::from_rgb(0.5, 0.5, 0.5)
+            };
+            table = table.push(
+                row![
+                    text(label).size(13).style(color).width(Length::Fixed(140.0)),
+                    text(left).size(13).width(Length::FillPortion(1)),
+                    text(right).size(13).width(Length::FillPortion(1)),
+                ]
+                .spacing(12)
+            );
+        }
+        
+        column![
+            row![
+                text("Compare Tasks").size(26),
+                Space::with_width(Length::Fill),
+                button("Back to Tasks")
+                    .on_press(Message::ChangeScreen(Screen::Tasks))
+                    .padding(8)
+                    .style(iced::theme::Button::Secondary),
+            ]
+            .align_items(alignment::Alignment::Center),
+            text("Differences are highlighted").size(11),
+            Space::with_height(20),
+            scrollable(
+                container(table)
+                    .padding(20)
+                    .style(iced::theme::Container::Box)
+            )
+            .height(Length::Fill),
+        ]
+        .into()
+    }
+    
+    fn view_task_detail(&self, id: Uuid) -> Element<'_, Message> {
+        let Some(task) = self.tasks.iter().find(|t| t.id == id) else {
+            return container(text("This task no longer exists").size(14))
+                .center_x()
+                .padding(40)
+                .into();
+        };
+        
+        let task_logs: Vec<&ExecutionLog> = self.logs.iter().filter(|l| l.task_id == id).collect();
+        
+        let rows = [
+            ("Command", task.command.clone()),
+            ("Schedule", task.schedule.describe()),
+            ("Status", if task.is_active { "Active" } else { "Inactive" }.to_string()),
+            ("Created", task.created_at.format("%b %d %Y, %H:%M").to_string()),
+            ("Runs", format!("{} ok / {} failed", task.success_count, task.failure_count)),
+            ("Success rate", self.format_success_rate(self.success_rate(task))),
+            ("Avg duration", self.avg_duration(id).map(format_millis).unwrap_or_else(|| "-".to_string())),
+            ("Last duration", self.last_duration(id).map(format_millis).unwrap_or_else(|| "-".to_string())),
+            ("Last run", task.last_run
+                .map(|t| t.format("%b %d, %H:%M:%S").to_string())
+                .unwrap_or_else(|| "Never".to_string())),
+        ];
+        
+        let mut details = column![].spacing(10);
+        for (label, value) in rows {
+            details = details.push(
+                row![
+                    text(label).size(13).style(Color::from_rgb(0.5, 0.5, 0.5)).width(Length::Fixed(140.0)),
+                    text(value).size(13).width(Length::Fill),
+                ]
+                .spacing(12)
+            );
+        }
+        
+        let (on_success, on_failure) = (task.notify_on_success, task.notify_on_failure);
+        let notify_toggles = row![
+            checkbox("Notify on success", on_success)
+                .on_toggle(move |on| Message::SetTaskNotifications(id, on, on_failure))
+                .size(16),
+            checkbox("Notify on failure", on_failure)
+                .on_toggle(move |on| Message::SetTaskNotifications(id, on_success, on))
+                .size(16),
+        ]
+        .spacing(16);
+        
+        let is_running = self.running_tasks.contains(&id);
+        let running_label = self.running_label(task).map(|(label, _)| label);
+        let actions = row![
+            button(text(running_label.unwrap_or_else(|| "Run Now".to_string())))
+                .on_press_maybe((!is_running).then_some(Message::RunTask(id)))
+                .padding(8)
+                .style(iced::theme::Button::Primary),
+            button("Edit")
+                .on_press(Message::EditTask(id))
+                .padding(8)
+                .style(iced::theme::Button::Secondary),
+            button("Clone")
+                .on_press(Message::CloneTask(id))
+                .padding(8)
+                .style(iced::theme::Button::Secondary),
+            button("All Logs")
+                .on_press(Message::ViewTaskLogs(id))
+                .padding(8)
+                .style(iced::theme::Button::Secondary),
+            button("Reset Stats")
+                .on_press(Message::RequestResetTaskStats(id))
+                .padding(8)
+                .style(iced::theme::Button::Destructive),
+        ]
+        .spacing(8);
+        
+        let live = self.live_output_text(id);
+        let output_title = if live.is_some() { "Live Output" } else { "Last Output" };
+        let last_output: Element<Message> = match live {
+            Some(live) if live.is_empty() => text("Waiting for output...").size(12).into(),
+            Some(live) => container(text(live).size(11).font(Font::MONOSPACE))
+                .padding([8, 12])
+                .width(Length::Fill)
+                .style(iced::theme::Container::Box)
+                .into(),
+            None if task.last_output.is_empty() => text("No output yet").size(12).into(),
+            None => container(text(&task.last_output).size(11))
+                .padding([8, 12])
+                .width(Length::Fill)
+                .style(iced::theme::Container::Box)
+                .into(),
+        };
+        
+        let mut recent = column![].spacing(6);
+        for log in task_logs.iter().rev().take(DETAIL_RECENT_LOGS) {
+            recent = recent.push(
+                row![
+                    text(if log.success { "OK" } else { "FAIL" })
+                        .size(12)
+                        .style(if log.success { Color::from_rgb(0.3, 0.8, 0.4) } else { Color::from_rgb(0.9, 0.3, 0.3) })
+                        .width(Length::Fixed(40.0)),
+                    text(log.timestamp.format("%b %d, %H:%M:%S").to_string()).size(12).width(Length::Fixed(140.0)),
+                    text(format!("{}ms", log.duration_ms)).size(12).width(Length::Fixed(80.0)),
+                    text(log.output_text().lines().next().unwrap_or("").to_string()).size(11),
+                ]
+                .spacing(10)
+            );
+        }
+        if task_logs.is_empty() {
+            recent = recent.push(text("No runs yet").size(12));
+        }
+        
+        column![
+            row![
+                text(&task.title).size(26),
+                Space::with_width(Length::Fill),
+                button("Back to Tasks")
+                    .on_press(Message::ChangeScreen(Screen::Tasks))
+                    .padding(8)
+                    .style(iced::theme::Button::Secondary),
+            ]
+            .align_items(alignment::Alignment::Center),
+            Space::with_height(20),
+            scrollable(
+                column![
+                    container(column![details, Space::with_height(12), notify_toggles, Space::with_height(15), actions])
+                        .padding(20)
+                        .style(iced::theme::Container::Box),
+                    Space::with_height(15),
+                    container(column![text(output_title).size(18), Space::with_height(10), last_output])
+                        .padding(20)
+                        .style(iced::theme::Container::Box),
+                    Space::with_height(15),
+                    container(column![text("Recent Runs").size(18), Space::with_height(10), recent])
+                        .padding(20)
+                        .style(iced::theme::Container::Box),
+                ]
+            )
+            .height(Length::Fill),
+        ]
+        .into()
+    }
+    
+    fn view_failures(&self) -> Element<'_, Message> {
+        let since = Local::now() - chrono::Duration::hours(FAILURE_WINDOW_HOURS);
+        let mut failures: Vec<&ExecutionLog> = self.logs.iter()
+            .filter(|l| !l.success && l.timestamp >= since)
+            .collect();
+        failures.sort_by_key(|l| std::cmp::Reverse(l.timestamp));
+        
+        let content: Element<Message> = if failures.is_empty() {
+            container(text(format!("No failures in the last {}h", FAILURE_WINDOW_HOURS)).size(14))
+                .center_x()
+                .padding(40)
+                .into()
+        } else {
+            let mut list = column![].spacing(8);
+            
+            for log in &failures {
+                let task = self.tasks.iter().find(|t| t.id == log.task_id);
+                let task_title = task.map(|t| t.title.as_str()).unwrap_or("Unknown");
+                let is_running = self.running_tasks.contains(&log.task_id);
+                
+                let rerun: Element<Message> = if task.is_some() {
+                    button(text(match task.and_then(|t| self.running_label(t)) {
+                        Some((label, _)) => label,
+                        None if is_running => "Running".to_string(),
+                        None => "Re-run".to_string(),
+                    }))
+                        .on_press(Message::RunTask(log.task_id))
+                        .padding(8)
+                        .style(iced::theme::Button::Primary)
+                        .into()
+                } else {
+                    Space::with_width(0).into()
+                };
+                
+                let card = container(
+                    column![
+                        row![
+                            container(text("FAIL").size(14))
+                                .padding([4, 8])
+                                .style(iced::theme::Container::Custom(Box::new(
+                                    ColoredContainer(Color::from_rgb(0.9, 0.3, 0.3))
+                                ))),
+                            text(task_title).size(14),
+                            Space::with_width(Length::Fill),
+                            text(log.timestamp.format("%b %d, %H:%M:%S").to_string()).size(12),
+                            text(format!("{}ms", log.duration_ms)).size(11),
+                            rerun,
+                            button("Logs")
+                                .on_press(Message::ViewTaskLogs(log.task_id))
+                                .padding(8)
+                                .style(iced::theme::Button::Secondary),
+                        ]
+                        .align_items(alignment::Alignment::Center)
+                        .spacing(10),
+                        if !log.output_text().is_empty() {
+                            container(text(log.output_text()).size(11))
+                                .padding([8, 12])
+                                .style(iced::theme::Container::Box)
+                        } else {
+                            container(Space::with_height(0))
+                        },
+                    ]
+                    .spacing(8)
+                )
+                .padding(12)
+                .style(iced::theme::Container::Box);
+                
+                list = list.push(card);
+            }
+            
+            scrollable(list).height(Length::Fixed(500.0)).into()
+        };
+        
+        column![
+            text("Recent Failures").size(26),
+            Space::with_height(20),
+            container(
+                text(format!("{} failed run(s) in the last {}h", failures.len(), FAILURE_WINDOW_HOURS)).size(16)
+            )
+            .padding(15)
+            .width(Length::Fill)
+            .style(iced::theme::Container::Box),
+            Space::with_height(12),
+            content,
+        ]
+        .into()
+    }
+    
+    fn view_calendar(&self) -> Element<'_, Message> {
+        let today = Local::now().date_naive();
+        let first_day = match self.calendar_range {
+            CalendarRange::Day => today,
+            CalendarRange::Week => {
+                today - chrono::Duration::days(today.weekday().num_days_from_monday() as i64)
+            }
+        };
+        let days = match self.calendar_range {
+            CalendarRange::Day => 1,
+            CalendarRange::Week => 7,
+        };
+        let start = local_midnight(first_day);
+        let end = local_midnight(first_day + chrono::Duration::days(days));
+        let span = (end - start).num_seconds().max(1) as f32;
+        let position = |at: DateTime<Local>| (at - start).num_seconds() as f32 / span;
+        
+        let rows: Vec<CalendarRow> = self.tasks.iter()
+            .map(|task| CalendarRow {
+                title: task.title.clone(),
+                scheduled: projected_runs(task, start, end)
+                    .into_iter()
+                    .map(position)
+                    .collect(),
+                runs: self.logs.iter()
+                    .filter(|l| l.task_id == task.id && l.timestamp >= start && l.timestamp < end)
+                    .map(|l| (position(l.timestamp), l.success))
+                    .collect(),
+            })
+            .collect();
+        
+        let range_button = |label: &str, range: CalendarRange| {
+            button(text(label).size(13))
+                .on_press(Message::CalendarRangeChanged(range))
+                .padding([6, 12])
+                .style(if self.calendar_range == range {
+                    iced::theme::Button::Primary
+                } else {
+                    iced::theme::Button::Secondary
+                })
+        };
+        
+        let content: Element<Message> = if rows.is_empty() {
+            container(text("No tasks to show").size(14))
+                .center_x()
+                .padding(40)
+                .into()
+        } else {
+            let height = CALENDAR_HEADER_HEIGHT + rows.len() as f32 * CALENDAR_ROW_HEIGHT;
+            let chart = CalendarChart {
+                rows,
+                days: days as u32,
+                first_day,
+                now: position(Local::now()),
+            };
+            scrollable(
+                canvas(chart)
+                    .width(Length::Fill)
+                    .height(Length::Fixed(height))
+            )
+            .height(Length::Fixed(500.0))
+            .into()
+        };
+        
+        column![
+            text("Execution Calendar").size(26),
+            Space::with_height(20),
+            container(
+                row![
+                    text(format!("{} - {}", start.format("%b %d"), 
+                        (end - chrono::Duration::days(1)).format("%b %d"))).size(16),
+                    Space::with_width(Length::Fill),
+                    text("| scheduled   ● success   ● failure").size(11),
+                    Space::with_width(20),
+                    range_button("Day", CalendarRange::Day),
+                    range_button("Week", CalendarRange::Week),
+                ]
+                .align_items(alignment::Alignment::Center)
+                .spacing(8)
+            )
+            .padding(15)
+            .style(iced::theme::Container::Box),
+            Space::with_height(12),
+            content,
+        ]
+        .into()
+    }
+    
+    fn view_settings(&self) -> Element<Message> {
+        let mut secret_list = column![].spacing(6);
+        for name in &self.config.secret_names {
+            secret_list = secret_list.push(
+                row![
+                    text(format!("{{{{secret:{}}}}}", name)).size(13).width(Length::Fill),
+                    button("Delete")
+                        .on_press(Message::DeleteSecret(name.clone()))
+                        .padding(6)
+                        .style(iced::theme::Button::Destructive),
+                ]
+                .align_items(alignment::Alignment::Center)
+                .spacing(10)
+            );
+        }
+        
+        let secrets = container(
+            column![
+                text("Secrets").size(18),
+                text("Stored in the system keyring and substituted only when a command runs. Resolved values are masked in logs.")
+                    .size(11),
+                Space::with_height(10),
+                secret_list,
+                Space::with_height(10),
+                row![
+                    text_input("NAME", &self.secret_name_input)
+                        .on_input(Message::SecretNameInput)
+                        .padding(8)
+                        .width(Length::Fixed(160.0)),
+                    text_input("Value", &self.secret_value_input)
+                        .on_input(Message::SecretValueInput)
+                        .secure(true)
+                        .padding(8)
+                        .width(Length::Fixed(250.0)),
+                    button("Save Secret")
+                        .on_press(Message::SaveSecret)
+                        .padding(8)
+                        .style(iced::theme::Button::Primary),
+                ]
+                .align_items(alignment::Alignment::Center)
+                .spacing(10),
+            ]
+        )
+        .padding(20)
+        .style(iced::theme::Container::Box);
+        
+        let unsaved = self.unsaved_settings();
+        let unsaved_status: Element<Message> = if unsaved.is_empty() {
+            text("All changes saved").size(12).into()
+        } else {
+            text(format!("Unsaved changes: {}", unsaved.join(", ")))
+                .size(12)
+                .style(Color::from_rgb(0.95, 0.7, 0.2))
+                .into()
+        };
+        
+        let content = column![
+            text("Settings").size(26),
+            Space::with_height(20),
+            container(
+                column![
+                    text("General").size(18),
+                    Space::with_height(15),
+                    row![
+                        text("Refresh Interval (seconds):").size(14).width(Length::Fixed(200.0)),
+                        text_input("5", &self.refresh_input)
+                            .on_input(Message::RefreshIntervalChanged)
+                            .padding(8)
+                            .width(Length::Fixed(100.0)),
+                        text("How often dashboard stats update; tasks are checked every second regardless").size(12),
+                    ]
+                    .align_items(alignment::Alignment::Center)
+                    .spacing(10),
+                    Space::with_height(12),
+                    row![
+                        text("Max Log Entries:").size(14).width(Length::Fixed(200.0)),
+                        text_input("500", &self.max_logs_input)
+                            .on_input(Message::MaxLogsChanged)
+                            .padding(8)
+                            .width(Length::Fixed(100.0)),
+                    ]
+                    .align_items(alignment::Alignment::Center)
+                    .spacing(10),
+                    Space::with_height(12),
+                    row![
+                        text("Save to Disk Every (seconds):").size(14).width(Length::Fixed(200.0)),
+                        text_input("10", &self.persist_interval_input)
+                            .on_input(Message::PersistIntervalChanged)
+                            .padding(8)
+                            .width(Length::Fixed(100.0)),
+                    ]
+                    .align_items(alignment::Alignment::Center)
+                    .spacing(10),
+                    Space::with_height(12),
+                    row![
+                        text("Tasks per Page:").size(14).width(Length::Fixed(200.0)),
+                        text_input("25", &self.task_page_size_input)
+                            .on_input(Message::TaskPageSizeChanged)
+                            .padding(8)
+                            .width(Length::Fixed(100.0)),
+                    ]
+                    .align_items(alignment::Alignment::Center)
+                    .spacing(10),
+                    Space::with_height(12),
+                    row![
+                        text("Max Concurrent Tasks:").size(14).width(Length::Fixed(200.0)),
+                        text_input("4", &self.max_concurrent_input)
+                            .on_input(Message::MaxConcurrentChanged)
+                            .padding(8)
+                            .width(Length::Fixed(100.0)),
+                    ]
+                    .align_items(alignment::Alignment::Center)
+                    .spacing(10),
+                    Space::with_height(12),
+                    row![
+                        text("Missed Runs:").size(14).width(Length::Fixed(200.0)),
+                        pick_list(&MissedRunPolicy::ALL[..], Some(self.settings_draft.missed_run_policy), Message::MissedRunPolicyChanged)
+                            .padding(8),
+                        text(format!("For runs that fell due while the app was closed (at most {} per task)", MAX_CATCH_UP_RUNS)).size(12),
+                    ]
+                    .align_items(alignment::Alignment::Center)
+                    .spacing(10),
+                    Space::with_height(12),
+                    row![
+                        text("Duplicate Titles:").size(14).width(Length::Fixed(200.0)),
+                        pick_list(&DuplicateTitlePolicy::ALL[..], Some(self.settings_draft.duplicate_titles), Message::DuplicateTitlePolicyChanged)
+                            .padding(8),
+                        text("When a new or edited task has the same name as another (ignoring case)").size(12),
+                    ]
+                    .align_items(alignment::Alignment::Center)
+                    .spacing(10),
+                    Space::with_height(12),
+                    row![
+                        text("Log Level:").size(14).width(Length::Fixed(200.0)),
+                        pick_list(&LogLevel::ALL[..], Some(self.settings_draft.log_level), Message::LogLevelChanged)
+                            .padding(8),
+                        text("Diagnostic messages written to stderr (RUST_LOG overrides this)").size(12),
+                    ]
+                    .align_items(alignment::Alignment::Center)
+                    .spacing(10),
+                    Space::with_height(12),
+                    row![
+                        text("Max Output (bytes):").size(14).width(Length::Fixed(200.0)),
+                        text_input("Unlimited", &self.global_max_output_input)
+                            .on_input(Message::GlobalMaxOutputChanged)
+                            .padding(8)
+                            .width(Length::Fixed(100.0)),
+                        text("Longer output is cut off in logs; tasks can override this").size(11),
+                    ]
+                    .align_items(alignment::Alignment::Center)
+                    .spacing(10),
+                    Space::with_height(12),
+                    row![
+                        text("Command Prefix:").size(14).width(Length::Fixed(200.0)),
+                        text_input(if cfg!(target_os = "windows") { "e.g. start /low /b /wait" } else { "e.g. nice -n 19" },
+                            &self.command_prefix_input)
+                            .on_input(Message::CommandPrefixChanged)
+                            .padding(8)
+                            .width(Length::Fixed(250.0)),
+                    ]
+                    .align_items(alignment::Alignment::Center)
+                    .spacing(10),
+                    Space::with_height(12),
+                    row![
+                        text("Data Directory:").size(14).width(Length::Fixed(200.0)),
+                        text_input(
+                            &default_data_dir().map(|d| d.display().to_string()).unwrap_or_default(),
+                            &self.data_dir_input
+                        )
+                            .on_input(Message::DataDirChanged)
+                            .padding(8)
+                            .width(Length::Fixed(330.0)),
+                    ]
+                    .align_items(alignment::Alignment::Center)
+                    .spacing(10),
+                    text(data_dir_status(self.config.data_dir.as_deref())).size(11),
+                    Space::with_height(12),
+                    row![
+                        text("Shell:").size(14).width(Length::Fixed(200.0)),
+                        pick_list(&SHELL_PRESETS[..], None::<&'static str>, Message::ShellPresetSelected)
+                            .placeholder("Choose...")
+                            .padding(8),
+                        text_input(if cfg!(target_os = "windows") { "cmd (default)" } else { "sh (default)" },
+                            &self.shell_input)
+                            .on_input(Message::ShellChanged)
+                            .padding(8)
+                            .width(Length::Fixed(160.0)),
+                        text_input("arguments", &self.shell_args_input)
+                            .on_input(Message::ShellArgsChanged)
+                            .padding(8)
+                            .width(Length::Fixed(160.0)),
+                    ]
+                    .align_items(alignment::Alignment::Center)
+                    .spacing(10),
+                    Space::with_height(12),
+                    checkbox("Start at login", self.settings_draft.start_at_login)
+                        .on_toggle(Message::StartAtLoginChanged)
+                        .size(16),
+                    Space::with_height(8),
+                    checkbox("Start minimized", self.settings_draft.start_minimized)
+                        .on_toggle(Message::StartMinimizedChanged)
+                        .size(16),
+                    Space::with_height(8),
+                    checkbox("Confirm before manual runs", self.settings_draft.confirm_before_run)
+                        .on_toggle(Message::ConfirmBeforeRunChanged)
+                        .size(16),
+                    Space::with_height(8),
+                    checkbox("Save execution logs to disk", self.settings_draft.log_to_file)
+                        .on_toggle(Message::LogToFileChanged)
+                        .size(16),
+                    Space::with_height(8),
+                    checkbox("Keep logs of deleted tasks", self.settings_draft.keep_orphaned_logs)
+                        .on_toggle(Message::KeepOrphanedLogsChanged)
+                        .size(16),
+                    Space::with_height(8),
+                    checkbox("Pause scheduled runs while on battery", self.settings_draft.pause_on_battery)
+                        .on_toggle(Message::PauseOnBatteryChanged)
+                        .size(16),
+                    Space::with_height(8),
+                    checkbox("Keep notification history between sessions", self.settings_draft.persist_notifications)
+                        .on_toggle(Message::PersistNotificationsChanged)
+                        .size(16),
+                    Space::with_height(8),
+                    checkbox("Desktop notification when a run finishes", self.settings_draft.desktop_notifications)
+                        .on_toggle(Message::DesktopNotificationsChanged)
+                        .size(16),
+                    Space::with_height(8),
+                    checkbox("Play a sound when a run finishes", self.settings_draft.alert_sound)
+                        .on_toggle(Message::AlertSoundChanged)
+                        .size(16),
                 ]
-                .spacing(8),
+            )
+            .padding(20)
+            .style(iced::theme::Container::Box),
+            Space::with_height(20),
+            container(
+                column![
+                    text("Schedules").size(18),
+                    text("Recalculate the next run of every active task from now, e.g. after changing the refresh interval.")
+                        .size(11),
+                    Space::with_height(10),
+                    button("Recompute All Schedules")
+                        .on_press(Message::RecomputeSchedules)
+                        .padding(10)
+                        .style(iced::theme::Button::Secondary),
+                ]
+            )
+            .padding(20)
+            .style(iced::theme::Container::Box),
+            Space::with_height(20),
+            secrets,
+            Space::with_height(20),
+            container(
+                column![
+                    text("Tasks").size(18),
+                    Space::with_height(10),
+                    text("Move your task set between machines as a JSON file. Imported tasks get new ids; tasks with the same title and command as an existing one are skipped.").size(12),
+                    Space::with_height(12),
+                    row![
+                        button("Export Tasks")
+                            .on_press(Message::ExportTasks)
+                            .padding(10)
+                            .style(iced::theme::Button::Secondary),
+                        button("Import Tasks")
+                            .on_press(Message::ImportTasks)
+                            .padding(10)
+                            .style(iced::theme::Button::Secondary),
+                    ]
+                    .spacing(10),
+                ]
+            )
+            .padding(20)
+            .style(iced::theme::Container::Box),
+            Space::with_height(20),
+            container(
+                column![
+                    text("About").size(18),
+                    Space::with_height(10),
+                    text(format!("Task with Me version {}", env!("CARGO_PKG_VERSION"))).size(14),
+                    Space::with_height(12),
+                    row![
+                        text("Release URL:").size(14).width(Length::Fixed(200.0)),
+                        text_input("https://api.github.com/repos/<owner>/<repo>/releases/latest", &self.update_url_input)
+                            .on_input(Message::UpdateUrlChanged)
+                            .padding(8)
+                            .width(Length::Fixed(400.0)),
+                    ]
+                    .align_items(alignment::Alignment::Center)
+                    .spacing(10),
+                    Space::with_height(12),
+                    row![
+                        button("Check for Updates")
+                            .on_press(Message::CheckForUpdates)
+                            .padding(10)
+                            .style(iced::theme::Button::Secondary),
+                        text(self.update_status.as_deref().unwrap_or("")).size(12),
+                    ]
+                    .align_items(alignment::Alignment::Center)
+                    .spacing(10),
+                ]
+            )
+            .padding(20)
+            .style(iced::theme::Container::Box),
+            Space::with_height(20),
+            container(
+                column![
+                    text("Appearance").size(18),
+                    Space::with_height(15),
+                    row![
+                        button("Light Theme")
+                            .on_press(Message::ThemeChanged(AppTheme::Light))
+                            .style(if self.settings_draft.theme == AppTheme::Light {
+                                iced::theme::Button::Primary
+                            } else {
+                                iced::theme::Button::Secondary
+                            })
+                            .padding(10),
+                        button("Dark Theme")
+                            .on_press(Message::ThemeChanged(AppTheme::Dark))
+                            .style(if self.settings_draft.theme == AppTheme::Dark {
+                                iced::theme::Button::Primary
+                            } else {
+                                iced::theme::Button::Secondary
+                            })
+                            .padding(10),
+                        button("System")
+                            .on_press(Message::ThemeChanged(AppTheme::System))
+                            .style(if self.settings_draft.theme == AppTheme::System {
+                                iced::theme::Button::Primary
+                            } else {
+                                iced::theme::Button::Secondary
+                            })
+                            .padding(10),
+                    ]
+                    .spacing(10),
+                    Space::with_height(15),
+                    row![
+                        text("Success Rate Precision:").size(14).width(Length::Fixed(200.0)),
+                        button("0 decimals")
+                            .on_press(Message::SuccessRatePrecisionChanged(0))
+                            .style(if self.settings_draft.success_rate_precision == 0 {
+                                iced::theme::Button::Primary
+                            } else {
+                                iced::theme::Button::Secondary
+                            })
+                            .padding(10),
+                        button("1 decimal")
+                            .on_press(Message::SuccessRatePrecisionChanged(1))
+                            .style(if self.settings_draft.success_rate_precision == 1 {
+                                iced::theme::Button::Primary
+                            } else {
+                                iced::theme::Button::Secondary
+                            })
+                            .padding(10),
+                    ]
+                    .align_items(alignment::Alignment::Center)
+                    .spacing(10),
+                    Space::with_height(15),
+                    row![
+                        text("UI Scale:").size(14).width(Length::Fixed(200.0)),
+                        button("−")
+                            .on_press_maybe((self.settings_draft.ui_scale > MIN_UI_SCALE)
+                                .then_some(Message::UiScaleChanged(self.settings_draft.ui_scale - UI_SCALE_STEP)))
+                            .style(iced::theme::Button::Secondary)
+                            .padding([10, 14]),
+                        text(format!("{:.0}%", self.settings_draft.ui_scale * 100.0))
+                            .size(14)
+                            .width(Length::Fixed(50.0))
+                            .horizontal_alignment(alignment::Horizontal::Center),
+                        button("+")
+                            .on_press_maybe((self.settings_draft.ui_scale < MAX_UI_SCALE)
+                                .then_some(Message::UiScaleChanged(self.settings_draft.ui_scale + UI_SCALE_STEP)))
+                            .style(iced::theme::Button::Secondary)
+                            .padding([10, 14]),
+                        button("Reset")
+                            .on_press(Message::UiScaleChanged(default_ui_scale()))
+                            .style(iced::theme::Button::Secondary)
+                            .padding(10),
+                        text("Applied when settings are saved").size(11),
+                    ]
+                    .align_items(alignment::Alignment::Center)
+                    .spacing(10),
+                ]
+            )
+            .padding(20)
+            .style(iced::theme::Container::Box),
+            Space::with_height(20),
+            row![
+                button(text(if self.saving_settings {
+                    format!("Saving {}", spinner_frame())
+                } else {
+                    "Save Settings".to_string()
+                }))
+                    .on_press_maybe((!self.saving_settings).then_some(Message::SaveSettings))
+                    .padding(12)
+                    .style(iced::theme::Button::Primary),
+                button("Discard")
+                    .on_press_maybe((!unsaved.is_empty()).then_some(Message::DiscardSettings))
+                    .padding(12)
+                    .style(iced::theme::Button::Secondary),
+                unsaved_status,
+            ]
+            .align_items(alignment::Alignment::Center)
+            .spacing(10),
+        ];
+        
+        scrollable(content).height(Length::Fill).into()
+    }
+    
+    fn view_notifications(&self) -> Element<Message> {
+        if self.notifications.is_empty() {
+            return Space::with_height(0).into();
+        }
+        
+        let mut list = column![].spacing(8);
+        
+        for notif in self.notifications.iter().rev() {
+            let (label, color) = match notif.level {
+                NotificationLevel::Info => ("INFO", Color::from_rgb(0.2, 0.6, 0.9)),
+                NotificationLevel::Success => ("SUCCESS", Color::from_rgb(0.3, 0.8, 0.4)),
+                NotificationLevel::Warning => ("WARNING", Color::from_rgb(0.95, 0.7, 0.2)),
+                NotificationLevel::Error => ("ERROR", Color::from_rgb(0.9, 0.3, 0.3)),
+            };
+            
+            let card = container(
+                row![
+                    container(text(label).size(11))
+                        .padding([4, 8])
+                        .style(iced::theme::Container::Custom(Box::new(
+                            ColoredContainer(color)
+                        ))),
+                    text(&notif.message).size(13).width(Length::Fill),
+                    button("X")
+                        .on_press(Message::CloseNotification(notif.id))
+                        .padding(6)
+                        .style(iced::theme::Button::Destructive),
+                ]
+                .align_items(alignment::Alignment::Center)
+                .spacing(10)
+            )
+            .padding(12)
+            .style(iced::theme::Container::Box);
+            
+            list = list.push(card);
+        }
+        
+        container(list)
+            .width(Length::Fill)
+            .padding([0, 0, 15, 0])
+            .into()
+    }
+}
+
+//Custom Container Style
+struct ColoredContainer(Color);
+
+impl iced::widget::container::StyleSheet for ColoredContainer {
+    type Style = Theme;
+    
+    fn appearance(&self, _style: &Self::Style) -> iced::widget::container::Appearance {
+        iced::widget::container::Appearance {
+            background: Some(iced::Background::Color(self.0)),
+            ..Default::default()
+        }
+    }
+}
+
+//Calendar Chart
+const CALENDAR_LABEL_WIDTH: f32 = 160.0;
+const CALENDAR_HEADER_HEIGHT: f32 = 24.0;
+const CALENDAR_ROW_HEIGHT: f32 = 28.0;
+const CALENDAR_MAX_PROJECTED: usize = 500;
+
+struct CalendarRow {
+    title: String,
+    // Positions are fractions of the visible range (0.0 = start, 1.0 = end)
+    scheduled: Vec<f32>,
+    runs: Vec<(f32, bool)>,
+}
+
+struct CalendarChart {
+    rows: Vec<CalendarRow>,
+    days: u32,
+    first_day: chrono::NaiveDate,
+    now: f32,
+}
+
+impl canvas::Program<Message> for CalendarChart {
+    type State = ();
+    
+    fn draw(
+        &self,
+        _state: &(),
+        renderer: &Renderer,
+        theme: &Theme,
+        bounds: Rectangle,
+        _cursor: mouse::Cursor,
+    ) -> Vec<canvas::Geometry> {
+        let mut frame = canvas::Frame::new(renderer, bounds.size());
+        let palette = theme.extended_palette();
+        let text_color = palette.background.base.text;
+        let grid_color = palette.background.strong.color;
+        
+        let grid_width = (bounds.width - CALENDAR_LABEL_WIDTH).max(1.0);
+        let x_at = |fraction: f32| CALENDAR_LABEL_WIDTH + fraction * grid_width;
+        let grid_bottom = CALENDAR_HEADER_HEIGHT + self.rows.len() as f32 * CALENDAR_ROW_HEIGHT;
+        
+        // Column divisions: hours for a single day, days for a week
+        let (divisions, label_every) = if self.days == 1 { (24, 3) } else { (self.days, 1) };
+        for i in 0..=divisions {
+            let x = x_at(i as f32 / divisions as f32);
+            frame.fill_rectangle(
+                Point::new(x, CALENDAR_HEADER_HEIGHT),
+                Size::new(1.0, grid_bottom - CALENDAR_HEADER_HEIGHT),
+                grid_color,
+            );
+            
+            if i < divisions && i % label_every == 0 {
+                let label = if self.days == 1 {
+                    format!("{:02}:00", i)
+                } else {
+                    (self.first_day + chrono::Duration::days(i as i64)).format("%a %d").to_string()
+                };
+                frame.fill_text(canvas::Text {
+                    content: label,
+                    position: Point::new(x + 4.0, 4.0),
+                    color: text_color,
+                    size: iced::Pixels(11.0),
+                    ..Default::default()
+                });
+            }
+        }
+        
+        for (idx, row) in self.rows.iter().enumerate() {
+            let top = CALENDAR_HEADER_HEIGHT + idx as f32 * CALENDAR_ROW_HEIGHT;
+            let middle = top + CALENDAR_ROW_HEIGHT / 2.0;
+            
+            frame.fill_rectangle(
+                Point::new(0.0, top + CALENDAR_ROW_HEIGHT - 1.0),
+                Size::new(bounds.width, 1.0),
+                grid_color,
+            );
+            
+            let mut title: String = row.title.chars().take(22).collect();
+            if row.title.chars().count() > 22 {
+                title.push('…');
+            }
+            frame.fill_text(canvas::Text {
+                content: title,
+                position: Point::new(4.0, middle - 7.0),
+                color: text_color,
+                size: iced::Pixels(12.0),
+                ..Default::default()
+            });
+            
+            for &at in &row.scheduled {
+                frame.fill_rectangle(
+                    Point::new(x_at(at) - 1.0, top + 6.0),
+                    Size::new(2.0, CALENDAR_ROW_HEIGHT - 12.0),
+                    Color::from_rgb(0.2, 0.6, 0.9),
+                );
+            }
+            
+            for &(at, success) in &row.runs {
+                let color = if success {
+                    Color::from_rgb(0.3, 0.8, 0.4)
+                } else {
+                    Color::from_rgb(0.9, 0.3, 0.3)
+                };
+                frame.fill(&canvas::Path::circle(Point::new(x_at(at), middle), 4.0), color);
+            }
+        }
+        
+        if (0.0..=1.0).contains(&self.now) {
+            frame.fill_rectangle(
+                Point::new(x_at(self.now) - 1.0, CALENDAR_HEADER_HEIGHT),
+                Size::new(2.0, grid_bottom - CALENDAR_HEADER_HEIGHT),
+                Color::from_rgb(0.95, 0.7, 0.2),
+            );
+        }
+        
+        vec![frame.into_geometry()]
+    }
+}
+
+//Run Sparkline
+const SPARKLINE_RUNS: usize = 20;
+const SPARKLINE_WIDTH: f32 = 80.0;
+const SPARKLINE_HEIGHT: f32 = 14.0;
+
+// One bar per run, oldest on the left; a fixed slot width keeps a bar's
+// position stable as new runs come in. Just rectangles, so cheap per card
+struct RunSparkline {
+    results: Vec<bool>,
+}
+
+impl canvas::Program<Message> for RunSparkline {
+    type State = ();
+    
+    fn draw(
+        &self,
+        _state: &(),
+        renderer: &Renderer,
+        _theme: &Theme,
+        bounds: Rectangle,
+        _cursor: mouse::Cursor,
+    ) -> Vec<canvas::Geometry> {
+        let mut frame = canvas::Frame::new(renderer, bounds.size());
+        let slot = bounds.width / SPARKLINE_RUNS as f32;
+        let offset = (SPARKLINE_RUNS - self.results.len().min(SPARKLINE_RUNS)) as f32 * slot;
+        
+        for (idx, success) in self.results.iter().enumerate() {
+            let color = if *success {
+                Color::from_rgb(0.3, 0.8, 0.4)
+            } else {
+                Color::from_rgb(0.9, 0.3, 0.3)
+            };
+            frame.fill_rectangle(
+                Point::new(offset + idx as f32 * slot, 0.0),
+                Size::new((slot - 1.0).max(1.0), bounds.height),
+                color,
+            );
+        }
+        
+        vec![frame.into_geometry()]
+    }
+}
+
+//Runs per Day Chart
+const RUNS_CHART_DAYS: u64 = 14;
+const RUNS_CHART_HEIGHT: f32 = 160.0;
+const RUNS_CHART_LABEL_HEIGHT: f32 = 16.0;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct DayRuns {
+    day: chrono::NaiveDate,
+    succeeded: usize,
+    failed: usize,
+}
+
+// One entry per day from `days` days ago up to today, oldest first; days
+// without runs are included with zero counts
+fn runs_per_day<'a>(
+    logs: impl IntoIterator<Item = &'a ExecutionLog>,
+    today: chrono::NaiveDate,
+    days: u64,
+) -> Vec<DayRuns> {
+    let first = today - chrono::Days::new(days.saturating_sub(1));
+    let mut buckets: Vec<DayRuns> = first.iter_days()
+        .take(days as usize)
+        .map(|day| DayRuns { day, succeeded: 0, failed: 0 })
+        .collect();
+    
+    for log in logs {
+        let day = log.timestamp.date_naive();
+        if day < first || day > today {
+            continue;
+        }
+        let bucket = &mut buckets[(day - first).num_days() as usize];
+        if log.success {
+            bucket.succeeded += 1;
+        } else {
+            bucket.failed += 1;
+        }
+    }
+    buckets
+}
+
+// Stacked bars, successes at the bottom, with the day of month under each bar
+struct RunsPerDayChart {
+    days: Vec<DayRuns>,
+}
+
+impl canvas::Program<Message> for RunsPerDayChart {
+    type State = ();
+    
+    fn draw(
+        &self,
+        _state: &(),
+        renderer: &Renderer,
+        theme: &Theme,
+        bounds: Rectangle,
+        _cursor: mouse::Cursor,
+    ) -> Vec<canvas::Geometry> {
+        let mut frame = canvas::Frame::new(renderer, bounds.size());
+        let palette = theme.extended_palette();
+        let text_color = palette.background.base.text;
+        
+        let max = self.days.iter().map(|d| d.succeeded + d.failed).max().unwrap_or(0).max(1);
+        let plot_height = (bounds.height - RUNS_CHART_LABEL_HEIGHT - 14.0).max(1.0);
+        let bottom = 14.0 + plot_height;
+        let slot = bounds.width / self.days.len().max(1) as f32;
+        let bar_width = (slot * 0.7).max(1.0);
+        let height_of = |count: usize| count as f32 / max as f32 * plot_height;
+        
+        frame.fill_text(canvas::Text {
+            content: format!("{} runs", max),
+            position: Point::new(0.0, 0.0),
+            color: text_color,
+            size: iced::Pixels(11.0),
+            ..Default::default()
+        });
+        frame.fill_rectangle(Point::new(0.0, bottom), Size::new(bounds.width, 1.0), palette.background.strong.color);
+        
+        for (idx, day) in self.days.iter().enumerate() {
+            let x = idx as f32 * slot + (slot - bar_width) / 2.0;
+            let ok_height = height_of(day.succeeded);
+            let failed_height = height_of(day.failed);
+            frame.fill_rectangle(
+                Point::new(x, bottom - ok_height),
+                Size::new(bar_width, ok_height),
+                Color::from_rgb(0.3, 0.8, 0.4),
+            );
+            frame.fill_rectangle(
+                Point::new(x, bottom - ok_height - failed_height),
+                Size::new(bar_width, failed_height),
+                Color::from_rgb(0.9, 0.3, 0.3),
+            );
+            frame.fill_text(canvas::Text {
+                content: day.day.format("%-d").to_string(),
+                position: Point::new(idx as f32 * slot + slot / 2.0, bottom + 3.0),
+                color: text_color,
+                size: iced::Pixels(10.0),
+                horizontal_alignment: alignment::Horizontal::Center,
+                ..Default::default()
+            });
+        }
+        
+        vec![frame.into_geometry()]
+    }
+}
+
+//Metric Chart
+const METRIC_CHART_HEIGHT: f32 = 140.0;
+const METRIC_LABEL_WIDTH: f32 = 60.0;
+
+// Metric values over runs, oldest first, spaced evenly by run
+struct MetricChart {
+    points: Vec<(DateTime<Local>, f64)>,
+}
+
+impl canvas::Program<Message> for MetricChart {
+    type State = ();
+    
+    fn draw(
+        &self,
+        _state: &(),
+        renderer: &Renderer,
+        theme: &Theme,
+        bounds: Rectangle,
+        _cursor: mouse::Cursor,
+    ) -> Vec<canvas::Geometry> {
+        let mut frame = canvas::Frame::new(renderer, bounds.size());
+        let palette = theme.extended_palette();
+        let text_color = palette.background.base.text;
+        let grid_color = palette.background.strong.color;
+        
+        let min = self.points.iter().map(|(_, v)| *v).fold(f64::INFINITY, f64::min);
+        let max = self.points.iter().map(|(_, v)| *v).fold(f64::NEG_INFINITY, f64::max);
+        let span = if max > min { max - min } else { 1.0 };
+        
+        let plot_width = (bounds.width - METRIC_LABEL_WIDTH).max(1.0);
+        let plot_height = (bounds.height - 20.0).max(1.0);
+        let last = (self.points.len() - 1).max(1) as f32;
+        let point_at = |idx: usize, value: f64| Point::new(
+            METRIC_LABEL_WIDTH + idx as f32 / last * plot_width,
+            4.0 + (1.0 - ((value - min) / span) as f32) * plot_height,
+        );
+        
+        for (label, y) in [(max, 4.0), (min, 4.0 + plot_height)] {
+            frame.fill_rectangle(Point::new(METRIC_LABEL_WIDTH, y), Size::new(plot_width, 1.0), grid_color);
+            frame.fill_text(canvas::Text {
+                content: format!("{:.2}", label),
+                position: Point::new(0.0, y - 6.0),
+                color: text_color,
+                size: iced::Pixels(11.0),
+                ..Default::default()
+            });
+        }
+        
+        let line = canvas::Path::new(|path| {
+            for (idx, (_, value)) in self.points.iter().enumerate() {
+                if idx == 0 {
+                    path.move_to(point_at(idx, *value));
+                } else {
+                    path.line_to(point_at(idx, *value));
+                }
+            }
+        });
+        frame.stroke(&line, canvas::Stroke::default()
+            .with_color(Color::from_rgb(0.2, 0.6, 0.9))
+            .with_width(2.0));
+        
+        for (idx, (_, value)) in self.points.iter().enumerate() {
+            frame.fill(&canvas::Path::circle(point_at(idx, *value), 3.0), Color::from_rgb(0.2, 0.6, 0.9));
+        }
+        
+        if let (Some((first, _)), Some((latest, _))) = (self.points.first(), self.points.last()) {
+            frame.fill_text(canvas::Text {
+                content: format!("{}  ->  {}", first.format("%b %d %H:%M"), latest.format("%b %d %H:%M")),
+                position: Point::new(METRIC_LABEL_WIDTH, bounds.height - 14.0),
+                color: text_color,
+                size: iced::Pixels(11.0),
+                ..Default::default()
+            });
+        }
+        
+        vec![frame.into_geometry()]
+    }
+}
+
+// Header of a day group in the Logs screen: "Today", "Yesterday", "Mar 3"
+fn day_label(day: chrono::NaiveDate, today: chrono::NaiveDate) -> String {
+    if day == today {
+        "Today".to_string()
+    } else if today.pred_opt() == Some(day) {
+        "Yesterday".to_string()
+    } else if day.year() == today.year() {
+        day.format("%b %-d").to_string()
+    } else {
+        day.format("%b %-d, %Y").to_string()
+    }
+}
+
+fn local_midnight(date: chrono::NaiveDate) -> DateTime<Local> {
+    date.and_hms_opt(0, 0, 0)
+        .and_then(|dt| dt.and_local_timezone(Local).earliest())
+        .unwrap_or_else(Local::now)
+}
+
+// Upcoming runs of an active task within [start, end), following its schedule from next_run
+fn projected_runs(task: &Task, start: DateTime<Local>, end: DateTime<Local>) -> Vec<DateTime<Local>> {
+    let mut runs = Vec::new();
+    if !task.is_active {
+        return runs;
+    }
+    
+    let schedule = &task.schedule;
+    let mut next = task.next_run;
+    while let Some(at) = next {
+        if at >= end || runs.len() >= CALENDAR_MAX_PROJECTED {
+            break;
+        }
+        if at >= start {
+            runs.push(at);
+        }
+        next = schedule.next_after(at).filter(|n| *n > at);
+    }
+    runs
+}
+
+//File Watching
+const WATCH_DEBOUNCE: Duration = Duration::from_millis(500);
+
+// Emits WatchTriggered once changes under `path` have settled for WATCH_DEBOUNCE
+fn watch_path(id: Uuid, path: PathBuf) -> Subscription<Message> {
+    iced::subscription::channel((id, path.clone()), 10, move |mut output| async move {
+        use iced::futures::SinkExt;
+        use notify::Watcher;
+        
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let watcher = notify::recommended_watcher(move |result: notify::Result<notify::Event>| {
+            if let Ok(event) = result {
+                if !event.kind.is_access() {
+                    let _ = tx.send(());
+                }
+            }
+        });
+        
+        let started = watcher.and_then(|mut watcher| {
+            watcher.watch(&path, notify::RecursiveMode::Recursive).map(|_| watcher)
+        });
+        // Keep the watcher alive for as long as the subscription runs
+        let _watcher = match started {
+            Ok(watcher) => Some(watcher),
+            Err(e) => {
+                let _ = output.send(Message::WatchFailed(id, e.to_string())).await;
+                None
+            }
+        };
+        
+        loop {
+            if rx.recv().await.is_none() {
+                std::future::pending::<()>().await;
+            }
+            while let Ok(Some(())) = tokio::time::timeout(WATCH_DEBOUNCE, rx.recv()).await {}
+            let _ = output.send(Message::WatchTriggered(id)).await;
+        }
+    })
+}
+
+// Advances once per Tick, which is what redraws the view while an operation is pending
+fn spinner_frame() -> char {
+    ['|', '/', '-', '\\'][Local::now().timestamp().rem_euclid(4) as usize]
+}
+
+//Storage Functions
+// --data-dir moves everything, config.json included. The data_dir setting can
+// only move the other files, since it is read from config.json
+static CLI_DATA_DIR: std::sync::OnceLock<PathBuf> = std::sync::OnceLock::new();
+static CONFIGURED_DATA_DIR: std::sync::OnceLock<PathBuf> = std::sync::OnceLock::new();
+
+fn default_data_dir() -> Option<PathBuf> {
+    dirs::data_local_dir().map(|dir| dir.join("task-with-me"))
+}
+
+// Where data files are read and written, without creating it
+fn data_dir_path() -> Option<PathBuf> {
+    CLI_DATA_DIR.get()
+        .or(CONFIGURED_DATA_DIR.get())
+        .cloned()
+        .or_else(default_data_dir)
+}
+
+fn create_dir(dir: Option<PathBuf>) -> Result<PathBuf, AppError> {
+    let dir = dir.ok_or_else(|| AppError::Config("Cannot determine data directory".to_string()))?;
+    fs::create_dir_all(&dir)?;
+    Ok(dir)
+}
+
+fn get_data_dir() -> Result<PathBuf, AppError> {
+    create_dir(data_dir_path())
+}
+
+fn get_config_dir() -> Result<PathBuf, AppError> {
+    create_dir(CLI_DATA_DIR.get().cloned().or_else(default_data_dir))
+}
+
+// Shown under the Data Directory setting
+fn data_dir_status(saved: Option<&Path>) -> String {
+    let current = data_dir_path().map(|d| d.display().to_string()).unwrap_or_else(|| "unknown".to_string());
+    if CLI_DATA_DIR.get().is_some() {
+        return format!("Files are stored in {} (set by --data-dir)", current);
+    }
+    let in_use = CONFIGURED_DATA_DIR.get().map(PathBuf::as_path);
+    if saved != in_use {
+        let next = saved.map(Path::to_path_buf).or_else(default_data_dir).unwrap_or_default();
+        return format!(
+            "Files are stored in {}; {} is used after a restart (existing files are not moved)",
+            current,
+            next.display()
+        );
+    }
+    format!("Files are stored in {}", current)
+}
+
+fn load_data_files() -> Command<Message> {
+    Command::batch(vec![
+        Command::perform(load_tasks(), Message::TasksLoaded),
+        Command::perform(load_logs(), Message::LogsLoaded),
+        Command::perform(load_events(), Message::EventsLoaded),
+        Command::perform(load_notifications(), Message::NotificationsLoaded),
+        Command::perform(load_templates(), Message::TemplatesLoaded),
+    ])
+}
+
+// Accepts `--data-dir PATH` and `--data-dir=PATH`
+fn parse_data_dir_arg(mut args: impl Iterator<Item = String>) -> Option<PathBuf> {
+    while let Some(arg) = args.next() {
+        if arg == "--data-dir" {
+            return args.next().map(PathBuf::from);
+        }
+        if let Some(dir) = arg.strip_prefix("--data-dir=") {
+            return Some(PathBuf::from(dir));
+        }
+    }
+    None
+}
+
+// A loaded file's contents, noting when they came from the .bak copy and
+// where an unreadable file was moved
+#[derive(Debug, Clone)]
+struct Loaded<T> {
+    value: T,
+    restored_from: Option<PathBuf>,
+    moved_aside: Option<PathBuf>,
+}
+
+impl<T> Loaded<T> {
+    fn fresh(value: T) -> Self {
+        Loaded { value, restored_from: None, moved_aside: None }
+    }
+}
+
+// tasks.json -> tasks.json.bak, logs.jsonl -> logs.jsonl.bak
+fn backup_path(path: &Path) -> PathBuf {
+    with_suffix(path, ".bak")
+}
+
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(suffix);
+    PathBuf::from(name)
+}
+
+// Whether `content` is well-formed for the file's format: one JSON value, or
+// one JSON value per line for .jsonl
+fn is_valid_data(path: &Path, content: &str) -> bool {
+    if path.extension().is_some_and(|ext| ext == "jsonl") {
+        content.lines()
+            .filter(|line| !line.trim().is_empty())
+            .all(|line| serde_json::from_str::<serde_json::Value>(line).is_ok())
+    } else {
+        serde_json::from_str::<serde_json::Value>(content).is_ok()
+    }
+}
+
+// Writes to a temporary file next to `path` and renames it into place, so a
+// crash mid-write never leaves a truncated file. Each write gets its own
+// temporary file, so two writes of the same file can't clobber each other's.
+// The previous contents are kept as .bak, but only if they still parse, so a
+// good backup is never replaced by a corrupt one
+fn write_atomic(path: &Path, content: &str) -> Result<(), AppError> {
+    let tmp = with_suffix(path, &format!(".{}.tmp", Uuid::new_v4()));
+    let written = (|| {
+        let mut file = fs::File::create(&tmp)?;
+        file.write_all(content.as_bytes())?;
+        file.sync_all()?;
+        
+        if let Ok(previous) = fs::read_to_string(path) {
+            if is_valid_data(path, &previous) {
+                fs::write(backup_path(path), previous)?;
+            }
+        }
+        
+        fs::rename(&tmp, path)
+    })();
+    if written.is_err() {
+        let _ = fs::remove_file(&tmp);
+    }
+    Ok(written?)
+}
+
+// None when the file does not exist. A file that fails to parse is moved to
+// <name>.corrupt-<timestamp> so the next save cannot overwrite it, and the
+// .bak copy is used instead, or an empty value if there is no good backup
+fn read_json<T: serde::de::DeserializeOwned + Default>(path: &Path) -> Result<Option<Loaded<T>>, AppError> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    
+    let content = fs::read_to_string(path)?;
+    if let Ok(value) = serde_json::from_str(&content) {
+        return Ok(Some(Loaded::fresh(value)));
+    }
+    
+    // Never replace an earlier corrupt copy from the same second
+    let first = corrupt_path(path, Local::now());
+    let mut moved_aside = first.clone();
+    let mut n = 1;
+    while moved_aside.exists() {
+        moved_aside = PathBuf::from(format!("{}-{}", first.display(), n));
+        n += 1;
+    }
+    fs::rename(path, &moved_aside)?;
+    
+    let backup = backup_path(path);
+    let restored = fs::read_to_string(&backup)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok());
+    Ok(Some(match restored {
+        Some(value) => Loaded { value, restored_from: Some(backup), moved_aside: Some(moved_aside) },
+        None => Loaded { value: T::default(), restored_from: None, moved_aside: Some(moved_aside) },
+    }))
+}
+
+fn corrupt_path(path: &Path, now: DateTime<Local>) -> PathBuf {
+    let name = path.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+    path.with_file_name(format!("{}.corrupt-{}", name, now.format("%Y%m%d-%H%M%S")))
+}
+
+async fn load_config() -> Result<Loaded<Config>, AppError> {
+    let path = get_config_dir()?.join("config.json");
+    
+    match read_json(&path)? {
+        Some(loaded) => Ok(loaded),
+        None => {
+            let config = Config::default();
+            let content = serde_json::to_string_pretty(&config)?;
+            write_atomic(&path, &content)?;
+            Ok(Loaded::fresh(config))
+        }
+    }
+}
+
+async fn save_config(config: Config) -> Result<(), AppError> {
+    let path = get_config_dir()?.join("config.json");
+    let content = serde_json::to_string_pretty(&config)?;
+    write_atomic(&path, &content)
+}
+
+async fn set_start_at_login(enabled: bool) -> Result<(), AppError> {
+    let exe = std::env::current_exe()?;
+    let auto_launch = AutoLaunchBuilder::new()
+        .set_app_name("task-with-me")
+        .set_app_path(&exe.to_string_lossy())
+        .set_use_launch_agent(true)
+        .build()
+        .map_err(|e| AppError::Config(e.to_string()))?;
+    
+    let is_enabled = auto_launch.is_enabled().unwrap_or(false);
+    let result = if enabled && !is_enabled {
+        auto_launch.enable()
+    } else if !enabled && is_enabled {
+        auto_launch.disable()
+    } else {
+        Ok(())
+    };
+    
+    result.map_err(|e| AppError::Config(e.to_string()))
+}
+
+async fn tasks_file_missing() -> bool {
+    get_data_dir().is_ok_and(|dir| !dir.join("tasks.json").exists())
+}
+
+async fn load_tasks() -> Result<Loaded<Vec<Task>>, AppError> {
+    let path = get_data_dir()?.join("tasks.json");
+    Ok(read_json(&path)?.unwrap_or_else(|| Loaded::fresh(Vec::new())))
+}
+
+// Logs are stored one JSON object per line so new runs can be appended.
+// A logs.json array from older versions is converted on first load.
+async fn load_logs() -> Result<Loaded<Vec<ExecutionLog>>, AppError> {
+    let dir = get_data_dir()?;
+    let path = dir.join("logs.jsonl");
+    
+    if !path.exists() {
+        let legacy = dir.join("logs.json");
+        let Some(loaded) = read_json::<Vec<ExecutionLog>>(&legacy)? else {
+            return Ok(Loaded::fresh(Vec::new()));
+        };
+        write_atomic(&path, &logs_to_jsonl(&loaded.value)?)?;
+        fs::remove_file(&legacy)?;
+        return Ok(loaded);
+    }
+    
+    let (logs, skipped) = parse_log_lines(&fs::read_to_string(&path)?);
+    if skipped > 0 {
+        log::warn!("Skipped {} unreadable line(s) in {}", skipped, path.display());
+    }
+    Ok(Loaded::fresh(logs))
+}
+
+// Returns the logs and the number of lines that could not be parsed, e.g. a
+// line cut short by a crash mid-append. Repeated ids keep the later entry.
+fn parse_log_lines(content: &str) -> (Vec<ExecutionLog>, usize) {
+    let mut skipped = 0;
+    let mut logs: Vec<ExecutionLog> = Vec::new();
+    let mut positions: HashMap<Uuid, usize> = HashMap::new();
+    
+    for line in content.lines().filter(|l| !l.trim().is_empty()) {
+        match serde_json::from_str::<ExecutionLog>(line) {
+            Ok(log) => match positions.get(&log.id) {
+                Some(&pos) => logs[pos] = log,
+                None => {
+                    positions.insert(log.id, logs.len());
+                    logs.push(log);
+                }
+            },
+            Err(_) => skipped += 1,
+        }
+    }
+    
+    (logs, skipped)
+}
+
+fn logs_to_jsonl(logs: &[ExecutionLog]) -> Result<String, AppError> {
+    let mut content = String::new();
+    for log in logs {
+        content.push_str(&serde_json::to_string(log)?);
+        content.push('\n');
+    }
+    Ok(content)
+}
+
+// Generation of the snapshot last written to tasks.json. Held for the whole write,
+// so writes happen one at a time, and a snapshot older than the one on disk is
+// dropped: a slow flush can't bring back a deleted task or undo a reorder
+static TASKS_WRITTEN: std::sync::Mutex<u64> = std::sync::Mutex::new(0);
+
+// Replaces the whole file with `tasks`, unless a newer snapshot got there first
+async fn write_tasks(generation: u64, tasks: Vec<Task>) -> Result<(), AppError> {
+    let path = get_data_dir()?.join("tasks.json");
+    let mut written = TASKS_WRITTEN.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    if generation <= *written {
+        return Ok(());
+    }
+    let content = serde_json::to_string_pretty(&tasks)?;
+    write_atomic(&path, &content)?;
+    *written = generation;
+    Ok(())
+}
+
+// A hand-edited or badly merged tasks.json can repeat ids, which would make the
+// id-based upserts overwrite the wrong task. The first occurrence keeps its id;
+// later ones get fresh ids. Returns the titles of the reassigned tasks.
+fn dedupe_task_ids(tasks: &mut [Task]) -> Vec<String> {
+    let mut seen = HashSet::new();
+    let mut reassigned = vec![];
+    for task in tasks.iter_mut() {
+        if !seen.insert(task.id) {
+            task.id = Uuid::new_v4();
+            seen.insert(task.id);
+            reassigned.push(task.title.clone());
+        }
+    }
+    reassigned
+}
+
+enum LogWrite {
+    Append(Vec<ExecutionLog>),
+    // Compaction: replaces the file with exactly these entries
+    Rewrite(Vec<ExecutionLog>),
+}
+
+async fn save_logs(write: LogWrite) -> Result<(), AppError> {
+    let path = get_data_dir()?.join("logs.jsonl");
+    match write {
+        LogWrite::Append(logs) => {
+            let mut file = fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&path)?;
+            file.write_all(logs_to_jsonl(&logs)?.as_bytes())?;
+            Ok(())
+        }
+        LogWrite::Rewrite(logs) => write_atomic(&path, &logs_to_jsonl(&logs)?),
+    }
+}
+
+async fn load_events() -> Result<Loaded<Vec<AppEvent>>, AppError> {
+    let path = get_data_dir()?.join("events.json");
+    Ok(read_json(&path)?.unwrap_or_else(|| Loaded::fresh(Vec::new())))
+}
+
+// A missing file is seeded with the built-in templates
+async fn load_templates() -> Result<Loaded<Vec<TaskTemplate>>, AppError> {
+    let path = get_data_dir()?.join("templates.json");
+    if let Some(loaded) = read_json(&path)? {
+        return Ok(loaded);
+    }
+    let templates = get_templates();
+    write_atomic(&path, &serde_json::to_string_pretty(&templates)?)?;
+    Ok(Loaded::fresh(templates))
+}
+
+async fn save_templates(templates: Vec<TaskTemplate>) -> Result<(), AppError> {
+    let path = get_data_dir()?.join("templates.json");
+    let content = serde_json::to_string_pretty(&templates)?;
+    write_atomic(&path, &content)
+}
+
+async fn load_notifications() -> Result<Loaded<Vec<Notification>>, AppError> {
+    let path = get_data_dir()?.join("notifications.json");
+    Ok(read_json(&path)?.unwrap_or_else(|| Loaded::fresh(Vec::new())))
+}
+
+async fn save_notifications(notifications: Vec<Notification>) -> Result<(), AppError> {
+    let path = get_data_dir()?.join("notifications.json");
+    let content = serde_json::to_string_pretty(&notifications)?;
+    write_atomic(&path, &content)
+}
+
+async fn save_events(events: Vec<AppEvent>) -> Result<(), AppError> {
+    let path = get_data_dir()?.join("events.json");
+    let content = serde_json::to_string_pretty(&events)?;
+    write_atomic(&path, &content)
+}
+
+//Cron Helpers
+
+// Crontab numbers weekdays 0-7 with Sunday as both 0 and 7, while the `cron`
+// crate uses 1-7 starting at Sunday, so numeric weekdays are rewritten as names.
+fn crontab_weekday_field(field: &str) -> String {
+    const NAMES: [&str; 8] = ["SUN", "MON", "TUE", "WED", "THU", "FRI", "SAT", "SUN"];
+    let name = |value: &str| {
+        value.parse::<usize>()
+            .ok()
+            .and_then(|n| NAMES.get(n))
+            .map(|n| n.to_string())
+            .unwrap_or_else(|| value.to_string())
+    };
+    
+    field.split(',')
+        .map(|part| {
+            let (range, step) = match part.split_once('/') {
+                Some((range, step)) => (range, Some(step)),
+                None => (part, None),
+            };
+            let range = match range.split_once('-') {
+                Some((start, "7")) if step.is_none() => format!("{}-SAT,SUN", name(start)),
+                Some((start, "7")) => format!("{}-SAT", name(start)),
+                Some((start, end)) => format!("{}-{}", name(start), name(end)),
+                None => name(range),
+            };
+            match step {
+                Some(step) => format!("{}/{}", range, step),
+                None => range,
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+fn parse_cron(expr: &str) -> Result<cron::Schedule, AppError> {
+    let expr = expr.trim();
+    let expanded = match expr {
+        "@yearly" | "@annually" => "0 0 1 1 *",
+        "@monthly" => "0 0 1 * *",
+        "@weekly" => "0 0 * * 0",
+        "@daily" | "@midnight" => "0 0 * * *",
+        "@hourly" => "0 * * * *",
+        other => other,
+    };
+    
+    let fields: Vec<&str> = expanded.split_whitespace().collect();
+    if fields.len() != 5 {
+        return Err(AppError::Config(format!(
+            "expected 5 cron fields, found {}", fields.len()
+        )));
+    }
+    
+    // The cron crate expects a leading seconds field
+    let full = format!(
+        "0 {} {} {} {} {}",
+        fields[0], fields[1], fields[2], fields[3], crontab_weekday_field(fields[4])
+    );
+    cron::Schedule::from_str(&full).map_err(|e| AppError::Config(e.to_string()))
+}
+
+fn split_crontab_line(line: &str) -> Option<(String, String)> {
+    if line.starts_with('@') {
+        let (schedule, command) = line.split_once(char::is_whitespace)?;
+        return Some((schedule.to_string(), command.trim().to_string()));
+    }
+    
+    let mut rest = line;
+    let mut fields = Vec::with_capacity(5);
+    for _ in 0..5 {
+        let end = rest.find(char::is_whitespace)?;
+        fields.push(&rest[..end]);
+        rest = rest[end..].trim_start();
+    }
+    Some((fields.join(" "), rest.trim().to_string()))
+}
+
+fn parse_crontab(input: &str) -> (Vec<Task>, Vec<String>) {
+    let mut tasks = Vec::new();
+    let mut errors = Vec::new();
+    let mut pending_title: Option<String> = None;
+    
+    for (idx, raw) in input.lines().enumerate() {
+        let line = raw.trim();
+        let line_no = idx + 1;
+        
+        if line.is_empty() {
+            pending_title = None;
+            continue;
+        }
+        
+        if let Some(comment) = line.strip_prefix('#') {
+            let comment = comment.trim();
+            if !comment.is_empty() {
+                pending_title = Some(comment.to_string());
+            }
+            continue;
+        }
+        
+        let Some((schedule, command)) = split_crontab_line(line) else {
+            errors.push(format!("line {}: not a cron entry", line_no));
+            continue;
+        };
+        
+        if command.is_empty() {
+            errors.push(format!("line {}: missing command", line_no));
+            continue;
+        }
+        
+        if let Err(e) = parse_cron(&schedule) {
+            errors.push(format!("line {}: {}", line_no, e));
+            continue;
+        }
+        
+        let title = pending_title.take().unwrap_or_else(|| {
+            let mut title: String = command.chars().take(40).collect();
+            if command.chars().count() > 40 {
+                title.push('…');
+            }
+            title
+        });
+        
+        tasks.push(Task {
+            id: Uuid::new_v4(),
+            title,
+            command,
+            schedule: Schedule::Cron(schedule),
+            is_active: false,
+            last_run: None,
+            next_run: None,
+            created_at: Local::now(),
+            success_count: 0,
+            failure_count: 0,
+            last_output: String::new(),
+            max_output_bytes: None,
+            output_file: None,
+            output_file_append: false,
+            paused_until: None,
+            skip_command_prefix: false,
+            watch_path: None,
+            failure_alert_after_seconds: None,
+            failing_since: None,
+            run_on_startup: false,
+            startup_order: None,
+            on_success_command: None,
+            timeout_seconds: None,
+            timeout_grace_seconds: None,
+            run_elevated: false,
+            max_retries: 0,
+            retry_delay_seconds: 0,
+            desktop_override: AlertOverride::Inherit,
+            sound_override: AlertOverride::Inherit,
+            verbose_logging: false,
+            metric_regex: None,
+            login_shell: false,
+            working_dir: None,
+            env_vars: Vec::new(),
+            run_on_activate: true,
+            depends_on: None,
+            one_shot: false,
+            completed_at: None,
+            notify_on_success: true,
+            notify_on_failure: true,
+            webhook_url: None,
+            last_success: None,
+        });
+    }
+    
+    (tasks, errors)
+}
+
+fn output_tail(output: &str, lines: usize) -> String {
+    let all: Vec<&str> = output.trim_end().lines().collect();
+    all[all.len().saturating_sub(lines)..].join("\n")
+}
+
+// Command output as text. UTF-8 is taken as is; UTF-16 (with a BOM, or mostly-ASCII
+// little-endian as some Windows tools write it) and Latin-1 are converted. Anything
+// that still comes out mostly unreadable is replaced by a short label
+fn decode_output(bytes: &[u8]) -> String {
+    let text = if let Some(rest) = bytes.strip_prefix(&[0xFF, 0xFE]) {
+        decode_utf16(rest, u16::from_le_bytes)
+    } else if let Some(rest) = bytes.strip_prefix(&[0xFE, 0xFF]) {
+        decode_utf16(rest, u16::from_be_bytes)
+    } else if looks_utf16le(bytes) {
+        decode_utf16(bytes, u16::from_le_bytes)
+    } else if let Ok(text) = std::str::from_utf8(bytes) {
+        text.to_string()
+    } else if bytes.iter().all(|&b| b >= 0xA0 || (0x20..0x7F).contains(&b) || b"\t\n\r".contains(&b)) {
+        bytes.iter().map(|&b| b as char).collect()
+    } else {
+        String::from_utf8_lossy(bytes).into_owned()
+    };
+    
+    let total = text.chars().count();
+    let unreadable = text.chars()
+        .filter(|&c| c == char::REPLACEMENT_CHARACTER || (c.is_control() && !c.is_whitespace()))
+        .count();
+    if unreadable * 4 > total {
+        format!("[binary/non-UTF-8 output, {} bytes omitted]", bytes.len())
+    } else {
+        text
+    }
+}
+
+fn decode_utf16(bytes: &[u8], unit: fn([u8; 2]) -> u16) -> String {
+    let units: Vec<u16> = bytes.chunks_exact(2).map(|pair| unit([pair[0], pair[1]])).collect();
+    String::from_utf16_lossy(&units)
+}
+
+// ASCII text in UTF-16LE has a zero in (nearly) every second byte
+fn looks_utf16le(bytes: &[u8]) -> bool {
+    if bytes.len() < 4 || !bytes.len().is_multiple_of(2) {
+        return false;
+    }
+    let zeros = bytes.iter().skip(1).step_by(2).filter(|&&b| b == 0).count();
+    zeros * 4 >= bytes.len() / 2 * 3
+}
+
+fn truncate_output(output: String, limit: Option<usize>) -> String {
+    let Some(limit) = limit else {
+        return output;
+    };
+    if output.len() <= limit {
+        return output;
+    }
+    
+    let mut end = limit;
+    while !output.is_char_boundary(end) {
+        end -= 1;
+    }
+    format!("{}\n…(truncated, {} bytes total)", &output[..end], output.len())
+}
+
+fn format_millis(ms: u64) -> String {
+    if ms < 1000 {
+        format!("{}ms", ms)
+    } else if ms < 60_000 {
+        format!("{:.1}s", ms as f64 / 1000.0)
+    } else {
+        TaskWithMe::format_countdown(ms / 1000)
+    }
+}
+
+// Shells use 126 and 127 for commands they could not start
+fn describe_exit_code(code: i32) -> String {
+    match code {
+        126 => "exit 126 (not executable)".to_string(),
+        127 => "exit 127 (command not found)".to_string(),
+        code => format!("exit {}", code),
+    }
+}
+
+fn push_line(buf: &mut String, line: &str) {
+    if !buf.is_empty() {
+        buf.push('\n');
+    }
+    buf.push_str(line);
+}
+
+// The single-text form of a run's output, used for search, exports and last output
+fn combine_output(stdout: &str, stderr: &str) -> String {
+    match (stdout.is_empty(), stderr.is_empty()) {
+        (_, true) => stdout.to_string(),
+        (true, false) => stderr.to_string(),
+        (false, false) => format!("{}\n--- stderr ---\n{}", stdout, stderr),
+    }
+}
+
+fn write_output_file(path: &Path, stdout: &[u8], append: bool) -> Result<(), AppError> {
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .write(true)
+        .append(append)
+        .truncate(!append)
+        .open(path)?;
+    file.write_all(stdout)?;
+    Ok(())
+}
+
+fn sanitize_file_name(name: &str) -> String {
+    let cleaned: String = name.chars()
+        .map(|c| if c.is_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+        .collect();
+    if cleaned.is_empty() { "output".to_string() } else { cleaned }
+}
+
+// Returns Ok(None) when the user cancels the dialog
+async fn save_text_file(default_name: String, content: String) -> Result<Option<PathBuf>, AppError> {
+    save_export_file(default_name, "Text", "txt", content).await
+}
+
+async fn save_export_file(
+    default_name: String,
+    filter_name: &'static str,
+    extension: &'static str,
+    content: String,
+) -> Result<Option<PathBuf>, AppError> {
+    let Some(handle) = rfd::AsyncFileDialog::new()
+        .add_filter(filter_name, &[extension])
+        .set_file_name(&default_name)
+        .save_file()
+        .await
+    else {
+        return Ok(None);
+    };
+    
+    let path = handle.path().to_path_buf();
+    fs::write(&path, content)?;
+    Ok(Some(path))
+}
+
+// Returns Ok(None) when the user cancels the dialog
+async fn read_tasks_file() -> Result<Option<Vec<Task>>, AppError> {
+    let Some(handle) = rfd::AsyncFileDialog::new()
+        .add_filter("JSON", &["json"])
+        .pick_file()
+        .await
+    else {
+        return Ok(None);
+    };
+    
+    let content = fs::read_to_string(handle.path())?;
+    let tasks = serde_json::from_str(&content)?;
+    Ok(Some(tasks))
+}
+
+// Imported tasks always get fresh ids so they cannot overwrite existing ones.
+// Tasks whose title and command match an existing (or earlier imported) task
+// are dropped; returns the tasks to add and the number skipped.
+fn prepare_imported_tasks(incoming: Vec<Task>, existing: &[Task]) -> (Vec<Task>, usize) {
+    let mut seen: HashSet<(String, String)> = existing.iter()
+        .map(|t| (t.title.clone(), t.command.clone()))
+        .collect();
+    let mut skipped = 0;
+    let mut tasks = Vec::new();
+    
+    for mut task in incoming {
+        if !seen.insert((task.title.clone(), task.command.clone())) {
+            skipped += 1;
+            continue;
+        }
+        task.id = Uuid::new_v4();
+        tasks.push(task);
+    }
+    
+    (tasks, skipped)
+}
+
+// Quotes a CSV field when it contains a delimiter, quote or line break
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+// Keeps a value inside one Markdown table cell
+fn md_cell(value: &str) -> String {
+    value.replace('|', "\\|").replace(['\r', '\n'], " ")
+}
+
+// A backtick fence longer than any run of backticks in `text`
+fn code_fence(text: &str) -> String {
+    let longest = text.split(|c| c != '`').map(str::len).max().unwrap_or(0);
+    "`".repeat(longest.max(2) + 1)
+}
+
+fn events_to_csv(events: &[AppEvent]) -> String {
+    let mut csv = String::from("timestamp,kind,task_id,detail\n");
+    for event in events {
+        csv.push_str(&format!(
+            "{},{},{},{}\n",
+            event.timestamp.to_rfc3339(),
+            csv_field(&event.kind.to_string()),
+            event.task_id.map(|id| id.to_string()).unwrap_or_default(),
+            csv_field(&event.detail),
+        ));
+    }
+    csv
+}
+
+// Titles are looked up per log since logs only store the task id
+fn logs_to_csv(logs: &[ExecutionLog], title_of: impl Fn(Uuid) -> String) -> String {
+    let mut csv = String::from("timestamp,task,success,duration_ms,output\n");
+    for log in logs {
+        csv.push_str(&format!(
+            "{},{},{},{},{}\n",
+            log.timestamp.to_rfc3339(),
+            csv_field(&title_of(log.task_id)),
+            log.success,
+            log.duration_ms,
+            csv_field(&log.output_text()),
+        ));
+    }
+    csv
+}
+
+// Writes logs.json plus a README describing the filters that produced it
+async fn export_log_bundle(default_name: String, logs: Vec<ExecutionLog>, readme: String) -> Result<Option<PathBuf>, AppError> {
+    let Some(handle) = rfd::AsyncFileDialog::new()
+        .add_filter("Zip", &["zip"])
+        .set_file_name(&default_name)
+        .save_file()
+        .await
+    else {
+        return Ok(None);
+    };
+    
+    let path = handle.path().to_path_buf();
+    let mut zip = zip::ZipWriter::new(fs::File::create(&path)?);
+    let options = zip::write::SimpleFileOptions::default();
+    
+    zip.start_file("logs.json", options)?;
+    zip.write_all(serde_json::to_string_pretty(&logs)?.as_bytes())?;
+    zip.start_file("README.txt", options)?;
+    zip.write_all(readme.as_bytes())?;
+    zip.finish()?;
+    
+    Ok(Some(path))
+}
+
+async fn send_webhook(url: String, payload: WebhookPayload) -> Result<(), AppError> {
+    reqwest::Client::new()
+        .post(&url)
+        .header(reqwest::header::USER_AGENT, concat!("task-with-me/", env!("CARGO_PKG_VERSION")))
+        .timeout(Duration::from_secs(10))
+        .json(&payload)
+        .send()
+        .await
+        .and_then(|r| r.error_for_status())
+        .map_err(|e| AppError::Io(e.to_string()))?;
+    Ok(())
+}
+
+// The release URL may return a GitHub-style release JSON (`tag_name`) or a
+// plain-text version string
+async fn check_for_updates(url: String) -> Result<Option<String>, AppError> {
+    let body = reqwest::Client::new()
+        .get(&url)
+        .header(reqwest::header::USER_AGENT, concat!("task-with-me/", env!("CARGO_PKG_VERSION")))
+        .timeout(Duration::from_secs(10))
+        .send()
+        .await
+        .and_then(|r| r.error_for_status())
+        .map_err(|e| AppError::Io(e.to_string()))?
+        .text()
+        .await
+        .map_err(|e| AppError::Io(e.to_string()))?;
+    
+    let latest = serde_json::from_str::<serde_json::Value>(&body)
+        .ok()
+        .and_then(|v| v.get("tag_name").and_then(|t| t.as_str()).map(str::to_string))
+        .unwrap_or_else(|| body.trim().to_string());
+    
+    newer_version(env!("CARGO_PKG_VERSION"), &latest)
+}
+
+fn newer_version(current: &str, latest: &str) -> Result<Option<String>, AppError> {
+    let parse = |v: &str| {
+        semver::Version::parse(v.trim().trim_start_matches('v'))
+            .map_err(|e| AppError::Config(format!("invalid version '{}': {}", v.trim(), e)))
+    };
+    let latest = parse(latest)?;
+    Ok((latest > parse(current)?).then(|| latest.to_string()))
+}
+
+async fn persist_state(
+    tasks: Option<(u64, Vec<Task>)>,
+    logs: Option<LogWrite>,
+    events: Option<Vec<AppEvent>>,
+    activity: Option<Vec<Notification>>,
+) -> Result<(), AppError> {
+    if let Some((generation, tasks)) = tasks {
+        write_tasks(generation, tasks).await?;
+    }
+    if let Some(logs) = logs {
+        save_logs(logs).await?;
+    }
+    if let Some(events) = events {
+        save_events(events).await?;
+    }
+    if let Some(activity) = activity {
+        save_notifications(activity).await?;
+    }
+    Ok(())
+}
+
+//Alerts
+
+async fn send_run_alert(title: String, success: bool, desktop: bool, sound: bool) -> Result<(), AppError> {
+    if desktop {
+        let body = if success {
+            format!("'{}' completed successfully", title)
+        } else {
+            format!("'{}' failed", title)
+        };
+        tokio::task::spawn_blocking(move || {
+            notify_rust::Notification::new()
+                .summary("Task with Me")
+                .body(&body)
+                .show()
+                .map(|_| ())
+                .map_err(|e| AppError::Execution(format!("desktop notification: {}", e)))
+        })
+        .await
+        .map_err(|e| AppError::Execution(e.to_string()))??;
+    }
+    
+    if sound {
+        play_alert_sound(success).await?;
+    }
+    Ok(())
+}
+
+// Uses the platform's own player and stock sounds rather than bundling audio
+async fn play_alert_sound(success: bool) -> Result<(), AppError> {
+    let (program, args): (&str, Vec<&str>) = if cfg!(target_os = "macos") {
+        let sound = if success { "/System/Library/Sounds/Glass.aiff" } else { "/System/Library/Sounds/Basso.aiff" };
+        ("afplay", vec![sound])
+    } else if cfg!(target_os = "windows") {
+        let sound = if success {
+            "[System.Media.SystemSounds]::Asterisk.Play()"
+        } else {
+            "[System.Media.SystemSounds]::Hand.Play()"
+        };
+        ("powershell", vec!["-NoProfile", "-Command", sound])
+    } else {
+        let sound = if success { "--id=complete" } else { "--id=dialog-error" };
+        ("canberra-gtk-play", vec![sound])
+    };
+    
+    tokio::process::Command::new(program)
+        .args(&args)
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .await
+        .map(|_| ())
+        .map_err(|e| AppError::Execution(format!("{}: {}", program, e)))
+}
+
+//Secrets
+const KEYRING_SERVICE: &str = "task-with-me";
+
+fn is_valid_secret_name(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+fn load_secret(name: &str) -> Result<String, AppError> {
+    keyring::Entry::new(KEYRING_SERVICE, name)
+        .and_then(|entry| entry.get_password())
+        .map_err(|e| AppError::Execution(format!("secret '{}' unavailable: {}", name, e)))
+}
+
+async fn store_secret(name: String, value: String) -> Result<String, AppError> {
+    keyring::Entry::new(KEYRING_SERVICE, &name)
+        .and_then(|entry| entry.set_password(&value))
+        .map_err(|e| AppError::Config(e.to_string()))?;
+    Ok(name)
+}
+
+async fn delete_secret(name: String) -> Result<String, AppError> {
+    match keyring::Entry::new(KEYRING_SERVICE, &name).and_then(|entry| entry.delete_credential()) {
+        Ok(()) | Err(keyring::Error::NoEntry) => Ok(name),
+        Err(e) => Err(AppError::Config(e.to_string())),
+    }
+}
+
+// Replaces `{{secret:NAME}}` references, returning the command and the values that were substituted
+fn resolve_secret_refs(
+    command: &str,
+    lookup: impl Fn(&str) -> Result<String, AppError>,
+) -> Result<(String, Vec<String>), AppError> {
+    const OPEN: &str = "{{secret:";
+    let mut rendered = String::with_capacity(command.len());
+    let mut values = Vec::new();
+    let mut rest = command;
+    
+    while let Some(start) = rest.find(OPEN) {
+        let after = &rest[start + OPEN.len()..];
+        let Some(end) = after.find("}}") else {
+            break;
+        };
+        let value = lookup(after[..end].trim())?;
+        rendered.push_str(&rest[..start]);
+        rendered.push_str(&value);
+        values.push(value);
+        rest = &after[end + 2..];
+    }
+    rendered.push_str(rest);
+    
+    Ok((rendered, values))
+}
+
+fn is_valid_env_name(name: &str) -> bool {
+    !name.is_empty() && !name.contains(['=', '\0']) && !name.chars().any(char::is_whitespace)
+}
+
+// Values of variables named like credentials are masked in logs as well
+fn looks_secret(name: &str) -> bool {
+    let name = name.to_ascii_uppercase();
+    ["SECRET", "TOKEN", "PASSWORD", "PASSWD", "API_KEY", "APIKEY", "PRIVATE", "CREDENTIAL", "AUTH"]
+        .iter()
+        .any(|marker| name.contains(marker))
+}
+
+fn mask_secrets(text: String, secrets: &[String]) -> String {
+    secrets.iter()
+        .filter(|s| !s.is_empty())
+        .fold(text, |acc, secret| acc.replace(secret.as_str(), "****"))
+}
+
+struct RenderedCommand {
+    command: String,
+    secrets: Vec<String>,
+}
+
+// How a command would be started, shown in the task form before saving
+#[derive(Debug, Clone, PartialEq)]
+struct CommandPreview {
+    invocation: String,
+    working_dir: String,
+    env_vars: Vec<(String, String)>,
+    warnings: Vec<String>,
+}
+
+fn extract_metric(pattern: &str, output: &str) -> Option<f64> {
+    let captures = regex::Regex::new(pattern).ok()?.captures(output)?;
+    let value = captures.get(1).or_else(|| captures.get(0))?;
+    value.as_str().trim().replace(',', "").parse().ok()
+}
+
+// The shell configured in Settings if any, otherwise sh (cmd on Windows). A login
+// shell adds -l, and without a configured shell uses the user's $SHELL, so it reads
+// the login profile (~/.profile, ~/.bash_profile, ~/.zprofile); whether ~/.bashrc
+// or ~/.zshrc is loaded depends on that profile sourcing it. cmd has no login mode
+fn platform_shell(login: bool, custom: Option<(&str, &[String])>) -> (String, Vec<String>) {
+    if let Some((shell, args)) = custom {
+        let mut flags: Vec<String> = if args.is_empty() {
+            default_shell_args(shell).iter().map(|s| s.to_string()).collect()
+        } else {
+            args.to_vec()
+        };
+        if login && !cfg!(target_os = "windows") {
+            flags.insert(0, "-l".to_string());
+        }
+        return (shell.to_string(), flags);
+    }
+    
+    let (shell, flags): (String, &[&str]) = if cfg!(target_os = "windows") {
+        ("cmd".to_string(), &["/C"])
+    } else if login {
+        let shell = std::env::var("SHELL")
+            .ok()
+            .filter(|s| !s.trim().is_empty())
+            .unwrap_or_else(|| "/bin/sh".to_string());
+        (shell, &["-l", "-c"])
+    } else {
+        ("sh".to_string(), &["-c"])
+    };
+    (shell, flags.iter().map(|s| s.to_string()).collect())
+}
+
+// The flag that makes `shell` run the next argument as a command
+fn default_shell_args(shell: &str) -> &'static [&'static str] {
+    let name = Path::new(shell)
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    match name.as_str() {
+        "cmd" => &["/C"],
+        "powershell" | "pwsh" => &["-NoProfile", "-Command"],
+        _ => &["-c"],
+    }
+}
+
+// Filled by the running command and read by the view, so it sits behind a lock
+type LiveOutput = std::sync::Arc<std::sync::Mutex<String>>;
+
+// How a task's command is started; shared by the main command and its hook
+struct ShellOptions {
+    login: bool,
+    shell: Option<String>,
+    shell_args: Vec<String>,
+    timeout: Option<u64>,
+    grace: u64,
+    working_dir: Option<PathBuf>,
+    env_vars: Vec<(String, String)>,
+    live_output: Option<LiveOutput>,
+    // Masked in the live output, like they are in the log
+    secrets: Vec<String>,
+}
+
+impl ShellOptions {
+    fn for_task(task: &Task, config: &Config) -> Self {
+        ShellOptions {
+            login: task.login_shell,
+            shell: config.shell.clone(),
+            shell_args: config.shell_args.clone(),
+            timeout: task.timeout_seconds,
+            grace: task.timeout_grace_seconds.unwrap_or(DEFAULT_TIMEOUT_GRACE_SECONDS),
+            working_dir: task.working_dir.clone(),
+            env_vars: task.env_vars.clone(),
+            live_output: None,
+            secrets: vec![],
+        }
+    }
+    
+    fn shell(&self) -> (String, Vec<String>) {
+        platform_shell(self.login, self.shell.as_deref().map(|shell| (shell, &self.shell_args[..])))
+    }
+}
+
+fn run_metadata(rendered: &RenderedCommand, options: &ShellOptions) -> RunMetadata {
+    let (shell, flags) = options.shell();
+    let mut env_keys: Vec<String> = std::env::vars_os()
+        .map(|(key, _)| key.to_string_lossy().into_owned())
+        .chain(options.env_vars.iter().map(|(key, _)| key.clone()))
+        .collect();
+    env_keys.sort();
+    env_keys.dedup();
+    
+    RunMetadata {
+        command: mask_secrets(rendered.command.clone(), &rendered.secrets),
+        shell: format!("{} {}", shell, flags.join(" ")),
+        working_dir: match &options.working_dir {
+            Some(dir) => dir.display().to_string(),
+            None => std::env::current_dir()
+                .map(|dir| dir.display().to_string())
+                .unwrap_or_else(|e| format!("unknown ({})", e)),
+        },
+        env_keys,
+    }
+}
+
+// Runs `command` through the platform shell. On timeout the command is asked
+// to terminate first (SIGTERM to its process group on Unix, a non-forced
+// taskkill on Windows) and force-killed if still running after `grace` seconds
+async fn run_shell(
+    command: &str,
+    options: &ShellOptions,
+    cancel: tokio::sync::watch::Receiver<bool>,
+) -> Result<std::process::Output, AppError> {
+    let (shell, flags) = options.shell();
+    let grace = options.grace;
+    let timeout = options.timeout;
+    
+    let mut cmd = tokio::process::Command::new(shell);
+    cmd.args(&flags)
+        .arg(command)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true);
+    // Refuse to run somewhere else rather than fall back to the app's directory
+    if let Some(dir) = &options.working_dir {
+        if !dir.is_dir() {
+            return Err(AppError::Execution(format!("working directory {} does not exist", dir.display())));
+        }
+        cmd.current_dir(dir);
+    }
+    cmd.envs(options.env_vars.iter().map(|(key, value)| (key, value)));
+    #[cfg(unix)]
+    cmd.process_group(0);
+    
+    let mut child = cmd.spawn().map_err(|e| AppError::Execution(e.to_string()))?;
+    let stdout = read_pipe_live(child.stdout.take(), options.live_output.clone(), options.secrets.clone());
+    let stderr = read_pipe(child.stderr.take());
+    
+    let finished = tokio::select! {
+        finished = wait_with_timeout(&mut child, timeout) => finished?,
+        _ = cancel_requested(cancel) => {
+            terminate_gracefully(&mut child, Duration::from_secs(grace)).await;
+            return Err(AppError::Cancelled);
+        }
+    };
+    let Some(status) = finished else {
+        terminate_gracefully(&mut child, Duration::from_secs(grace)).await;
+        return Err(AppError::Execution(format!("timed out after {}s", timeout.unwrap_or_default())));
+    };
+    
+    Ok(std::process::Output {
+        status,
+        stdout: stdout.await.unwrap_or_default(),
+        stderr: stderr.await.unwrap_or_default(),
+    })
+}
+
+// None when the timeout elapsed first
+async fn wait_with_timeout(
+    child: &mut tokio::process::Child,
+    timeout: Option<u64>,
+) -> Result<Option<std::process::ExitStatus>, AppError> {
+    match timeout {
+        None => Ok(Some(child.wait().await?)),
+        Some(secs) => match tokio::time::timeout(Duration::from_secs(secs), child.wait()).await {
+            Ok(status) => Ok(Some(status?)),
+            Err(_) => Ok(None),
+        },
+    }
+}
+
+// Never resolves if the sender is gone without cancelling
+async fn cancel_requested(mut cancel: tokio::sync::watch::Receiver<bool>) {
+    if cancel.wait_for(|cancelled| *cancelled).await.is_err() {
+        std::future::pending::<()>().await;
+    }
+}
+
+fn read_pipe<R>(pipe: Option<R>) -> tokio::task::JoinHandle<Vec<u8>>
+where
+    R: tokio::io::AsyncRead + Unpin + Send + 'static,
+{
+    tokio::spawn(async move {
+        use tokio::io::AsyncReadExt;
+        
+        let mut buf = Vec::new();
+        if let Some(mut pipe) = pipe {
+            let _ = pipe.read_to_end(&mut buf).await;
+        }
+        buf
+    })
+}
+
+// Like read_pipe, but also refreshes `live` as soon as anything arrives
+fn read_pipe_live<R>(pipe: Option<R>, live: Option<LiveOutput>, secrets: Vec<String>) -> tokio::task::JoinHandle<Vec<u8>>
+where
+    R: tokio::io::AsyncRead + Unpin + Send + 'static,
+{
+    let Some(live) = live else {
+        return read_pipe(pipe);
+    };
+    tokio::spawn(async move {
+        use tokio::io::AsyncReadExt;
+        
+        let mut buf = Vec::new();
+        let Some(mut pipe) = pipe else {
+            return buf;
+        };
+        let mut chunk = [0u8; 8192];
+        while let Ok(read) = pipe.read(&mut chunk).await {
+            if read == 0 {
+                break;
+            }
+            buf.extend_from_slice(&chunk[..read]);
+            let text = live_output_tail(&buf, &secrets);
+            if let Ok(mut out) = live.lock() {
+                *out = text;
+            }
+        }
+        buf
+    })
+}
+
+// The tail of the output so far, decoded and masked the same way as the log. Decoding
+// the raw tail again each time keeps the encoding guess stable and catches secrets
+// split across reads. The start stays even so UTF-16 units are not split
+fn live_output_tail(raw: &[u8], secrets: &[String]) -> String {
+    let start = raw.len().saturating_sub(2 * LIVE_OUTPUT_MAX_BYTES) & !1;
+    let text = mask_secrets(decode_output(&raw[start..]), secrets);
+    let mut out = String::new();
+    append_live_output(&mut out, &text);
+    out
+}
+
+// Drops whole lines from the front once the buffer passes LIVE_OUTPUT_MAX_BYTES
+fn append_live_output(out: &mut String, chunk: &str) {
+    out.push_str(chunk);
+    if out.len() <= LIVE_OUTPUT_MAX_BYTES {
+        return;
+    }
+    let excess = out.len() - LIVE_OUTPUT_MAX_BYTES;
+    let cut = match out[excess..].find('\n') {
+        Some(newline) => excess + newline + 1,
+        None => out.len(),
+    };
+    out.drain(..cut);
+}
+
+async fn terminate_gracefully(child: &mut tokio::process::Child, grace: Duration) {
+    let Some(pid) = child.id() else {
+        return;
+    };
+    
+    #[cfg(unix)]
+    // SAFETY: plain kill(2) on the process group created for this child
+    unsafe {
+        libc::kill(-(pid as libc::pid_t), libc::SIGTERM);
+    }
+    #[cfg(windows)]
+    {
+        let _ = tokio::process::Command::new("taskkill")
+            .args(["/PID", &pid.to_string(), "/T"])
+            .output()
+            .await;
+    }
+    
+    if tokio::time::timeout(grace, child.wait()).await.is_err() {
+        #[cfg(unix)]
+        // SAFETY: as above
+        unsafe {
+            libc::kill(-(pid as libc::pid_t), libc::SIGKILL);
+        }
+        let _ = child.kill().await;
+    }
+}
+
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "'\\''"))
+}
+
+fn find_in_path(program: &str) -> Option<PathBuf> {
+    std::env::split_paths(&std::env::var_os("PATH")?)
+        .map(|dir| dir.join(program))
+        .find(|path| path.is_file())
+}
+
+// Elevation goes through the platform's own password prompt (polkit on Linux,
+// the administrator dialog on macOS); the app never sees or stores credentials.
+// Windows can't elevate a child while capturing its output, so it is refused.
+fn elevate_command(command: &str) -> Result<String, AppError> {
+    if cfg!(target_os = "windows") {
+        return Err(AppError::Execution(
+            "Elevated tasks are not supported on Windows; start Task with Me as administrator instead".to_string()
+        ));
+    }
+    
+    if cfg!(target_os = "macos") {
+        let script = format!(
+            "do shell script \"{}\" with administrator privileges",
+            command.replace('\\', "\\\\").replace('"', "\\\"")
+        );
+        return Ok(format!("osascript -e {}", shell_quote(&script)));
+    }
+    
+    if find_in_path("pkexec").is_some() {
+        Ok(format!("pkexec sh -c {}", shell_quote(command)))
+    } else if find_in_path("sudo").is_some() {
+        // Without a terminal sudo can only succeed with cached or NOPASSWD credentials
+        Ok(format!("sudo -n sh -c {}", shell_quote(command)))
+    } else {
+        Err(AppError::Execution(
+            "Elevated tasks need pkexec (polkit) or sudo to be installed".to_string()
+        ))
+    }
+}
+
+fn render_command(task: &Task, config: &Config) -> Result<RenderedCommand, AppError> {
+    let (command, secrets) = resolve_secret_refs(&task.command, load_secret)?;
+    let command = wrap_command(command, config, task.skip_command_prefix, task.run_elevated)?;
+    Ok(RenderedCommand { command, secrets })
+}
+
+// Adds the global command prefix and the elevation wrapper. The prefix is joined
+// into the command string so it still goes through the same shell
+fn wrap_command(command: String, config: &Config, skip_prefix: bool, elevated: bool) -> Result<String, AppError> {
+    let command = match config.command_prefix.as_deref() {
+        Some(prefix) if !skip_prefix && !prefix.trim().is_empty() => {
+            format!("{} {}", prefix.trim(), command)
+        }
+        _ => command,
+    };
+    if elevated { elevate_command(&command) } else { Ok(command) }
+}
+
+// Secret references are left as written and credential-like variables are masked,
+// so the preview never shows a secret value
+fn preview_command(
+    command: &str,
+    options: &ShellOptions,
+    config: &Config,
+    skip_prefix: bool,
+    elevated: bool,
+) -> CommandPreview {
+    let mut warnings = command_warnings(command);
+    let command = match wrap_command(command.to_string(), config, skip_prefix, elevated) {
+        Ok(command) => command,
+        Err(e) => {
+            warnings.push(e.to_string());
+            command.to_string()
+        }
+    };
+    
+    let (shell, flags) = options.shell();
+    let invocation = std::iter::once(shell)
+        .chain(flags)
+        .chain(std::iter::once(shell_quote(&command)))
+        .collect::<Vec<_>>()
+        .join(" ");
+    
+    let working_dir = match &options.working_dir {
+        Some(dir) => {
+            if !dir.is_dir() {
+                warnings.push(format!("Working directory {} does not exist; runs will fail", dir.display()));
+            }
+            dir.display().to_string()
+        }
+        None => std::env::current_dir()
+            .map(|dir| format!("App directory ({})", dir.display()))
+            .unwrap_or_else(|_| "App directory".to_string()),
+    };
+    
+    let env_vars = options.env_vars.iter()
+        .map(|(key, value)| {
+            let value = if looks_secret(key) { "****".to_string() } else { value.clone() };
+            (key.clone(), value)
+        })
+        .collect();
+    
+    CommandPreview { invocation, working_dir, env_vars, warnings }
+}
+
+// Flags patterns that are easy to get catastrophically wrong; not a safety guarantee
+fn command_warnings(command: &str) -> Vec<String> {
+    const SEPARATORS: [&str; 5] = [";", "&&", "||", "|", "&"];
+    const ROOT_TARGETS: [&str; 9] = ["/", "/*", "~", "~/", "~/*", "*", ".", "..", "$HOME"];
+    
+    let mut warnings = Vec::new();
+    let tokens: Vec<&str> = command.split_whitespace().collect();
+    
+    for (i, _) in tokens.iter().enumerate().filter(|(_, t)| **t == "rm" || t.ends_with("/rm")) {
+        let args: Vec<&str> = tokens[i + 1..].iter()
+            .take_while(|t| !SEPARATORS.contains(t))
+            .copied()
+            .collect();
+        let recursive = args.iter().any(|a| {
+            *a == "--recursive" || (a.starts_with('-') && !a.starts_with("--") && a.contains(['r', 'R']))
+        });
+        if !recursive {
+            continue;
+        }
+        for target in args.iter().filter(|a| !a.starts_with('-')) {
+            if ROOT_TARGETS.contains(target) {
+                warnings.push(format!("`rm` recursively deletes {}", target));
+            } else if target.starts_with('$') {
+                warnings.push(format!(
+                    "`rm` recursively deletes a path built from {}; if it is empty this may delete from /",
+                    target
+                ));
+            }
+        }
+    }
+    
+    if command.contains("mkfs") {
+        warnings.push("Command formats a filesystem (mkfs)".to_string());
+    }
+    if command.contains("of=/dev/") || command.contains("> /dev/sd") {
+        warnings.push("Command writes directly to a device".to_string());
+    }
+    
+    let mut unquoted = Vec::new();
+    let mut chars = command.chars().peekable();
+    let (mut single, mut double) = (false, false);
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if !single => {
+                chars.next();
+            }
+            '\'' if !double => single = !single,
+            '"' if !single => double = !double,
+            '$' if !single && !double => {
+                let braced = chars.next_if_eq(&'{').is_some();
+                let mut name = String::new();
+                while let Some(c) = chars.next_if(|c| c.is_ascii_alphanumeric() || *c == '_') {
+                    name.push(c);
+                }
+                if braced {
+                    chars.next_if_eq(&'}');
+                }
+                if !name.is_empty() && !unquoted.contains(&name) {
+                    unquoted.push(name);
+                }
+            }
+            _ => {}
+        }
+    }
+    warnings.extend(unquoted.into_iter().map(|name| {
+        format!("Unquoted variable ${}; write \"${}\" if it may be empty or contain spaces", name, name)
+    }));
+    
+    warnings
+}
+
+async fn execute_task(
+    task: Task,
+    config: Config,
+    cancel: tokio::sync::watch::Receiver<bool>,
+    live: LiveOutput,
+) -> Result<ExecutionResult, AppError> {
+    let start = Instant::now();
+    
+    let mut rendered = render_command(&task, &config)?;
+    let mut options = ShellOptions::for_task(&task, &config);
+    options.live_output = Some(live);
+    for (key, value) in options.env_vars.iter_mut() {
+        let (resolved, secrets) = resolve_secret_refs(value, load_secret)?;
+        *value = resolved;
+        rendered.secrets.extend(secrets);
+        if looks_secret(key) {
+            rendered.secrets.push(value.clone());
+        }
+    }
+    options.secrets = rendered.secrets.clone();
+    let metadata = task.verbose_logging.then(|| run_metadata(&rendered, &options));
+    
+    let output = run_shell(&rendered.command, &options, cancel.clone()).await?;
+    
+    let duration = start.elapsed();
+    let success = output.status.success();
+    
+    let mut stdout_text = decode_output(&output.stdout).trim().to_string();
+    let mut stderr_text = decode_output(&output.stderr).trim().to_string();
+    let exit_code = output.status.code();
+    #[cfg(unix)]
+    if let Some(signal) = std::os::unix::process::ExitStatusExt::signal(&output.status) {
+        push_line(&mut stderr_text, &format!("[terminated by signal {}]", signal));
+    }
+    
+    if let Some(path) = task.output_path() {
+        if let Err(e) = write_output_file(&path, &output.stdout, task.output_file_append) {
+            push_line(&mut stderr_text, &format!("[failed to write output file {}: {}]", path.display(), e));
+        }
+    }
+    
+    // The hook's result is appended to the log; it does not change the run's status
+    if let Some(hook) = task.on_success_command.as_deref().filter(|_| success) {
+        let hook_task = Task { command: hook.to_string(), ..task.clone() };
+        // A hook whose secrets or elevation can't be resolved fails like one that can't spawn
+        let hook_output = match render_command(&hook_task, &config) {
+            Ok(hook_rendered) => {
+                options.secrets.extend(hook_rendered.secrets.iter().cloned());
+                let output = run_shell(&hook_rendered.command, &options, cancel).await;
+                rendered.secrets.extend(hook_rendered.secrets);
+                output
+            }
+            Err(e) => Err(e),
+        };
+        
+        match hook_output {
+            Ok(out) if out.status.success() => push_line(&mut stdout_text, &format!(
+                "--- on-success hook ---\n{}",
+                decode_output(&out.stdout).trim()
+            )),
+            Ok(out) => push_line(&mut stderr_text, &format!(
+                "--- on-success hook failed ({}) ---\n{}",
+                out.status,
+                decode_output(&out.stderr).trim()
+            )),
+            Err(e) => push_line(&mut stderr_text, &format!("--- on-success hook could not start: {} ---", e)),
+        }
+    }
+    
+    // Per-task limit wins over the global one; neither set means unlimited
+    let output_limit = task.max_output_bytes.or(config.max_output_bytes);
+    
+    let output_text = combine_output(&stdout_text, &stderr_text);
+    let metric = task.metric_regex.as_deref().and_then(|pattern| extract_metric(pattern, &output_text));
+    let clean = |text: String| truncate_output(mask_secrets(text, &rendered.secrets), output_limit);
+    
+    let result = ExecutionResult {
+        success,
+        output: clean(output_text),
+        stdout: clean(stdout_text),
+        stderr: clean(stderr_text),
+        exit_code,
+        duration_ms: duration.as_millis() as u64,
+        metadata,
+        metric,
+    };
+    
+    Ok(result)
+}
+
+// Labeled stdout/stderr sections, or the combined output for older logs
+fn log_output_sections(log: &ExecutionLog) -> Element<'_, Message> {
+    let section = |label: &'static str, body: &str| {
+        container(
+            column![
+                text(label).size(10).style(Color::from_rgb(0.6, 0.6, 0.6)),
+                text(body.to_string()).size(11),
             ]
-            .align_items(alignment::Alignment::Center)
+            .spacing(4)
         )
+        .padding([8, 12])
         .width(Length::Fill)
-        .into()
+        .style(iced::theme::Container::Box)
+    };
+    
+    if log.stdout.is_empty() && log.stderr.is_empty() {
+        return if log.output.is_empty() {
+            Space::with_height(0).into()
+        } else {
+            container(text(&log.output).size(11))
+                .padding([8, 12])
+                .style(iced::theme::Container::Box)
+                .into()
+        };
+    }
+    
+    let mut sections = column![].spacing(6);
+    if !log.stdout.is_empty() {
+        sections = sections.push(section("stdout", &log.stdout));
+    }
+    if !log.stderr.is_empty() {
+        sections = sections.push(section("stderr", &log.stderr));
+    }
+    sections.into()
+}
+
+// Main
+fn main() -> iced::Result {
+    // Everything is let through here; log::set_max_level does the filtering,
+    // so the Log Level setting can change it at runtime
+    env_logger::Builder::new()
+        .filter_level(log::LevelFilter::Trace)
+        .parse_default_env()
+        .init();
+    apply_log_level(LogLevel::default());
+    
+    if let Some(dir) = parse_data_dir_arg(std::env::args().skip(1)) {
+        let _ = CLI_DATA_DIR.set(dir);
+    }
+    
+    TaskWithMe::run(Settings {
+        window: window::Settings {
+            size: iced::Size::new(1200.0, 800.0),
+            min_size: Some(iced::Size::new(900.0, 600.0)),
+            // Closing is handled in CloseRequested so pending state gets flushed first
+            exit_on_close_request: false,
+            ..Default::default()
+        },
+        default_font: Font::default(),
+        ..Settings::default()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_task_creation() {
+        let task = Task {
+            id: Uuid::new_v4(),
+            title: "Test".to_string(),
+            command: "echo test".to_string(),
+            schedule: Schedule::Interval(60),
+            is_active: false,
+            last_run: None,
+            next_run: None,
+            created_at: Local::now(),
+            success_count: 0,
+            failure_count: 0,
+            last_output: String::new(),
+            max_output_bytes: None,
+            output_file: None,
+            output_file_append: false,
+            paused_until: None,
+            skip_command_prefix: false,
+            watch_path: None,
+            failure_alert_after_seconds: None,
+            failing_since: None,
+            run_on_startup: false,
+            startup_order: None,
+            on_success_command: None,
+            timeout_seconds: None,
+            timeout_grace_seconds: None,
+            run_elevated: false,
+            max_retries: 0,
+            retry_delay_seconds: 0,
+            desktop_override: AlertOverride::Inherit,
+            sound_override: AlertOverride::Inherit,
+            verbose_logging: false,
+            metric_regex: None,
+            login_shell: false,
+            working_dir: None,
+            env_vars: Vec::new(),
+            run_on_activate: true,
+            depends_on: None,
+            one_shot: false,
+            completed_at: None,
+            notify_on_success: true,
+            notify_on_failure: true,
+            webhook_url: None,
+            last_success: None,
+        };
+        
+        assert_eq!(task.title, "Test");
+        assert!(!task.is_active);
+    }
+
+    #[test]
+    fn test_success_rate_calculation() {
+        let app = TaskWithMe::default();
+        let mut task = Task {
+            id: Uuid::new_v4(),
+            title: "Test".to_string(),
+            command: "test".to_string(),
+            schedule: Schedule::Interval(60),
+            is_active: false,
+            last_run: None,
+            next_run: None,
+            created_at: Local::now(),
+            success_count: 7,
+            failure_count: 3,
+            last_output: String::new(),
+            max_output_bytes: None,
+            output_file: None,
+            output_file_append: false,
+            paused_until: None,
+            skip_command_prefix: false,
+            watch_path: None,
+            failure_alert_after_seconds: None,
+            failing_since: None,
+            run_on_startup: false,
+            startup_order: None,
+            on_success_command: None,
+            timeout_seconds: None,
+            timeout_grace_seconds: None,
+            run_elevated: false,
+            max_retries: 0,
+            retry_delay_seconds: 0,
+            desktop_override: AlertOverride::Inherit,
+            sound_override: AlertOverride::Inherit,
+            verbose_logging: false,
+            metric_regex: None,
+            login_shell: false,
+            working_dir: None,
+            env_vars: Vec::new(),
+            run_on_activate: true,
+            depends_on: None,
+            one_shot: false,
+            completed_at: None,
+            notify_on_success: true,
+            notify_on_failure: true,
+            webhook_url: None,
+            last_success: None,
+        };
+        
+        assert_eq!(app.success_rate(&task), 70.0);
+        
+        task.success_count = 0;
+        task.failure_count = 0;
+        assert_eq!(app.success_rate(&task), 0.0);
+    }
+    
+    #[test]
+    fn test_duration_formatting() {
+        assert_eq!(TaskWithMe::format_duration(45), "45s");
+        assert_eq!(TaskWithMe::format_duration(120), "2m");
+        assert_eq!(TaskWithMe::format_duration(7200), "2h");
+        assert_eq!(TaskWithMe::format_duration(172800), "2d");
+    }
+    
+    #[test]
+    fn test_success_rate_precision() {
+        let mut app = TaskWithMe::default();
+        assert_eq!(app.format_success_rate(99.5), "100%");
+        
+        app.config.success_rate_precision = 1;
+        assert_eq!(app.format_success_rate(99.5), "99.5%");
+        assert_eq!(app.format_success_rate(66.66), "66.7%");
+    }
+    
+    #[test]
+    fn test_effective_interval_with_test_schedule() {
+        let mut app = TaskWithMe::default();
+        let task = Task {
+            id: Uuid::new_v4(),
+            title: "Test".to_string(),
+            command: "echo test".to_string(),
+            schedule: Schedule::Interval(3600),
+            is_active: true,
+            last_run: None,
+            next_run: None,
+            created_at: Local::now(),
+            success_count: 0,
+            failure_count: 0,
+            last_output: String::new(),
+            max_output_bytes: None,
+            output_file: None,
+            output_file_append: false,
+            paused_until: None,
+            skip_command_prefix: false,
+            watch_path: None,
+            failure_alert_after_seconds: None,
+            failing_since: None,
+            run_on_startup: false,
+            startup_order: None,
+            on_success_command: None,
+            timeout_seconds: None,
+            timeout_grace_seconds: None,
+            run_elevated: false,
+            max_retries: 0,
+            retry_delay_seconds: 0,
+            desktop_override: AlertOverride::Inherit,
+            sound_override: AlertOverride::Inherit,
+            verbose_logging: false,
+            metric_regex: None,
+            login_shell: false,
+            working_dir: None,
+            env_vars: Vec::new(),
+            run_on_activate: true,
+            depends_on: None,
+            one_shot: false,
+            completed_at: None,
+            notify_on_success: true,
+            notify_on_failure: true,
+            webhook_url: None,
+            last_success: None,
+        };
+        
+        assert_eq!(app.effective_interval(&task), 3600);
+        
+        app.test_schedules.insert(task.id, TestSchedule {
+            interval_seconds: TEST_SCHEDULE_INTERVAL,
+            expires_at: Local::now() + chrono::Duration::seconds(TEST_SCHEDULE_DURATION),
+        });
+        assert_eq!(app.effective_interval(&task), TEST_SCHEDULE_INTERVAL);
+        assert_eq!(task.schedule, Schedule::Interval(3600));
+    }
+    
+    #[test]
+    fn test_trusted_session_skips_run_confirmation() {
+        let mut app = TaskWithMe::default();
+        let id = Uuid::new_v4();
+        app.config.confirm_before_run = true;
+        
+        assert!(app.needs_confirmation(&PendingAction::RunTask(id)));
+        
+        app.trusted_session = true;
+        assert!(!app.needs_confirmation(&PendingAction::RunTask(id)));
+        assert!(app.needs_confirmation(&PendingAction::DeleteTask(id)));
+    }
+    
+    #[test]
+    fn test_parse_crontab() {
+        let input = "\
+# Nightly backup
+30 2 * * * /usr/local/bin/backup.sh --full
+*/5 * * * 1-5 df -h
+
+not a cron line
+@reboot echo hi
+@hourly echo tick
+";
+        let (tasks, errors) = parse_crontab(input);
+        
+        assert_eq!(tasks.len(), 3);
+        assert_eq!(tasks[0].title, "Nightly backup");
+        assert_eq!(tasks[0].command, "/usr/local/bin/backup.sh --full");
+        assert_eq!(tasks[0].schedule, Schedule::Cron("30 2 * * *".to_string()));
+        assert_eq!(tasks[1].title, "df -h");
+        assert_eq!(tasks[2].schedule, Schedule::Cron("@hourly".to_string()));
+        
+        assert_eq!(errors.len(), 2);
+        assert!(errors[0].starts_with("line 5"));
+        assert!(errors[1].starts_with("line 6"));
+    }
+    
+    #[test]
+    fn test_cron_weekday_mapping() {
+        use chrono::Weekday;
+        
+        // Saturday 2024-06-01 12:00; "1-5" means Monday-Friday in crontab
+        let from = Local.with_ymd_and_hms(2024, 6, 1, 12, 0, 0).unwrap();
+        let next = Schedule::Cron("0 9 * * 1-5".to_string()).next_after(from).unwrap();
+        assert_eq!(next.weekday(), Weekday::Mon);
+        
+        let next = Schedule::Cron("0 9 * * 0".to_string()).next_after(from).unwrap();
+        assert_eq!(next.weekday(), Weekday::Sun);
+        
+        assert!(parse_cron("61 * * * *").is_err());
+    }
+    
+    #[test]
+    fn test_projected_runs_within_range() {
+        use chrono::TimeZone;
+        
+        let start = Local.with_ymd_and_hms(2024, 6, 3, 0, 0, 0).unwrap();
+        let end = start + chrono::Duration::days(1);
+        let mut task = Task {
+            id: Uuid::new_v4(),
+            title: "Test".to_string(),
+            command: "echo test".to_string(),
+            schedule: Schedule::Interval(3600),
+            is_active: true,
+            last_run: None,
+            next_run: Some(start + chrono::Duration::minutes(30)),
+            created_at: Local::now(),
+            success_count: 0,
+            failure_count: 0,
+            last_output: String::new(),
+            max_output_bytes: None,
+            output_file: None,
+            output_file_append: false,
+            paused_until: None,
+            skip_command_prefix: false,
+            watch_path: None,
+            failure_alert_after_seconds: None,
+            failing_since: None,
+            run_on_startup: false,
+            startup_order: None,
+            on_success_command: None,
+            timeout_seconds: None,
+            timeout_grace_seconds: None,
+            run_elevated: false,
+            max_retries: 0,
+            retry_delay_seconds: 0,
+            desktop_override: AlertOverride::Inherit,
+            sound_override: AlertOverride::Inherit,
+            verbose_logging: false,
+            metric_regex: None,
+            login_shell: false,
+            working_dir: None,
+            env_vars: Vec::new(),
+            run_on_activate: true,
+            depends_on: None,
+            one_shot: false,
+            completed_at: None,
+            notify_on_success: true,
+            notify_on_failure: true,
+            webhook_url: None,
+            last_success: None,
+        };
+        
+        let runs = projected_runs(&task, start, end);
+        assert_eq!(runs.len(), 24);
+        assert!(runs.iter().all(|r| *r >= start && *r < end));
+        
+        task.is_active = false;
+        assert!(projected_runs(&task, start, end).is_empty());
+    }
+    
+    #[test]
+    fn test_reload_keeps_unflushed_task_changes() {
+        let mut app = TaskWithMe::default();
+        let stale = Task {
+            id: Uuid::new_v4(),
+            title: "Test".to_string(),
+            command: "echo test".to_string(),
+            schedule: Schedule::Interval(60),
+            is_active: false,
+            last_run: None,
+            next_run: None,
+            created_at: Local::now(),
+            success_count: 0,
+            failure_count: 0,
+            last_output: String::new(),
+            max_output_bytes: None,
+            output_file: None,
+            output_file_append: false,
+            paused_until: None,
+            skip_command_prefix: false,
+            watch_path: None,
+            failure_alert_after_seconds: None,
+            failing_since: None,
+            run_on_startup: false,
+            startup_order: None,
+            on_success_command: None,
+            timeout_seconds: None,
+            timeout_grace_seconds: None,
+            run_elevated: false,
+            max_retries: 0,
+            retry_delay_seconds: 0,
+            desktop_override: AlertOverride::Inherit,
+            sound_override: AlertOverride::Inherit,
+            verbose_logging: false,
+            metric_regex: None,
+            login_shell: false,
+            working_dir: None,
+            env_vars: Vec::new(),
+            run_on_activate: true,
+            depends_on: None,
+            one_shot: false,
+            completed_at: None,
+            notify_on_success: true,
+            notify_on_failure: true,
+            webhook_url: None,
+            last_success: None,
+        };
+        let mut fresh = stale.clone();
+        fresh.success_count = 5;
+        
+        app.tasks = vec![fresh];
+        app.dirty_tasks.insert(stale.id);
+        let _ = app.update(Message::TasksLoaded(Ok(Loaded::fresh(vec![stale]))));
+        
+        assert_eq!(app.tasks[0].success_count, 5);
+    }
+    
+    #[test]
+    fn test_pause_until_resolves_to_future_midnight() {
+        use chrono::{Timelike, Weekday};
+        
+        // Wednesday afternoon
+        let now = Local.with_ymd_and_hms(2024, 6, 5, 15, 30, 0).unwrap();
+        
+        let monday = PauseUntil::NextMonday.resolve(now);
+        assert_eq!(monday.weekday(), Weekday::Mon);
+        assert_eq!(monday.hour(), 0);
+        assert!(monday > now);
+        
+        let tomorrow = PauseUntil::Tomorrow.resolve(now);
+        assert_eq!(tomorrow.date_naive(), now.date_naive() + chrono::Duration::days(1));
+        assert_eq!(PauseUntil::OneHour.resolve(now), now + chrono::Duration::hours(1));
+    }
+    
+    #[test]
+    fn test_render_command_applies_prefix() {
+        let mut config = Config::default();
+        let (mut tasks, _) = parse_crontab("* * * * * echo hi");
+        let mut task = tasks.remove(0);
+        
+        assert_eq!(render_command(&task, &config).unwrap().command, "echo hi");
+        
+        config.command_prefix = Some("nice -n 19".to_string());
+        assert_eq!(render_command(&task, &config).unwrap().command, "nice -n 19 echo hi");
+        
+        task.skip_command_prefix = true;
+        assert_eq!(render_command(&task, &config).unwrap().command, "echo hi");
+    }
+    
+    #[test]
+    fn test_secret_refs_resolved_and_masked() {
+        let lookup = |name: &str| match name {
+            "API_TOKEN" => Ok("s3cr3t".to_string()),
+            other => Err(AppError::Execution(format!("secret '{}' unavailable", other))),
+        };
+        
+        let (command, secrets) = resolve_secret_refs(
+            "curl -H 'Authorization: {{secret:API_TOKEN}}' https://example.com",
+            lookup,
+        ).unwrap();
+        assert_eq!(command, "curl -H 'Authorization: s3cr3t' https://example.com");
+        assert_eq!(mask_secrets("token=s3cr3t".to_string(), &secrets), "token=****");
+        
+        assert!(resolve_secret_refs("echo {{secret:MISSING}}", lookup).is_err());
+        assert_eq!(resolve_secret_refs("echo {{secret:", lookup).unwrap().0, "echo {{secret:");
+    }
+    
+    #[test]
+    fn test_theme_is_saved_on_change_and_reloaded() {
+        let mut app = TaskWithMe::default();
+        app.reset_settings_draft();
+        let _ = app.update(Message::RefreshIntervalChanged("30".to_string()));
+        
+        // Saved at once, without the unrelated unsaved edit
+        assert_eq!(app.update(Message::ThemeChanged(AppTheme::Light)).actions().len(), 1);
+        assert_eq!(app.config.theme, AppTheme::Light);
+        assert_eq!(app.config.refresh_interval, 5);
+        assert_eq!(app.unsaved_settings(), vec!["Refresh Interval"]);
+        
+        // What ThemeChanged wrote, read back by a fresh app
+        let saved = serde_json::to_string_pretty(&app.config).unwrap();
+        let mut restarted = TaskWithMe::default();
+        let _ = restarted.update(Message::ConfigLoaded(Ok(Loaded::fresh(serde_json::from_str(&saved).unwrap()))));
+        assert_eq!(restarted.theme(), Theme::Light);
+        assert_eq!(restarted.settings_draft.theme, AppTheme::Light);
+    }
+    
+    #[test]
+    fn test_custom_templates_are_saved_and_deleted() {
+        let mut app = TaskWithMe::default();
+        let defaults = app.templates.len();
+        app.title_input = "Sync notes".to_string();
+        app.command_input = "rsync -a ~/notes server:".to_string();
+        app.interval_input = "900".to_string();
+        
+        assert_eq!(app.update(Message::SaveFormAsTemplate).actions().len(), 1);
+        let saved = app.templates.last().unwrap().clone();
+        assert_eq!(saved.description, "Every 15m");
+        assert_eq!(saved.interval, 900);
+        
+        // Same name replaces the template
+        app.interval_input = "60".to_string();
+        let _ = app.update(Message::SaveFormAsTemplate);
+        assert_eq!(app.templates.len(), defaults + 1);
+        assert_eq!(app.templates.last().unwrap().interval, 60);
+        
+        app.cron_mode_input = true;
+        assert_eq!(app.update(Message::SaveFormAsTemplate).actions().len(), 0);
+        
+        let json = serde_json::to_string(&app.templates).unwrap();
+        assert_eq!(serde_json::from_str::<Vec<TaskTemplate>>(&json).unwrap(), app.templates);
+        
+        let _ = app.update(Message::DeleteTemplate(defaults));
+        assert_eq!(app.templates, get_templates());
+    }
+    
+    #[test]
+    fn test_onboarding_shows_once_and_seeds_templates() {
+        let mut app = TaskWithMe::default();
+        app.reset_settings_draft();
+        let _ = app.update(Message::OnboardingChecked(false));
+        assert!(!app.show_onboarding);
+        let _ = app.update(Message::OnboardingChecked(true));
+        assert!(app.show_onboarding);
+        
+        // One template already exists as a task, so it is not added twice
+        app.tasks.push(app.templates[0].to_task());
+        let seeded = app.update(Message::SeedTemplates);
+        assert_eq!(seeded.actions().len(), 2);
+        assert!(!app.show_onboarding);
+        assert!(app.config.onboarded && app.draft_config().onboarded);
+        let imported = app.events.iter().find(|e| e.kind == AppEventKind::TasksImported).unwrap();
+        assert!(imported.detail.starts_with(&format!("Added {} template task(s)", app.templates.len() - 1)));
+        assert!(!app.templates[0].to_task().is_active);
+        
+        // Onboarded configs never show it again
+        let _ = app.update(Message::OnboardingChecked(true));
+        assert!(!app.show_onboarding);
+    }
+    
+    #[test]
+    fn test_recent_results_are_oldest_first() {
+        let (task, other) = (Uuid::new_v4(), Uuid::new_v4());
+        let app = TaskWithMe {
+            logs: VecDeque::from([
+                sample_log(task, false, "1"),
+                sample_log(other, false, "x"),
+                sample_log(task, true, "2"),
+                sample_log(task, true, "3"),
+            ]),
+            ..Default::default()
+        };
+        assert_eq!(app.recent_results(task, 20), vec![false, true, true]);
+        assert_eq!(app.recent_results(task, 2), vec![true, true]);
+        assert!(app.recent_results(Uuid::new_v4(), 20).is_empty());
+    }
+    
+    #[test]
+    fn test_log_day_groups() {
+        let day = |y, m, d| chrono::NaiveDate::from_ymd_opt(y, m, d).unwrap();
+        let today = day(2024, 3, 5);
+        assert_eq!(day_label(today, today), "Today");
+        assert_eq!(day_label(day(2024, 3, 4), today), "Yesterday");
+        assert_eq!(day_label(day(2024, 3, 3), today), "Mar 3");
+        assert_eq!(day_label(day(2023, 12, 31), today), "Dec 31, 2023");
+        
+        let mut app = TaskWithMe::default();
+        let _ = app.update(Message::ToggleLogDay(today));
+        assert!(app.collapsed_log_days.contains(&today));
+        let _ = app.update(Message::ToggleLogDay(today));
+        assert!(app.collapsed_log_days.is_empty());
+    }
+    
+    #[test]
+    fn test_battery_pauses_scheduling() {
+        use battery::State;
+        assert_eq!(on_battery_from_states(&[]), Some(false));
+        assert_eq!(on_battery_from_states(&[State::Full]), Some(false));
+        assert_eq!(on_battery_from_states(&[State::Charging, State::Unknown]), Some(false));
+        assert_eq!(on_battery_from_states(&[State::Discharging]), Some(true));
+        assert_eq!(on_battery_from_states(&[State::Full, State::Discharging]), Some(true));
+        assert_eq!(on_battery_from_states(&[State::Unknown]), None);
+        
+        let mut task = sample_task("Backup");
+        task.is_active = true;
+        task.next_run = Some(Local::now() - chrono::Duration::seconds(5));
+        let mut app = TaskWithMe { tasks: vec![task], ..Default::default() };
+        app.config.pause_on_battery = true;
+        
+        let _ = app.update(Message::PowerSourceDetected(Some(true)));
+        assert!(app.battery_suspended());
+        assert_eq!(app.notifications.back().unwrap().level, NotificationLevel::Warning);
+        let _ = app.update(Message::CheckScheduledTasks);
+        assert!(app.running_tasks.is_empty());
+        
+        // Unknown power state never suspends
+        let _ = app.update(Message::PowerSourceDetected(None));
+        assert!(!app.battery_suspended());
+        assert_eq!(app.notifications.back().unwrap().message, "Scheduled runs resumed");
+    }
+    
+    #[test]
+    fn test_webhook_payload_and_failure() {
+        let task = sample_task("Backup");
+        let mut log = sample_log(task.id, false, &"x".repeat(WEBHOOK_OUTPUT_BYTES + 10));
+        log.exit_code = Some(2);
+        
+        let payload = WebhookPayload::from_log(&log, &task.title);
+        let json = serde_json::to_value(&payload).unwrap();
+        assert_eq!(json["task_id"], task.id.to_string());
+        assert_eq!(json["title"], "Backup");
+        assert_eq!(json["success"], false);
+        assert_eq!(json["exit_code"], 2);
+        assert!(payload.output.starts_with(&"x".repeat(WEBHOOK_OUTPUT_BYTES)));
+        assert!(payload.output.ends_with("bytes total)"));
+        
+        // An unreachable webhook only warns
+        let mut app = TaskWithMe { tasks: vec![task], ..Default::default() };
+        let _ = app.update(Message::WebhookSent("Backup".to_string(), Err(AppError::Io("refused".to_string()))));
+        assert_eq!(app.notifications.back().unwrap().level, NotificationLevel::Warning);
+        assert_eq!(app.tasks[0].failure_count, 0);
     }
     
-    fn view_overview(&self) -> Element<Message> {
-        let total = self.tasks.len();
-        let active = self.tasks.iter().filter(|t| t.is_active).count();
-        let running = self.running_tasks.len();
+    #[test]
+    fn test_notification_preferences_silence_runs() {
+        let mut task = sample_task("Health ping");
+        task.notify_on_success = false;
+        let id = task.id;
+        let mut app = TaskWithMe { tasks: vec![task], ..Default::default() };
+        let result = |success| Ok(ExecutionResult {
+            success,
+            output: String::new(),
+            stdout: String::new(),
+            stderr: String::new(),
+            exit_code: Some(if success { 0 } else { 1 }),
+            duration_ms: 1,
+            metadata: None,
+            metric: None,
+        });
         
-        let total_success: u32 = self.tasks.iter().map(|t| t.success_count).sum();
-        let total_failure: u32 = self.tasks.iter().map(|t| t.failure_count).sum();
-        let total_runs = total_success + total_failure;
-        let success_rate = if total_runs > 0 {
-            (total_success as f32 / total_runs as f32 * 100.0) as usize
-        } else {
-            0
+        let _ = app.update(Message::TaskExecuted(id, result(true)));
+        assert!(app.notifications.is_empty());
+        assert_eq!(app.tasks[0].success_count, 1);
+        
+        let _ = app.update(Message::TaskExecuted(id, result(false)));
+        assert_eq!(app.notifications.back().unwrap().level, NotificationLevel::Error);
+        
+        app.notifications.clear();
+        let _ = app.update(Message::SetTaskNotifications(id, false, false));
+        let _ = app.update(Message::TaskExecuted(id, result(false)));
+        assert!(app.notifications.is_empty());
+        assert_eq!(app.tasks[0].failure_count, 2);
+    }
+    
+    #[test]
+    fn test_one_shot_completes_after_success() {
+        let mut task = sample_task("Migrate");
+        task.one_shot = true;
+        task.is_active = true;
+        task.next_run = Some(Local::now());
+        let id = task.id;
+        let mut app = TaskWithMe { tasks: vec![task], ..Default::default() };
+        let result = |success| Ok(ExecutionResult {
+            success,
+            output: String::new(),
+            stdout: String::new(),
+            stderr: String::new(),
+            exit_code: Some(if success { 0 } else { 1 }),
+            duration_ms: 1,
+            metadata: None,
+            metric: None,
+        });
+        
+        // A failure keeps it scheduled
+        let _ = app.update(Message::TaskExecuted(id, result(false)));
+        assert!(app.tasks[0].is_active && app.tasks[0].next_run.is_some());
+        assert!(app.tasks[0].completed_at.is_none());
+        
+        let _ = app.update(Message::TaskExecuted(id, result(true)));
+        assert!(!app.tasks[0].is_active);
+        assert!(app.tasks[0].next_run.is_none());
+        assert!(app.tasks[0].completed_at.is_some());
+        
+        // Starting it again clears the completed state
+        let _ = app.update(Message::ToggleTask(id));
+        assert!(app.tasks[0].is_active);
+        assert!(app.tasks[0].completed_at.is_none());
+    }
+    
+    #[test]
+    fn test_ui_scale_steps_within_bounds() {
+        let mut app = TaskWithMe::default();
+        app.reset_settings_draft();
+        
+        for _ in 0..3 {
+            let _ = app.update(Message::UiScaleChanged(app.settings_draft.ui_scale + UI_SCALE_STEP));
+        }
+        assert_eq!(app.settings_draft.ui_scale, 1.3);
+        assert_eq!(app.unsaved_settings(), vec!["UI Scale"]);
+        assert_eq!(app.scale_factor(), 1.0);
+        
+        let _ = app.update(Message::UiScaleChanged(5.0));
+        assert_eq!(app.settings_draft.ui_scale, MAX_UI_SCALE);
+        let _ = app.update(Message::UiScaleChanged(0.1));
+        assert_eq!(app.settings_draft.ui_scale, MIN_UI_SCALE);
+        
+        app.config.ui_scale = 9.0;
+        assert_eq!(app.scale_factor(), MAX_UI_SCALE as f64);
+        
+        let legacy: Config = serde_json::from_str(
+            r#"{"refresh_interval":5,"max_logs":500,"theme":"Dark","log_to_file":true}"#
+        ).unwrap();
+        assert_eq!(legacy.ui_scale, 1.0);
+    }
+    
+    #[test]
+    fn test_system_theme_follows_detected_scheme() {
+        let mut app = TaskWithMe::default();
+        app.config.theme = AppTheme::System;
+        assert_eq!(app.theme(), Theme::Dark);
+        
+        let _ = app.update(Message::SystemThemeDetected(Some(false)));
+        assert_eq!(app.theme(), Theme::Light);
+        let _ = app.update(Message::SystemThemeDetected(Some(true)));
+        assert_eq!(app.theme(), Theme::Dark);
+        let _ = app.update(Message::SystemThemeDetected(None));
+        assert_eq!(app.theme(), Theme::Dark);
+        
+        assert_eq!(serde_json::to_string(&AppTheme::System).unwrap(), "\"System\"");
+    }
+    
+    #[test]
+    fn test_settings_draft_tracks_and_discards_changes() {
+        let mut app = TaskWithMe::default();
+        app.reset_settings_draft();
+        assert!(app.unsaved_settings().is_empty());
+        
+        let _ = app.update(Message::RefreshIntervalChanged("30".to_string()));
+        assert_eq!(app.unsaved_settings(), vec!["Refresh Interval"]);
+        assert_eq!(app.config.refresh_interval, 5);
+        
+        let _ = app.update(Message::DiscardSettings);
+        assert!(app.unsaved_settings().is_empty());
+        assert_eq!(app.refresh_input, "5");
+    }
+    
+    #[test]
+    fn test_failure_alert_waits_for_sustained_failure() {
+        let now = Local::now();
+        let mut task = Task {
+            id: Uuid::new_v4(),
+            title: "Test".to_string(),
+            command: "false".to_string(),
+            schedule: Schedule::Interval(60),
+            is_active: true,
+            last_run: None,
+            next_run: None,
+            created_at: now,
+            success_count: 0,
+            failure_count: 0,
+            last_output: String::new(),
+            max_output_bytes: None,
+            output_file: None,
+            output_file_append: false,
+            paused_until: None,
+            skip_command_prefix: false,
+            watch_path: None,
+            failure_alert_after_seconds: None,
+            failing_since: Some(now),
+            run_on_startup: false,
+            startup_order: None,
+            on_success_command: None,
+            timeout_seconds: None,
+            timeout_grace_seconds: None,
+            run_elevated: false,
+            max_retries: 0,
+            retry_delay_seconds: 0,
+            desktop_override: AlertOverride::Inherit,
+            sound_override: AlertOverride::Inherit,
+            verbose_logging: false,
+            metric_regex: None,
+            login_shell: false,
+            working_dir: None,
+            env_vars: Vec::new(),
+            run_on_activate: true,
+            depends_on: None,
+            one_shot: false,
+            completed_at: None,
+            notify_on_success: true,
+            notify_on_failure: true,
+            webhook_url: None,
+            last_success: None,
         };
+        assert!(task.failure_alert_due(now));
         
-        let stats = row![
-            self.stat_card("Total Tasks", total, Color::from_rgb(0.2, 0.6, 0.9)),
-            self.stat_card("Active", active, Color::from_rgb(0.3, 0.8, 0.4)),
-            self.stat_card("Running", running, Color::from_rgb(0.95, 0.7, 0.2)),
-            self.stat_card("Success Rate", success_rate, Color::from_rgb(0.7, 0.4, 0.9)),
-        ]
-        .spacing(15);
+        task.failure_alert_after_seconds = Some(300);
+        assert!(!task.failure_alert_due(now + chrono::Duration::seconds(60)));
+        assert!(task.failure_alert_due(now + chrono::Duration::seconds(300)));
+    }
+    
+    #[test]
+    fn test_add_interval_clamps_large_values() {
+        let now = Local::now();
+        let cap = now + chrono::Duration::seconds(MAX_INTERVAL_SECONDS as i64);
         
-        let quick_actions = container(
-            column![
-                text("Quick Actions").size(18),
-                Space::with_height(15),
-                row![
-                    button("+ New Task")
-                        .on_press(Message::ChangeScreen(Screen::Tasks))
-                        .padding(15)
-                        .style(iced::theme::Button::Primary),
-                    button("View All Tasks")
-                        .on_press(Message::ChangeScreen(Screen::Tasks))
-                        .padding(15),
-                    button("View Logs")
-                        .on_press(Message::ChangeScreen(Screen::Logs(None)))
-                        .padding(15),
-                ]
-                .spacing(10),
-            ]
-        )
-        .padding(20)
-        .style(iced::theme::Container::Box);
+        assert_eq!(add_interval(now, 60), now + chrono::Duration::seconds(60));
+        assert_eq!(add_interval(now, MAX_INTERVAL_SECONDS), cap);
+        assert_eq!(add_interval(now, MAX_INTERVAL_SECONDS + 1), cap);
+        assert_eq!(add_interval(now, u64::MAX), cap);
         
-        let recent_tasks = self.view_recent_tasks();
+        let near_max = Local.from_utc_datetime(&(chrono::NaiveDateTime::MAX - chrono::Duration::days(2)));
+        assert!(add_interval(near_max, u64::MAX) >= near_max);
+    }
+    
+    #[test]
+    fn test_newer_version() {
+        assert_eq!(newer_version("0.1.0", "v0.2.0").unwrap(), Some("0.2.0".to_string()));
+        assert_eq!(newer_version("0.1.0", "0.1.0").unwrap(), None);
+        assert_eq!(newer_version("0.2.0", "0.1.5").unwrap(), None);
+        assert!(newer_version("0.1.0", "<html>").is_err());
+    }
+    
+    #[cfg(unix)]
+    #[test]
+    fn test_login_shell_uses_user_shell_with_login_flag() {
+        assert_eq!(platform_shell(false, None), ("sh".to_string(), vec!["-c".to_string()]));
+        let (_, flags) = platform_shell(true, None);
+        assert_eq!(flags, vec!["-l", "-c"]);
+    }
+    
+    #[test]
+    fn test_configured_shell_and_default_args() {
+        let (shell, flags) = platform_shell(false, Some(("/bin/bash", &[])));
+        assert_eq!(shell, "/bin/bash");
+        assert_eq!(flags, vec!["-c"]);
         
-        column![
-            text("Dashboard").size(26),
-            Space::with_height(20),
-            stats,
-            Space::with_height(25),
-            quick_actions,
-            Space::with_height(25),
-            recent_tasks,
-        ]
-        .into()
+        let args = vec!["-e".to_string(), "-c".to_string()];
+        assert_eq!(platform_shell(false, Some(("zsh", &args))).1, args);
+        assert_eq!(default_shell_args("cmd.exe"), &["/C"]);
+        assert_eq!(default_shell_args("pwsh"), &["-NoProfile", "-Command"]);
     }
     
-    fn stat_card(&self, label: &str, value: usize, color: Color) -> Element<Message> {
-        let display = if label == "Success Rate" {
-            format!("{}%", value)
-        } else {
-            value.to_string()
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_run_shell_timeout_escalates_to_kill() {
+        let start = Instant::now();
+        
+        let options = ShellOptions {
+            login: false,
+            shell: None,
+            shell_args: vec![],
+            timeout: Some(1),
+            grace: 1,
+            working_dir: None,
+            env_vars: vec![],
+            live_output: None,
+            secrets: vec![],
         };
+        let (_cancel_tx, cancel) = tokio::sync::watch::channel(false);
+        let result = run_shell("sleep 5", &options, cancel.clone()).await;
+        assert!(matches!(result, Err(AppError::Execution(msg)) if msg.contains("timed out")));
         
-        container(
-            column![
-                text(label).size(13),
-                Space::with_height(8),
-                text(display).size(24).style(color),
-            ]
-        )
-        .padding(20)
-        .width(Length::FillPortion(1))
-        .style(iced::theme::Container::Box)
-        .into()
+        // SIGTERM is ignored here, so only the forced kill after the grace period stops it
+        let result = run_shell("trap '' TERM; sleep 5", &options, cancel).await;
+        assert!(result.is_err());
+        assert!(start.elapsed() < Duration::from_secs(5));
     }
     
-    fn view_recent_tasks(&self) -> Element<Message> {
-        let mut recent: Vec<&Task> = self.tasks.iter()
-            .filter(|t| t.last_run.is_some())
-            .collect();
-        recent.sort_by(|a, b| b.last_run.cmp(&a.last_run));
-        recent.truncate(5);
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_run_shell_cancel_terminates_command() {
+        let start = Instant::now();
+        let (cancel_tx, cancel) = tokio::sync::watch::channel(false);
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(200)).await;
+            let _ = cancel_tx.send(true);
+        });
         
-        let content: Element<Message> = if recent.is_empty() {
-            container(text("No tasks have run yet").size(14))
-                .center_x()
-                .padding(40)
-                .into()
-        } else {
-            let mut list = column![].spacing(8);
-            
-            for task in recent {
-                let success_rate = self.success_rate(task);
-                let card = container(
-                    row![
-                        column![
-                            text(&task.title).size(14),
-                            text(format!("Last run: {}", 
-                                task.last_run.unwrap().format("%b %d, %H:%M")))
-                                .size(11),
-                        ]
-                        .width(Length::Fill),
-                        text(format!("{:.0}%", success_rate)).size(13),
-                        button("View Logs")
-                            .on_press(Message::ViewTaskLogs(task.id))
-                            .padding(8)
-                            .style(iced::theme::Button::Secondary),
-                    ]
-                    .align_items(alignment::Alignment::Center)
-                    .spacing(12)
-                )
-                .padding(12)
-                .style(iced::theme::Container::Box);
-                
-                list = list.push(card);
-            }
-            
-            scrollable(list).height(Length::Fixed(250.0)).into()
+        let options = ShellOptions {
+            login: false,
+            shell: None,
+            shell_args: vec![],
+            timeout: None,
+            grace: 1,
+            working_dir: None,
+            env_vars: vec![],
+            live_output: None,
+            secrets: vec![],
+        };
+        let result = run_shell("sleep 5", &options, cancel).await;
+        assert!(matches!(result, Err(AppError::Cancelled)));
+        assert!(start.elapsed() < Duration::from_secs(2));
+    }
+    
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_run_shell_uses_working_dir() {
+        let dir = std::env::temp_dir().canonicalize().unwrap();
+        let (_cancel_tx, cancel) = tokio::sync::watch::channel(false);
+        let mut options = ShellOptions {
+            login: false,
+            shell: None,
+            shell_args: vec![],
+            timeout: None,
+            grace: 1,
+            working_dir: Some(dir.clone()),
+            env_vars: vec![("BACKUP_TARGET".to_string(), "/mnt/backup".to_string())],
+            live_output: None,
+            secrets: vec![],
+        };
+        
+        let output = run_shell("pwd", &options, cancel.clone()).await.unwrap();
+        assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), dir.display().to_string());
+        
+        let output = run_shell("echo $BACKUP_TARGET", &options, cancel.clone()).await.unwrap();
+        assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "/mnt/backup");
+        
+        options.working_dir = Some(dir.join(Uuid::new_v4().to_string()));
+        let result = run_shell("pwd", &options, cancel).await;
+        assert!(matches!(result, Err(AppError::Execution(msg)) if msg.contains("does not exist")));
+    }
+    
+    #[test]
+    fn test_cancelled_run_is_logged_without_counting_failure() {
+        let mut app = TaskWithMe::default();
+        let task = sample_task("Long job");
+        let id = task.id;
+        app.tasks.push(task);
+        app.running_tasks.push(id);
+        app.run_started.insert(id, Instant::now() - Duration::from_secs(3));
+        
+        let _ = app.update(Message::TaskExecuted(id, Err(AppError::Cancelled)));
+        assert!(app.running_tasks.is_empty());
+        assert_eq!(app.tasks[0].failure_count, 0);
+        let log = app.logs.back().unwrap();
+        assert!(!log.success);
+        assert_eq!(log.output, "cancelled by user");
+        assert!(log.duration_ms >= 3000);
+    }
+    
+    #[test]
+    fn test_timed_out_run_is_logged_as_failure() {
+        let mut app = TaskWithMe::default();
+        let task = Task {
+            is_active: true,
+            max_retries: 1,
+            next_run: Some(Local::now() - chrono::Duration::seconds(1)),
+            ..sample_task("Hung ping")
+        };
+        let id = task.id;
+        app.tasks.push(task);
+        app.running_tasks.push(id);
+        let timed_out = || Err(AppError::Execution("timed out after 30s".to_string()));
+        
+        // Retries left: logged, not counted yet
+        let _ = app.update(Message::TaskExecuted(id, timed_out()));
+        assert!(app.running_tasks.is_empty());
+        assert_eq!(app.tasks[0].failure_count, 0);
+        assert_eq!(app.retry_attempts.get(&id), Some(&1));
+        let log = app.logs.back().unwrap();
+        assert!(!log.success);
+        assert_eq!(log.stderr, "Execution error: timed out after 30s");
+        
+        let _ = app.update(Message::TaskExecuted(id, timed_out()));
+        assert_eq!(app.tasks[0].failure_count, 1);
+        assert!(app.tasks[0].next_run.unwrap() > Local::now());
+        assert!(app.retry_attempts.is_empty());
+    }
+    
+    #[test]
+    fn test_run_that_could_not_start_is_logged_as_failure() {
+        let mut app = TaskWithMe::default();
+        let task = Task {
+            is_active: true,
+            next_run: Some(Local::now() - chrono::Duration::seconds(1)),
+            ..sample_task("Keyring locked")
+        };
+        let id = task.id;
+        app.tasks.push(task);
+        app.running_tasks.push(id);
+        
+        let error = AppError::Config("no secret service available".to_string());
+        let _ = app.update(Message::TaskExecuted(id, Err(error)));
+        assert_eq!(app.tasks[0].failure_count, 1);
+        assert!(app.tasks[0].failing_since.is_some());
+        assert!(app.tasks[0].next_run.unwrap() > Local::now());
+        assert_eq!(app.tasks[0].last_output, "Configuration error: no secret service available");
+        assert!(!app.logs.back().unwrap().success);
+    }
+    
+    #[test]
+    fn test_events_to_csv_escapes_fields() {
+        let event = AppEvent {
+            id: Uuid::new_v4(),
+            timestamp: Local::now(),
+            kind: AppEventKind::TaskCreated,
+            task_id: None,
+            detail: "'Backup' created with command `echo \"a,b\"`".to_string(),
+        };
+        let csv = events_to_csv(&[event]);
+        let row = csv.lines().nth(1).unwrap();
+        
+        assert!(row.contains(",Task created,,"));
+        assert!(row.ends_with("\"'Backup' created with command `echo \"\"a,b\"\"`\""));
+    }
+    
+    #[test]
+    fn test_shell_quote() {
+        assert_eq!(shell_quote("echo hi"), "'echo hi'");
+        assert_eq!(shell_quote("echo 'a b'"), "'echo '\\''a b'\\'''");
+    }
+    
+    #[test]
+    fn test_output_tail() {
+        assert_eq!(output_tail("a\nb\nc\nd\n", 3), "b\nc\nd");
+        assert_eq!(output_tail("only", 3), "only");
+        assert_eq!(output_tail("", 3), "");
+    }
+    
+    #[test]
+    fn test_edit_task_preserves_identity_and_stats() {
+        let mut app = TaskWithMe::default();
+        let _ = app.update(Message::TitleInput("Backup".to_string()));
+        let _ = app.update(Message::CommandInput("echo one".to_string()));
+        let _ = app.update(Message::IntervalInput("60".to_string()));
+        let _ = app.update(Message::CreateTask);
+        assert!(app.saving_task);
+        // Another save finishing first must not re-enable the form
+        let _ = app.update(Message::TaskSaved(Ok(())));
+        assert!(app.saving_task);
+        let _ = app.update(Message::TaskFormSaved(Ok(())));
+        assert!(!app.saving_task);
+        assert_eq!(app.tasks.len(), 1);
+        let created = app.tasks[0].id;
+        app.tasks[0].success_count = 4;
+        app.tasks[0].failure_count = 1;
+        let created_at = app.tasks[0].created_at;
+        
+        let _ = app.update(Message::EditTask(created));
+        assert_eq!(app.command_input, "echo one");
+        let _ = app.update(Message::CommandInput("echo two".to_string()));
+        let _ = app.update(Message::CreateTask);
+        
+        assert_eq!(app.tasks.len(), 1);
+        let task = &app.tasks[0];
+        assert_eq!(task.id, created);
+        assert_eq!(task.command, "echo two");
+        assert_eq!(task.created_at, created_at);
+        assert_eq!((task.success_count, task.failure_count), (4, 1));
+        assert!(app.editing_task.is_none());
+        assert_eq!(app.events.back().unwrap().kind, AppEventKind::TaskUpdated);
+    }
+    
+    #[test]
+    fn test_duplicate_task_ids_are_reassigned_on_load() {
+        let id = Uuid::new_v4();
+        let entry = |title: &str| format!(
+            r#"{{"id":"{}","title":"{}","command":"echo","interval_seconds":60,"is_active":false,
+                "last_run":null,"next_run":null,"created_at":"{}","success_count":0,"failure_count":0}}"#,
+            id, title, Local::now().to_rfc3339()
+        );
+        let content = format!("[{},{},{}]", entry("First"), entry("Second"), entry("Third"));
+        let mut tasks: Vec<Task> = serde_json::from_str(&content).unwrap();
+        
+        let reassigned = dedupe_task_ids(&mut tasks);
+        
+        assert_eq!(reassigned, vec!["Second".to_string(), "Third".to_string()]);
+        assert_eq!(tasks[0].id, id);
+        let unique: HashSet<Uuid> = tasks.iter().map(|t| t.id).collect();
+        assert_eq!(unique.len(), 3);
+        assert!(dedupe_task_ids(&mut tasks).is_empty());
+    }
+    
+    fn sample_task(title: &str) -> Task {
+        serde_json::from_str(&format!(
+            r#"{{"id":"{}","title":"{}","command":"false","interval_seconds":60,"is_active":false,
+                "last_run":null,"next_run":null,"created_at":"{}","success_count":0,"failure_count":0}}"#,
+            Uuid::new_v4(), title, Local::now().to_rfc3339()
+        ))
+        .unwrap()
+    }
+    
+    fn sample_log(task_id: Uuid, success: bool, output: &str) -> ExecutionLog {
+        ExecutionLog {
+            id: Uuid::new_v4(),
+            task_id,
+            timestamp: Local::now(),
+            success,
+            output: output.to_string(),
+            duration_ms: 10,
+            attempt: 0,
+            metadata: None,
+            metric: None,
+            stdout: String::new(),
+            stderr: String::new(),
+            exit_code: None,
+        }
+    }
+    
+    #[test]
+    fn test_parse_log_lines_skips_broken_lines_and_repeats() {
+        let task_id = Uuid::new_v4();
+        let first = sample_log(task_id, true, "one");
+        let mut updated = first.clone();
+        updated.output = "one again".to_string();
+        let second = sample_log(task_id, false, "two");
+        
+        let mut content = logs_to_jsonl(&[first, second.clone(), updated]).unwrap();
+        content.push_str(r#"{"id":"cut short"#);
+        
+        let (logs, skipped) = parse_log_lines(&content);
+        assert_eq!(skipped, 1);
+        assert_eq!(logs.len(), 2);
+        assert_eq!(logs[0].output, "one again");
+        assert_eq!(logs[1].id, second.id);
+    }
+    
+    #[test]
+    fn test_flush_appends_until_compaction_is_due() {
+        let task_id = Uuid::new_v4();
+        let mut app = TaskWithMe::default();
+        app.config.max_logs = 10;
+        
+        for n in 0..3 {
+            app.push_log(sample_log(task_id, true, &n.to_string()));
+        }
+        assert!(app.has_unflushed_changes());
+        drop(app.flush_state());
+        assert_eq!(app.log_lines_on_disk, 3);
+        assert!(app.pending_logs.is_empty());
+        
+        app.log_lines_on_disk = 10 + LOG_COMPACT_SLACK;
+        app.push_log(sample_log(task_id, true, "over"));
+        drop(app.flush_state());
+        assert_eq!(app.log_lines_on_disk, app.logs.len());
+    }
+    
+    #[test]
+    fn test_task_writes_are_whole_snapshots() {
+        let (keep, gone) = (sample_task("Keep"), sample_task("Gone"));
+        let (keep_id, gone_id) = (keep.id, gone.id);
+        let mut app = TaskWithMe { tasks_loaded: true, tasks: vec![keep, gone], ..Default::default() };
+        
+        let _ = app.update(Message::DeleteTask(gone_id));
+        let delete_generation = app.tasks_generation;
+        // A flush taken while the delete is in flight must not write the task back
+        app.dirty_tasks.insert(keep_id);
+        drop(app.flush_state());
+        assert_eq!(app.tasks_generation, delete_generation + 1);
+        let (_, snapshot) = app.tasks_snapshot();
+        assert_eq!(snapshot.iter().map(|t| t.id).collect::<Vec<_>>(), vec![keep_id]);
+        
+        let _ = app.update(Message::TaskDeleted(gone_id, Ok(())));
+        assert_eq!(app.tasks.len(), 1);
+        assert!(app.deleting_tasks.is_empty());
+    }
+    
+    #[test]
+    fn test_log_output_is_derived_from_streams() {
+        let log = ExecutionLog {
+            stdout: "done".to_string(),
+            stderr: "warning: slow".to_string(),
+            ..sample_log(Uuid::new_v4(), true, "")
+        };
+        let line = serde_json::to_string(&log).unwrap();
+        assert!(!line.contains("\"output\""));
+        let parsed: ExecutionLog = serde_json::from_str(&line).unwrap();
+        assert_eq!(parsed.output_text(), "done\n--- stderr ---\nwarning: slow");
+        
+        // Logs from before the split only have the combined text
+        let legacy = sample_log(Uuid::new_v4(), true, "old output");
+        let parsed: ExecutionLog = serde_json::from_str(&serde_json::to_string(&legacy).unwrap()).unwrap();
+        assert_eq!(parsed.output_text(), "old output");
+    }
+    
+    #[test]
+    fn test_failed_flush_keeps_run_results_queued() {
+        let task = sample_task("Backup");
+        let id = task.id;
+        let mut app = TaskWithMe { tasks_loaded: true, ..Default::default() };
+        app.tasks.push(task);
+        
+        app.running_tasks.push(id);
+        let ok = ExecutionResult { success: true, output: "done".to_string(), duration_ms: 1, metadata: None, metric: None, stdout: String::new(), stderr: String::new(), exit_code: None };
+        let _ = app.update(Message::TaskExecuted(id, Ok(ok)));
+        assert_eq!(app.tasks[0].success_count, 1);
+        
+        drop(app.flush_state());
+        assert!(!app.has_unflushed_changes());
+        
+        let flushed = FlushedState { tasks: vec![id, Uuid::new_v4()], logs: true, events: false, activity: false };
+        let _ = app.update(Message::StateFlushed(flushed, Err(AppError::Io("disk full".to_string()))));
+        assert_eq!(app.dirty_tasks, HashSet::from([id]));
+        assert!(app.logs_dirty && !app.events_dirty);
+        assert_eq!(app.notifications.back().unwrap().level, NotificationLevel::Error);
+        // Counters stay as shown; the next flush writes them
+        assert_eq!(app.tasks[0].success_count, 1);
+    }
+    
+    #[test]
+    fn test_log_search_combines_with_task_and_status_filters() {
+        let backup = sample_task("Backup");
+        let sync = sample_task("Sync");
+        let app = TaskWithMe {
+            logs: VecDeque::from([
+                sample_log(backup.id, false, "Connection TIMEOUT after 30s"),
+                sample_log(backup.id, true, "done, no timeout"),
+                sample_log(backup.id, false, "disk full"),
+                sample_log(sync.id, false, "timeout"),
+            ]),
+            tasks: vec![backup.clone(), sync.clone()],
+            log_search: "timeout".to_string(),
+            log_status_filter: LogStatusFilter::Failed,
+            ..Default::default()
+        };
+        
+        let matching = app.filtered_logs(Some(backup.id));
+        assert_eq!(matching.len(), 1);
+        assert_eq!(matching[0].output, "Connection TIMEOUT after 30s");
+        
+        let by_title = TaskWithMe { log_search: "sync".to_string(), ..app };
+        assert_eq!(by_title.filtered_logs(None).len(), 1);
+    }
+    
+    #[test]
+    fn test_failed_attempts_retry_before_counting_failure() {
+        let mut app = TaskWithMe::default();
+        let mut task = sample_task("Flaky");
+        task.max_retries = 2;
+        let id = task.id;
+        app.tasks.push(task);
+        
+        let failed = || Ok(ExecutionResult { success: false, output: "down".to_string(), duration_ms: 5, metadata: None, metric: None, stdout: String::new(), stderr: String::new(), exit_code: None });
+        
+        let _ = app.update(Message::TaskExecuted(id, failed()));
+        assert_eq!(app.retry_attempts.get(&id), Some(&1));
+        let _ = app.update(Message::TaskExecuted(id, failed()));
+        assert_eq!(app.retry_attempts.get(&id), Some(&2));
+        assert_eq!(app.tasks[0].failure_count, 0);
+        
+        let _ = app.update(Message::TaskExecuted(id, failed()));
+        assert!(!app.retry_attempts.contains_key(&id));
+        assert_eq!(app.tasks[0].failure_count, 1);
+        
+        let attempts: Vec<u32> = app.logs.iter().map(|l| l.attempt).collect();
+        assert_eq!(attempts, vec![0, 1, 2]);
+    }
+    
+    #[test]
+    fn test_alert_override_layers_on_global_setting() {
+        assert!(AlertOverride::Inherit.resolve(true));
+        assert!(!AlertOverride::Inherit.resolve(false));
+        assert!(AlertOverride::On.resolve(false));
+        assert!(!AlertOverride::Off.resolve(true));
+        
+        let task = sample_task("Routine");
+        assert_eq!(task.desktop_override, AlertOverride::Inherit);
+        assert_eq!(task.sound_override, AlertOverride::Inherit);
+    }
+    
+    #[test]
+    fn test_create_task_validates_cron_expression() {
+        let mut app = TaskWithMe {
+            title_input: "Weekday report".to_string(),
+            command_input: "echo report".to_string(),
+            cron_mode_input: true,
+            cron_input: "0 9 * *".to_string(),
+            ..Default::default()
         };
         
-        container(
-            column![
-                text("Recent Activity").size(18),
-                Space::with_height(12),
-                content,
-            ]
-        )
-        .padding(20)
-        .style(iced::theme::Container::Box)
-        .into()
+        let _ = app.update(Message::CreateTask);
+        assert!(app.notifications.iter().any(|n| n.message.starts_with("Invalid cron expression")));
+        
+        // A valid expression is accepted and the form is cleared
+        app.cron_input = "0  9 * * 1-5".to_string();
+        let _ = app.update(Message::CreateTask);
+        assert!(!app.cron_mode_input);
+        assert!(app.cron_input.is_empty());
+        
+        let mut task = sample_task("Weekday report");
+        task.schedule = Schedule::Cron("0 9 * * 1-5".to_string());
+        let json = serde_json::to_value(&task).unwrap();
+        assert_eq!(json["cron"], "0 9 * * 1-5");
+        assert_eq!(json["interval_seconds"], 0);
+        let loaded: Task = serde_json::from_value(json).unwrap();
+        assert_eq!(loaded.schedule, task.schedule);
+        
+        // Tasks saved before cron support still deserialize as interval schedules
+        let mut json = serde_json::to_value(sample_task("Legacy")).unwrap();
+        json.as_object_mut().unwrap().remove("cron");
+        let loaded: Task = serde_json::from_value(json).unwrap();
+        assert_eq!(loaded.schedule, Schedule::Interval(60));
     }
     
-    fn view_tasks(&self) -> Element<Message> {
-        // Task creation form
-        let form = container(
-            column![
-                text("Create New Task").size(18),
-                Space::with_height(12),
-                row![
-                    column![
-                        text("Title").size(12),
-                        text_input("Enter task title", &self.title_input)
-                            .on_input(Message::TitleInput)
-                            .padding(8)
-                            .width(Length::Fixed(200.0)),
-                    ]
-                    .spacing(4),
-                    column![
-                        text("Command").size(12),
-                        text_input("Enter shell command", &self.command_input)
-                            .on_input(Message::CommandInput)
-                            .padding(8)
-                            .width(Length::Fixed(300.0)),
-                    ]
-                    .spacing(4),
-                    column![
-                        text("Interval (sec)").size(12),
-                        text_input("60", &self.interval_input)
-                            .on_input(Message::IntervalInput)
-                            .padding(8)
-                            .width(Length::Fixed(120.0)),
-                    ]
-                    .spacing(4),
-                    column![
-                        Space::with_height(12),
-                        button("Create")
-                            .on_press(Message::CreateTask)
-                            .padding(8)
-                            .style(iced::theme::Button::Primary),
-                    ],
-                ]
-                .spacing(10)
-                .align_items(alignment::Alignment::End),
-            ]
-        )
-        .padding(20)
-        .style(iced::theme::Container::Box);
+    #[test]
+    fn test_log_level_setting_is_saved() {
+        let mut app = TaskWithMe::default();
+        app.reset_settings_draft();
+        assert_eq!(app.config.log_level, LogLevel::Warn);
         
-        // Templates
-        let mut templates_col = column![
-            text("Quick Templates").size(16),
-            Space::with_height(10),
-        ].spacing(6);
+        let _ = app.update(Message::LogLevelChanged(LogLevel::Debug));
+        assert_eq!(app.unsaved_settings(), vec!["Log Level"]);
+        let _ = app.update(Message::SaveSettings);
+        assert_eq!(app.config.log_level.filter(), log::LevelFilter::Debug);
+    }
+    
+    #[test]
+    fn test_search_applies_after_typing_pauses() {
+        let mut app = TaskWithMe { task_page: 2, ..Default::default() };
+        let _ = app.update(Message::SearchInput("ba".to_string()));
+        let _ = app.update(Message::SearchInput("back".to_string()));
+        assert_eq!(app.search_input, "back");
+        assert!(app.search_query.is_empty());
         
-        for (idx, template) in self.templates.iter().enumerate() {
-            let btn = button(
-                column![
-                    text(template.name).size(13),
-                    text(template.description).size(11),
-                ]
-                .spacing(2)
-            )
-            .on_press(Message::SelectTemplate(idx))
-            .padding(10)
-            .width(Length::Fill)
-            .style(iced::theme::Button::Secondary);
-            
-            templates_col = templates_col.push(btn);
+        // The first keystroke's timer is stale
+        let _ = app.update(Message::ApplySearch(app.search_seq - 1));
+        assert!(app.search_query.is_empty());
+        let _ = app.update(Message::ApplySearch(app.search_seq));
+        assert_eq!(app.search_query, "back");
+        assert_eq!(app.task_page, 0);
+    }
+    
+    #[test]
+    fn test_bulk_actions_apply_to_selection() {
+        let mut app = TaskWithMe::default();
+        let (a, b, c) = (sample_task("Alpha"), sample_task("Beta"), sample_task("Gamma"));
+        let (a_id, b_id, c_id) = (a.id, b.id, c.id);
+        app.tasks = vec![a, b, c];
+        app.tasks.iter_mut().for_each(|t| t.is_active = true);
+        app.search_query = "alpha".to_string();
+        
+        let _ = app.update(Message::SelectAllFiltered);
+        let _ = app.update(Message::ToggleTaskSelected(b_id, true));
+        assert_eq!(app.selected_tasks, HashSet::from([a_id, b_id]));
+        
+        let _ = app.update(Message::SetSelectedActive(false));
+        assert!(!app.tasks.iter().any(|t| t.id != c_id && t.is_active));
+        assert!(app.tasks.iter().find(|t| t.id == c_id).unwrap().is_active);
+        
+        let _ = app.update(Message::RequestDeleteSelected);
+        assert!(app.needs_confirmation(app.pending_action.as_ref().unwrap()));
+        let _ = app.update(Message::ConfirmPendingAction);
+        assert!(app.deleting_tasks.contains(&a_id) && app.deleting_tasks.contains(&b_id));
+        
+        app.logs = vec![sample_log(a_id, true, "x"), sample_log(c_id, true, "y")].into();
+        let _ = app.update(Message::TasksDeleted(vec![a_id, b_id], Ok(())));
+        assert!(app.selected_tasks.is_empty() && app.deleting_tasks.is_empty());
+        assert_eq!(app.logs.len(), 1);
+    }
+    
+    #[test]
+    fn test_pending_delete_ignores_duplicate_requests() {
+        let mut app = TaskWithMe::default();
+        let task = sample_task("Cleanup");
+        let id = task.id;
+        app.tasks.push(task);
+        
+        let _ = app.update(Message::DeleteTask(id));
+        let _ = app.update(Message::DeleteTask(id));
+        assert!(app.deleting_tasks.contains(&id));
+        let deleted = app.events.iter().filter(|e| e.kind == AppEventKind::TaskDeleted).count();
+        assert_eq!(deleted, 1);
+        
+        let _ = app.update(Message::TaskDeleted(id, Err(AppError::Io("disk full".to_string()))));
+        assert!(!app.deleting_tasks.contains(&id));
+    }
+    
+    #[test]
+    fn test_next_run_stays_on_interval_grid() {
+        let task = sample_task("Every minute");
+        let start = Local.with_ymd_and_hms(2024, 3, 1, 9, 0, 0).unwrap();
+        let secs = |n: i64| chrono::Duration::seconds(n);
+        
+        // Three runs that each start late: normal latency, a slow poll, and a sleep past a full cycle
+        let mut scheduled = start;
+        for delay in [3, 7, 65] {
+            let now = scheduled + secs(delay);
+            scheduled = next_scheduled_run(&task, None, Some(scheduled), now).unwrap();
+            assert!(scheduled > now);
+            assert_eq!((scheduled - start).num_seconds() % 60, 0);
         }
+        assert_eq!(scheduled, start + secs(240));
         
-        let templates = container(templates_col)
-            .padding(15)
-            .style(iced::theme::Container::Box);
+        // Running early keeps the pending slot; no previous slot starts from now
+        assert_eq!(next_scheduled_run(&task, None, Some(scheduled), start), Some(scheduled));
+        assert_eq!(next_scheduled_run(&task, None, None, start), Some(start + secs(60)));
+    }
+    
+    #[test]
+    fn test_cron_run_that_overlaps_next_slot_keeps_it() {
+        let mut task = sample_task("Every five minutes");
+        task.schedule = Schedule::Cron("*/5 * * * *".to_string());
+        let slot = Local.with_ymd_and_hms(2024, 3, 1, 9, 0, 0).unwrap();
+        let mins = |n: i64| chrono::Duration::minutes(n);
         
-        // Search and filter
-        let controls = container(
-            row![
-                text_input("Search tasks...", &self.search_query)
-                    .on_input(Message::SearchInput)
-                    .padding(8)
-                    .width(Length::Fixed(250.0)),
-                Space::with_width(Length::Fill),
-                row![
-                    button("All")
-                        .on_press(Message::FilterChanged(TaskFilter::All))
-                        .style(if self.filter == TaskFilter::All {
-                            iced::theme::Button::Primary
-                        } else {
-                            iced::theme::Button::Secondary
-                        })
-                        .padding([6, 12]),
-                    button("Active")
-                        .on_press(Message::FilterChanged(TaskFilter::Active))
-                        .style(if self.filter == TaskFilter::Active {
-                            iced::theme::Button::Primary
-                        } else {
-                            iced::theme::Button::Secondary
-                        })
-                        .padding([6, 12]),
-                    button("Inactive")
-                        .on_press(Message::FilterChanged(TaskFilter::Inactive))
-                        .style(if self.filter == TaskFilter::Inactive {
-                            iced::theme::Button::Primary
-                        } else {
-                            iced::theme::Button::Secondary
-                        })
-                        .padding([6, 12]),
-                ]
-                .spacing(6),
-            ]
-            .align_items(alignment::Alignment::Center)
-        )
-        .padding(12)
-        .style(iced::theme::Container::Box);
+        // Finishing early steps to the following slot
+        assert_eq!(next_scheduled_run(&task, None, Some(slot), slot + mins(1)), Some(slot + mins(5)));
+        // A run that finished past 9:05 leaves that slot due instead of waiting for 9:10
+        assert_eq!(next_scheduled_run(&task, None, Some(slot), slot + mins(7)), Some(slot + mins(5)));
+        // Several missed slots are skipped, like missed intervals
+        assert_eq!(next_scheduled_run(&task, None, Some(slot), slot + mins(12)), Some(slot + mins(15)));
+        // A manual run ahead of the slot leaves it alone
+        assert_eq!(next_scheduled_run(&task, None, Some(slot), slot - mins(1)), Some(slot));
+    }
+    
+    #[test]
+    fn test_run_metadata_masks_secrets_and_env_values() {
+        let rendered = RenderedCommand {
+            command: "curl -H 'Token: hunter2' https://example.com".to_string(),
+            secrets: vec!["hunter2".to_string()],
+        };
+        let metadata = run_metadata(&rendered, &ShellOptions::for_task(&sample_task("Report"), &Config::default()));
+        assert_eq!(metadata.command, "curl -H 'Token: ****' https://example.com");
+        assert!(metadata.env_keys.iter().any(|key| key == "PATH"));
         
-        // Task list
-        let filtered = self.filtered_tasks();
+        let path = std::env::var("PATH").unwrap();
+        let described = metadata.describe();
+        assert!(described.contains("PATH=****"));
+        assert!(!described.contains(&path));
+    }
+    
+    #[test]
+    fn test_extract_metric() {
+        let df = "Filesystem Size Used Avail Use%\n/dev/sda1 100G 40G 60G 40%";
+        assert_eq!(extract_metric(r"(\d+)%", df), Some(40.0));
+        assert_eq!(extract_metric(r"Avail\s+Use%\n\S+\s+\S+\s+\S+\s+([\d.]+)G", df), Some(60.0));
+        assert_eq!(extract_metric(r"\d+\.\d+", "load 1.25"), Some(1.25));
+        assert_eq!(extract_metric(r"rows: ([\d,]+)", "rows: 12,500"), Some(12500.0));
+        assert_eq!(extract_metric(r"(\d+)%", "no numbers"), None);
+        assert_eq!(extract_metric(r"(unclosed", "anything"), None);
+    }
+    
+    #[test]
+    fn test_deleting_task_purges_its_logs_unless_kept() {
+        let log = |task_id: Uuid| ExecutionLog {
+            id: Uuid::new_v4(),
+            task_id,
+            timestamp: Local::now(),
+            success: true,
+            output: String::new(),
+            duration_ms: 1,
+            attempt: 0,
+            metadata: None,
+            metric: None,
+            stdout: String::new(),
+            stderr: String::new(),
+            exit_code: None,
+        };
+        let (deleted, kept) = (Uuid::new_v4(), Uuid::new_v4());
         
-        // Debug info
-        let debug_info = container(
-            text(format!("Total tasks: {} | Filtered: {} | Active filter: {:?}", 
-                self.tasks.len(), filtered.len(), self.filter))
-                .size(11)
-        )
-        .padding(8)
-        .style(iced::theme::Container::Box);
+        let mut app = TaskWithMe {
+            logs: VecDeque::from([log(deleted), log(kept), log(deleted)]),
+            ..Default::default()
+        };
+        let _ = app.update(Message::TaskDeleted(deleted, Ok(())));
+        assert_eq!(app.logs.len(), 1);
+        assert_eq!(app.logs[0].task_id, kept);
+        assert!(app.logs_dirty);
         
-        let task_list: Element<Message> = if filtered.is_empty() {
-            container(
-                column![
-                    text("No tasks found").size(14),
-                    Space::with_height(10),
-                    text(format!("Total tasks in memory: {}", self.tasks.len())).size(11),
-                ]
-            )
-            .center_x()
-            .padding(40)
-            .into()
-        } else {
-            let mut list = column![].spacing(8);
-            
-            println!("Rendering {} tasks", filtered.len());
-            
-            for task in filtered {
-                let is_running = self.running_tasks.contains(&task.id);
-                let success_rate = self.success_rate(task);
-                
-                let status_color = if task.is_active {
-                    Color::from_rgb(0.3, 0.8, 0.4)
-                } else {
-                    Color::from_rgb(0.5, 0.5, 0.5)
-                };
-                
-                println!("  Rendering task: {}", task.title);
-                
-                let card = container(
-                    row![
-                        container(Space::with_width(4))
-                            .width(Length::Fixed(4.0))
-                            .height(Length::Fixed(80.0))
-                            .style(iced::theme::Container::Custom(Box::new(
-                                ColoredContainer(status_color)
-                            ))),
-                        column![
-                            row![
-                                text(&task.title).size(15),
-                                Space::with_width(Length::Fill),
-                                text(format!("{:.0}%", success_rate)).size(12),
-                            ]
-                            .align_items(alignment::Alignment::Center),
-                            text(&task.command).size(12),
-                            row![
-                                text(format!("Every {}", Self::format_duration(task.interval_seconds)))
-                                    .size(11),
-                                Space::with_width(Length::Fill),
-                                if let Some(next) = task.next_run {
-                                    text(format!("Next: {}", next.format("%H:%M"))).size(11)
-                                } else {
-                                    text("Not scheduled").size(11)
-                                },
-                            ],
-                        ]
-                        .spacing(4)
-                        .width(Length::Fill),
-                        row![
-                            button(if is_running { "Running" } else { "Run" })
-                                .on_press(Message::ExecuteTask(task.id))
-                                .padding(8)
-                                .style(if is_running {
-                                    iced::theme::Button::Secondary
-                                } else {
-                                    iced::theme::Button::Primary
-                                }),
-                            button(if task.is_active { "Pause" } else { "Start" })
-                                .on_press(Message::ToggleTask(task.id))
-                                .padding(8)
-                                .style(iced::theme::Button::Secondary),
-                            button("Logs")
-                                .on_press(Message::ViewTaskLogs(task.id))
-                                .padding(8)
-                                .style(iced::theme::Button::Secondary),
-                            button("Delete")
-                                .on_press(Message::DeleteTask(task.id))
-                                .padding(8)
-                                .style(iced::theme::Button::Destructive),
-                        ]
-                        .spacing(6),
-                    ]
-                    .align_items(alignment::Alignment::Center)
-                    .spacing(12)
-                )
-                .padding(12)
-                .width(Length::Fill)
-                .style(iced::theme::Container::Box);
-                
-                list = list.push(card);
-            }
+        let mut app = TaskWithMe::default();
+        app.config.keep_orphaned_logs = true;
+        app.logs = vec![log(deleted), log(kept)].into();
+        let _ = app.update(Message::TaskDeleted(deleted, Ok(())));
+        assert_eq!(app.logs.len(), 2);
+    }
+    
+    #[test]
+    fn test_lowering_max_logs_keeps_newest_entries() {
+        let task_id = Uuid::new_v4();
+        let logs: Vec<ExecutionLog> = (0..15).map(|n| ExecutionLog {
+            id: Uuid::new_v4(),
+            task_id,
+            timestamp: Local::now(),
+            success: true,
+            output: n.to_string(),
+            duration_ms: 1,
+            attempt: 0,
+            metadata: None,
+            metric: None,
+            stdout: String::new(),
+            stderr: String::new(),
+            exit_code: None,
+        }).collect();
+        let mut app = TaskWithMe { logs: logs.into(), ..Default::default() };
+        
+        let _ = app.update(Message::MaxLogsChanged("10".to_string()));
+        let _ = app.update(Message::SaveSettings);
+        
+        assert_eq!(app.logs.len(), 10);
+        assert_eq!(app.logs.front().unwrap().output, "5");
+        assert_eq!(app.logs.back().unwrap().output, "14");
+        assert!(app.logs_dirty);
+    }
+    
+    #[test]
+    fn test_env_var_names() {
+        assert!(is_valid_env_name("BACKUP_TARGET"));
+        assert!(!is_valid_env_name(""));
+        assert!(!is_valid_env_name("A=B"));
+        assert!(!is_valid_env_name("MY VAR"));
+        
+        assert!(looks_secret("github_token"));
+        assert!(looks_secret("DB_PASSWORD"));
+        assert!(!looks_secret("BACKUP_TARGET"));
+    }
+    
+    #[test]
+    fn test_run_all_active_respects_concurrency_limit() {
+        let mut app = TaskWithMe::default();
+        app.config.max_concurrent_tasks = 2;
+        for n in 0..5 {
+            let mut task = sample_task(&format!("Task {}", n));
+            task.is_active = n < 4;
+            app.tasks.push(task);
+        }
+        app.running_tasks.push(app.tasks[0].id);
+        
+        let _ = app.update(Message::RunAllActive);
+        assert_eq!(app.running_tasks.len(), 2);
+        assert_eq!(app.run_queue.len(), 2);
+        assert!(!app.run_queue.contains(&app.tasks[0].id));
+        assert!(!app.run_queue.contains(&app.tasks[4].id));
+        
+        // A finished run frees a slot for the next queued task
+        let done = app.running_tasks[0];
+        let ok = ExecutionResult { success: true, output: String::new(), duration_ms: 1, metadata: None, metric: None, stdout: String::new(), stderr: String::new(), exit_code: None };
+        let _ = app.update(Message::TaskExecuted(done, Ok(ok)));
+        assert_eq!(app.running_tasks.len(), 2);
+        assert_eq!(app.run_queue.len(), 1);
+    }
+    
+    #[test]
+    fn test_manual_runs_queue_at_concurrency_limit() {
+        let mut app = TaskWithMe::default();
+        app.config.max_concurrent_tasks = 1;
+        let (first, second) = (sample_task("First"), sample_task("Second"));
+        let (first_id, second_id) = (first.id, second.id);
+        app.tasks.extend([first, second]);
+        
+        let _ = app.update(Message::ExecuteTask(first_id));
+        let _ = app.update(Message::ExecuteTask(second_id));
+        let _ = app.update(Message::ExecuteTask(second_id));
+        assert_eq!(app.running_tasks, vec![first_id]);
+        assert_eq!(app.run_queue, VecDeque::from([second_id]));
+        
+        let ok = ExecutionResult { success: true, output: String::new(), duration_ms: 1, metadata: None, metric: None, stdout: String::new(), stderr: String::new(), exit_code: None };
+        let _ = app.update(Message::TaskExecuted(first_id, Ok(ok)));
+        assert_eq!(app.running_tasks, vec![second_id]);
+        assert!(app.run_queue.is_empty());
+    }
+    
+    #[test]
+    fn test_output_cap_defaults_to_8kb() {
+        assert_eq!(Config::default().max_output_bytes, Some(8192));
+        
+        let legacy: Config = serde_json::from_str(
+            r#"{"refresh_interval":5,"max_logs":500,"theme":"Dark","log_to_file":true}"#
+        ).unwrap();
+        assert_eq!(legacy.max_output_bytes, Some(8192));
+        
+        let mut app = TaskWithMe::default();
+        let _ = app.update(Message::GlobalMaxOutputChanged(String::new()));
+        assert_eq!(app.draft_config().max_output_bytes, None);
+    }
+    
+    #[test]
+    fn test_corrupt_file_without_backup_is_moved_aside() {
+        let dir = std::env::temp_dir().join(format!("task-with-me-test-{}", Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("tasks.json");
+        
+        for malformed in ["[{\"id\": ", "not json at all", "{\"tasks\": []}"] {
+            fs::write(&path, malformed).unwrap();
+            let loaded = read_json::<Vec<Task>>(&path).unwrap().unwrap();
+            assert!(loaded.value.is_empty());
+            assert_eq!(loaded.restored_from, None);
             
-            container(
-                scrollable(list)
-                    .height(Length::Fill)
-            )
-            .height(Length::Fixed(400.0))
-            .into()
-        };
+            let moved = loaded.moved_aside.unwrap();
+            assert!(moved.file_name().unwrap().to_string_lossy().starts_with("tasks.json.corrupt-"));
+            assert_eq!(fs::read_to_string(&moved).unwrap(), malformed);
+            assert!(!path.exists());
+            fs::remove_file(moved).unwrap();
+        }
         
-        column![
-            text("Task Management").size(26),
-            Space::with_height(20),
-            form,
-            Space::with_height(15),
-            templates,
-            Space::with_height(15),
-            controls,
-            Space::with_height(12),
-            debug_info,
-            Space::with_height(8),
-            task_list,
-        ]
-        .into()
+        let _ = fs::remove_dir_all(dir);
     }
     
-    fn view_logs(&self, task_id: Option<Uuid>) -> Element<Message> {
-        let filtered_logs: Vec<&ExecutionLog> = if let Some(id) = task_id {
-            self.logs.iter().filter(|l| l.task_id == id).collect()
-        } else {
-            self.logs.iter().collect()
-        };
+    #[test]
+    fn test_corrupt_path_keeps_file_name() {
+        let now = Local.with_ymd_and_hms(2024, 3, 5, 14, 7, 9).unwrap();
+        assert_eq!(
+            corrupt_path(Path::new("/data/tasks.json"), now),
+            PathBuf::from("/data/tasks.json.corrupt-20240305-140709")
+        );
+    }
+    
+    #[test]
+    fn test_atomic_write_backs_up_jsonl() {
+        let dir = std::env::temp_dir().join(format!("task-with-me-test-{}", Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("logs.jsonl");
+        assert_eq!(backup_path(&path), dir.join("logs.jsonl.bak"));
         
-        let task_name = task_id.and_then(|id| {
-            self.tasks.iter().find(|t| t.id == id).map(|t| t.title.clone())
+        write_atomic(&path, "{\"a\":1}\n{\"a\":2}\n").unwrap();
+        write_atomic(&path, "{\"a\":3}\n").unwrap();
+        assert_eq!(fs::read_to_string(backup_path(&path)).unwrap(), "{\"a\":1}\n{\"a\":2}\n");
+        
+        // A line cut short by a crash keeps the earlier backup
+        fs::write(&path, "{\"a\":3}\n{\"a\"").unwrap();
+        write_atomic(&path, "{\"a\":4}\n").unwrap();
+        assert_eq!(fs::read_to_string(backup_path(&path)).unwrap(), "{\"a\":1}\n{\"a\":2}\n");
+        
+        // Concurrent writes each use their own temporary file
+        std::thread::scope(|scope| {
+            for n in 0..8 {
+                let path = &path;
+                scope.spawn(move || write_atomic(path, &format!("{{\"a\":{}}}\n", n)).unwrap());
+            }
         });
+        assert!(is_valid_data(&path, &fs::read_to_string(&path).unwrap()));
+        let _ = fs::remove_dir_all(&dir);
+    }
+    
+    #[test]
+    fn test_atomic_write_keeps_backup_for_corrupt_file() {
+        let dir = std::env::temp_dir().join(format!("task-with-me-test-{}", Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("tasks.json");
         
-        let header = if let Some(name) = task_name {
-            text(format!("Logs for: {}", name)).size(20)
-        } else {
-            text("All Execution Logs").size(20)
+        write_atomic(&path, "[1]").unwrap();
+        write_atomic(&path, "[1, 2]").unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "[1, 2]");
+        assert_eq!(fs::read_to_string(backup_path(&path)).unwrap(), "[1]");
+        let leftovers = fs::read_dir(&dir).unwrap()
+            .filter(|e| e.as_ref().unwrap().file_name().to_string_lossy().ends_with(".tmp"))
+            .count();
+        assert_eq!(leftovers, 0);
+        
+        // A truncated file falls back to the backup
+        fs::write(&path, "[1, 2").unwrap();
+        let loaded = read_json::<Vec<u32>>(&path).unwrap().unwrap();
+        assert_eq!(loaded.value, vec![1]);
+        assert_eq!(loaded.restored_from, Some(backup_path(&path)));
+        assert_eq!(fs::read_to_string(loaded.moved_aside.unwrap()).unwrap(), "[1, 2");
+        assert!(!path.exists());
+        
+        // Saving over the corrupt file must not replace the good backup
+        write_atomic(&path, "[3]").unwrap();
+        assert_eq!(fs::read_to_string(backup_path(&path)).unwrap(), "[1]");
+        
+        // With no usable backup either, it starts empty; both corrupt copies are kept
+        fs::write(backup_path(&path), "garbage").unwrap();
+        fs::write(&path, "garbage").unwrap();
+        let loaded = read_json::<Vec<u32>>(&path).unwrap().unwrap();
+        assert!(loaded.value.is_empty() && loaded.restored_from.is_none());
+        assert_eq!(fs::read_to_string(loaded.moved_aside.unwrap()).unwrap(), "garbage");
+        let corrupt_copies = fs::read_dir(&dir).unwrap()
+            .filter(|e| e.as_ref().unwrap().file_name().to_string_lossy().starts_with("tasks.json.corrupt-"))
+            .count();
+        assert_eq!(corrupt_copies, 2);
+        assert!(read_json::<Vec<u32>>(&dir.join("missing.json")).unwrap().is_none());
+        
+        fs::remove_dir_all(&dir).unwrap();
+    }
+    
+    #[test]
+    fn test_run_length_classification() {
+        assert_eq!(RunLength::classify(10, None), RunLength::Normal);
+        assert_eq!(RunLength::classify(LONG_RUN_WARNING_SECONDS, None), RunLength::Long);
+        assert_eq!(RunLength::classify(70, Some(100)), RunLength::Normal);
+        assert_eq!(RunLength::classify(80, Some(100)), RunLength::Long);
+        assert_eq!(RunLength::classify(100, Some(100)), RunLength::OverTimeout);
+        
+        let task = sample_task("Slow");
+        let mut app = TaskWithMe::default();
+        assert!(app.running_label(&task).is_none());
+        app.run_started.insert(task.id, Instant::now());
+        assert_eq!(app.running_label(&task).unwrap().0, "Running 0s");
+    }
+    
+    #[test]
+    fn test_task_report_markdown() {
+        let mut task = sample_task("Deploy | prod");
+        task.success_count = 1;
+        task.failure_count = 1;
+        let mut failed = sample_log(task.id, false, "error: see ```log```");
+        failed.exit_code = Some(2);
+        let ok = sample_log(task.id, true, "done");
+        let app = TaskWithMe {
+            tasks: vec![task.clone()],
+            logs: VecDeque::from([failed, ok, sample_log(Uuid::new_v4(), true, "other task")]),
+            ..Default::default()
         };
         
-        let content: Element<Message> = if filtered_logs.is_empty() {
-            container(text("No logs available").size(14))
-                .center_x()
-                .padding(40)
-                .into()
-        } else {
-            let mut list = column![].spacing(8);
-            
-            for log in filtered_logs.iter().rev().take(50) {
-                let task_title = self.tasks.iter()
-                    .find(|t| t.id == log.task_id)
-                    .map(|t| t.title.as_str())
-                    .unwrap_or("Unknown");
-                
-                let status_color = if log.success {
-                    Color::from_rgb(0.3, 0.8, 0.4)
-                } else {
-                    Color::from_rgb(0.9, 0.3, 0.3)
-                };
-                
-                let card = container(
-                    column![
-                        row![
-                            container(
-                                text(if log.success { "OK" } else { "FAIL" })
-                                    .size(14)
-                            )
-                            .padding([4, 8])
-                            .style(iced::theme::Container::Custom(Box::new(
-                                ColoredContainer(status_color)
-                            ))),
-                            text(task_title).size(14),
-                            Space::with_width(Length::Fill),
-                            text(log.timestamp.format("%b %d, %H:%M:%S").to_string())
-                                .size(12),
-                            text(format!("{}ms", log.duration_ms)).size(11),
-                        ]
-                        .align_items(alignment::Alignment::Center)
-                        .spacing(10),
-                        if !log.output.is_empty() {
-                            container(
-                                text(&log.output).size(11)
-                            )
-                            .padding([8, 12])
-                            .style(iced::theme::Container::Box)
-                        } else {
-                            container(Space::with_height(0))
-                        },
-                    ]
-                    .spacing(8)
-                )
-                .padding(12)
-                .style(iced::theme::Container::Box);
-                
-                list = list.push(card);
-            }
-            
-            scrollable(list).height(Length::Fixed(500.0)).into()
+        let report = app.task_report(&task, Local::now());
+        assert!(report.starts_with("# Task report: Deploy | prod\n"));
+        assert!(report.contains("| Command | `false` |"));
+        assert!(report.contains("- Runs: 1 ok / 1 failed"));
+        assert!(report.contains("| FAIL | 2 |"));
+        assert!(!report.contains("other task"));
+        // Newest output first, fenced so the embedded backticks don't end the block
+        assert!(report.find("done").unwrap() < report.find("error: see").unwrap());
+        assert!(report.contains("````\nerror: see ```log```\n````"));
+        
+        assert_eq!(md_cell("a|b\nc"), "a\\|b c");
+    }
+    
+    #[test]
+    fn test_logs_to_csv_quotes_output() {
+        let log = ExecutionLog {
+            id: Uuid::new_v4(),
+            task_id: Uuid::new_v4(),
+            timestamp: Local::now(),
+            success: false,
+            output: "error: \"disk\", full\nretrying".to_string(),
+            duration_ms: 42,
+            attempt: 0,
+            metadata: None,
+            metric: None,
+            stdout: String::new(),
+            stderr: String::new(),
+            exit_code: None,
         };
+        let csv = logs_to_csv(std::slice::from_ref(&log), |_| "Backup, nightly".to_string());
         
-        column![
-            text("Execution Logs").size(26),
-            Space::with_height(20),
-            container(
-                row![
-                    header,
-                    Space::with_width(Length::Fill),
-                    if task_id.is_some() {
-                        button("View All Logs")
-                            .on_press(Message::ChangeScreen(Screen::Logs(None)))
-                            .padding(8)
-                    } else {
-                        button("")
-                            .padding(0)
-                            .style(iced::theme::Button::Text)
-                    },
-                ]
-                .align_items(alignment::Alignment::Center)
-            )
-            .padding(15)
-            .style(iced::theme::Container::Box),
-            Space::with_height(12),
-            content,
-        ]
-        .into()
+        let mut lines = csv.lines();
+        assert_eq!(lines.next(), Some("timestamp,task,success,duration_ms,output"));
+        assert!(csv.ends_with(",\"Backup, nightly\",false,42,\"error: \"\"disk\"\", full\nretrying\"\n"));
     }
     
-    fn view_settings(&self) -> Element<Message> {
-        column![
-            text("Settings").size(26),
-            Space::with_height(20),
-            container(
-                column![
-                    text("General").size(18),
-                    Space::with_height(15),
-                    row![
-                        text("Refresh Interval (seconds):").size(14).width(Length::Fixed(200.0)),
-                        text_input("5", &self.refresh_input)
-                            .on_input(Message::RefreshIntervalChanged)
-                            .padding(8)
-                            .width(Length::Fixed(100.0)),
-                    ]
-                    .align_items(alignment::Alignment::Center)
-                    .spacing(10),
-                    Space::with_height(12),
-                    row![
-                        text("Max Log Entries:").size(14).width(Length::Fixed(200.0)),
-                        text_input("500", &self.max_logs_input)
-                            .on_input(Message::MaxLogsChanged)
-                            .padding(8)
-                            .width(Length::Fixed(100.0)),
-                    ]
-                    .align_items(alignment::Alignment::Center)
-                    .spacing(10),
-                ]
-            )
-            .padding(20)
-            .style(iced::theme::Container::Box),
-            Space::with_height(20),
-            container(
-                column![
-                    text("Appearance").size(18),
-                    Space::with_height(15),
-                    row![
-                        button("Light Theme")
-                            .on_press(Message::ThemeChanged(AppTheme::Light))
-                            .style(if self.config.theme == AppTheme::Light {
-                                iced::theme::Button::Primary
-                            } else {
-                                iced::theme::Button::Secondary
-                            })
-                            .padding(10),
-                        button("Dark Theme")
-                            .on_press(Message::ThemeChanged(AppTheme::Dark))
-                            .style(if self.config.theme == AppTheme::Dark {
-                                iced::theme::Button::Primary
-                            } else {
-                                iced::theme::Button::Secondary
-                            })
-                            .padding(10),
-                    ]
-                    .spacing(10),
-                ]
-            )
-            .padding(20)
-            .style(iced::theme::Container::Box),
-            Space::with_height(20),
-            button("Save Settings")
-                .on_press(Message::SaveSettings)
-                .padding(12)
-                .style(iced::theme::Button::Primary),
-        ]
-        .into()
+    #[test]
+    fn test_prepare_imported_tasks_skips_duplicates() {
+        let existing = vec![sample_task("Backup")];
+        let duplicate = existing[0].clone();
+        let new_task = sample_task("Cleanup");
+        let new_id = new_task.id;
+        
+        let (tasks, skipped) = prepare_imported_tasks(
+            vec![duplicate, new_task.clone(), new_task],
+            &existing,
+        );
+        
+        assert_eq!(skipped, 2);
+        assert_eq!(tasks.len(), 1);
+        assert_eq!(tasks[0].title, "Cleanup");
+        assert_ne!(tasks[0].id, new_id);
+    }
+    
+    #[test]
+    fn test_clone_task_resets_state() {
+        let mut original = sample_task("Backup");
+        original.is_active = true;
+        original.success_count = 7;
+        original.failure_count = 2;
+        original.last_run = Some(Local::now());
+        let mut app = TaskWithMe { tasks: vec![original.clone()], ..Default::default() };
+        
+        let _ = app.update(Message::CloneTask(original.id));
+        
+        assert_eq!(app.tasks.len(), 2);
+        let copy = &app.tasks[1];
+        assert_ne!(copy.id, original.id);
+        assert_eq!(copy.title, "Backup (copy)");
+        assert_eq!(copy.command, original.command);
+        assert!(!copy.is_active);
+        assert_eq!((copy.success_count, copy.failure_count), (0, 0));
+        assert!(copy.last_run.is_none());
+    }
+    
+    #[test]
+    fn test_scheduler_fires_regardless_of_refresh_interval() {
+        assert!(SCHEDULER_POLL <= Duration::from_secs(1));
+        
+        let mut task = sample_task("Every 10s");
+        task.schedule = Schedule::Interval(10);
+        task.is_active = true;
+        task.next_run = Some(Local::now() + chrono::Duration::milliseconds(500));
+        let mut app = TaskWithMe { tasks: vec![task], ..Default::default() };
+        app.config.refresh_interval = 300;
+        
+        assert!(app.update(Message::CheckScheduledTasks).actions().is_empty());
+        
+        // The next poll lands at most SCHEDULER_POLL after the due time
+        app.tasks[0].next_run = Some(Local::now() - chrono::Duration::milliseconds(900));
+        assert_eq!(app.update(Message::CheckScheduledTasks).actions().len(), 1);
+    }
+    
+    #[test]
+    fn test_activating_task_runs_it_unless_disabled() {
+        let task = sample_task("Backup");
+        let id = task.id;
+        let mut app = TaskWithMe { tasks: vec![task], ..Default::default() };
+        assert!(app.tasks[0].run_on_activate);
+        
+        let _ = app.update(Message::ToggleTask(id));
+        assert!(app.running_tasks.contains(&id));
+        assert!(app.tasks[0].next_run.is_some_and(|next| next > Local::now()));
+        
+        let mut task = sample_task("Quiet");
+        task.run_on_activate = false;
+        let id = task.id;
+        let mut app = TaskWithMe { tasks: vec![task], ..Default::default() };
+        let _ = app.update(Message::ToggleTask(id));
+        assert!(app.tasks[0].is_active);
+        assert!(!app.running_tasks.contains(&id));
+    }
+    
+    #[test]
+    fn test_missed_run_policies() {
+        let now = Local::now();
+        let mut task = sample_task("Hourly");
+        task.schedule = Schedule::Interval(3600);
+        task.is_active = true;
+        task.next_run = Some(now - chrono::Duration::minutes(150));
+        assert_eq!(missed_runs(&task, now), 3);
+        
+        let loaded = |policy| {
+            let mut app = TaskWithMe {
+                tasks: vec![task.clone()],
+                tasks_loaded: true,
+                config_loaded: true,
+                ..Default::default()
+            };
+            app.config.missed_run_policy = policy;
+            app
+        };
+        
+        let mut app = loaded(MissedRunPolicy::Skip);
+        assert!(app.update(Message::CheckScheduledTasks).actions().is_empty());
+        assert!(app.tasks[0].next_run.is_some_and(|next| next > now));
+        assert_eq!(app.notifications.len(), 1);
+        
+        let mut app = loaded(MissedRunPolicy::RunOnce);
+        assert_eq!(app.update(Message::CheckScheduledTasks).actions().len(), 1);
+        assert!(app.catch_up_runs.is_empty());
+        
+        let mut app = loaded(MissedRunPolicy::RunAll);
+        assert_eq!(app.update(Message::CheckScheduledTasks).actions().len(), 1);
+        assert_eq!(app.catch_up_runs.get(&task.id), Some(&2));
+    }
+    
+    #[test]
+    fn test_last_output_restored_from_logs_on_load() {
+        let task = sample_task("Backup");
+        let logs = vec![
+            sample_log(task.id, true, "older"),
+            sample_log(task.id, false, "newest"),
+            sample_log(Uuid::new_v4(), true, "other task"),
+        ];
+        
+        let mut app = TaskWithMe::default();
+        let _ = app.update(Message::TasksLoaded(Ok(Loaded::fresh(vec![task.clone()]))));
+        let _ = app.update(Message::LogsLoaded(Ok(Loaded::fresh(logs.clone()))));
+        assert_eq!(app.tasks[0].last_output, "newest");
+        
+        let mut app = TaskWithMe::default();
+        let _ = app.update(Message::LogsLoaded(Ok(Loaded::fresh(logs))));
+        let _ = app.update(Message::TasksLoaded(Ok(Loaded::fresh(vec![task]))));
+        assert_eq!(app.tasks[0].last_output, "newest");
     }
     
-    fn view_notifications(&self) -> Element<Message> {
-        if self.notifications.is_empty() {
-            return Space::with_height(0).into();
-        }
+    #[test]
+    fn test_reset_task_stats_keeps_logs() {
+        let mut task = sample_task("Backup");
+        task.success_count = 3;
+        task.failure_count = 9;
+        let id = task.id;
+        let mut app = TaskWithMe {
+            tasks: vec![task],
+            logs: VecDeque::from([sample_log(id, false, "broken"), sample_log(id, true, "fixed")]),
+            ..Default::default()
+        };
         
-        let mut list = column![].spacing(8);
+        let _ = app.update(Message::RequestResetTaskStats(id));
+        assert_eq!(app.tasks[0].failure_count, 9);
+        let _ = app.update(Message::ConfirmPendingAction);
         
-        for notif in self.notifications.iter().rev() {
-            let (label, color) = match notif.level {
-                NotificationLevel::Info => ("INFO", Color::from_rgb(0.2, 0.6, 0.9)),
-                NotificationLevel::Success => ("SUCCESS", Color::from_rgb(0.3, 0.8, 0.4)),
-                NotificationLevel::Warning => ("WARNING", Color::from_rgb(0.95, 0.7, 0.2)),
-                NotificationLevel::Error => ("ERROR", Color::from_rgb(0.9, 0.3, 0.3)),
-            };
-            
-            let card = container(
-                row![
-                    container(text(label).size(11))
-                        .padding([4, 8])
-                        .style(iced::theme::Container::Custom(Box::new(
-                            ColoredContainer(color)
-                        ))),
-                    text(&notif.message).size(13).width(Length::Fill),
-                    button("X")
-                        .on_press(Message::CloseNotification(notif.id))
-                        .padding(6)
-                        .style(iced::theme::Button::Destructive),
-                ]
-                .align_items(alignment::Alignment::Center)
-                .spacing(10)
-            )
-            .padding(12)
-            .style(iced::theme::Container::Box);
-            
-            list = list.push(card);
-        }
+        assert_eq!((app.tasks[0].success_count, app.tasks[0].failure_count), (0, 0));
+        assert_eq!(app.logs.len(), 2);
+        assert!(app.events.iter().any(|e| e.kind == AppEventKind::StatsReset));
+    }
+    
+    #[test]
+    fn test_keyboard_shortcuts() {
+        let ctrl = keyboard::Modifiers::COMMAND;
+        let key = |c: &str| keyboard::Key::Character(c.into());
         
-        container(list)
-            .width(Length::Fill)
-            .padding([0, 0, 15, 0])
-            .into()
+        assert!(matches!(global_shortcut(key("3"), ctrl), Some(Message::ChangeScreen(Screen::Logs(None)))));
+        assert!(matches!(global_shortcut(key("n"), ctrl), Some(Message::FocusNewTask)));
+        assert!(global_shortcut(key("n"), keyboard::Modifiers::empty()).is_none());
+        assert!(matches!(settings_shortcut(key("s"), ctrl), Some(Message::SaveSettings)));
     }
-}
-
-//Custom Container Style
-struct ColoredContainer(Color);
-
-impl iced::widget::container::StyleSheet for ColoredContainer {
-    type Style = Theme;
     
-    fn appearance(&self, _style: &Self::Style) -> iced::widget::container::Appearance {
-        iced::widget::container::Appearance {
-            background: Some(iced::Background::Color(self.0)),
+    #[test]
+    fn test_move_task_swaps_with_visible_neighbour() {
+        let mut hidden = sample_task("Hidden");
+        hidden.is_active = true;
+        let (first, second) = (sample_task("First"), sample_task("Second"));
+        let mut app = TaskWithMe {
+            tasks: vec![first.clone(), hidden.clone(), second.clone()],
+            filter: TaskFilter::Inactive,
             ..Default::default()
+        };
+        
+        let _ = app.update(Message::MoveTaskUp(second.id));
+        let order: Vec<Uuid> = app.tasks.iter().map(|t| t.id).collect();
+        assert_eq!(order, vec![second.id, hidden.id, first.id]);
+        
+        let _ = app.update(Message::MoveTaskUp(second.id));
+        assert_eq!(app.tasks[0].id, second.id);
+    }
+    
+    #[test]
+    fn test_activity_keeps_history_beyond_toasts() {
+        let mut app = TaskWithMe::default();
+        app.config.persist_notifications = true;
+        for n in 0..15 {
+            app.notify(format!("event {}", n), NotificationLevel::Info);
         }
+        let _ = app.update(Message::ClearNotifications);
+        
+        assert!(app.notifications.is_empty());
+        assert_eq!(app.activity.len(), 15);
+        assert!(app.has_unflushed_changes());
+        
+        let stored = vec![Notification {
+            id: Uuid::new_v4(),
+            message: "from last session".to_string(),
+            level: NotificationLevel::Success,
+            timestamp: Local::now() - chrono::Duration::days(1),
+        }];
+        let _ = app.update(Message::NotificationsLoaded(Ok(Loaded::fresh(stored))));
+        assert_eq!(app.activity.front().unwrap().message, "from last session");
+        assert_eq!(app.activity.back().unwrap().message, "event 14");
     }
-}
-
-//Storage Functions
-fn get_data_dir() -> Result<PathBuf, AppError> {
-    let dir = dirs::data_local_dir()
-        .ok_or_else(|| AppError::Config("Cannot determine data directory".to_string()))?
-        .join("task-with-me");
     
-    fs::create_dir_all(&dir)?;
-    Ok(dir)
-}
-
-async fn load_config() -> Result<Config, AppError> {
-    let path = get_data_dir()?.join("config.json");
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_exit_code_is_recorded() {
+        let run = |command: &str| {
+            let task = Task { command: command.to_string(), ..sample_task("Exit") };
+            let (_cancel_tx, cancel_rx) = tokio::sync::watch::channel(false);
+            execute_task(task, Config::default(), cancel_rx, LiveOutput::default())
+        };
+        
+        assert_eq!(run("exit 3").await.unwrap().exit_code, Some(3));
+        assert_eq!(run("definitely-not-a-command-xyz").await.unwrap().exit_code, Some(127));
+        
+        let killed = run("kill -9 $$").await.unwrap();
+        assert_eq!(killed.exit_code, None);
+        assert!(killed.stderr.contains("terminated by signal 9"));
+    }
     
-    if path.exists() {
-        let content = fs::read_to_string(&path)?;
-        Ok(serde_json::from_str(&content)?)
-    } else {
-        let config = Config::default();
-        let content = serde_json::to_string_pretty(&config)?;
-        fs::write(&path, content)?;
-        Ok(config)
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_hook_that_cannot_render_keeps_run_result() {
+        let task = Task {
+            command: "echo main".to_string(),
+            // An empty secret name never resolves
+            on_success_command: Some("echo {{secret: }}".to_string()),
+            ..sample_task("Hooked")
+        };
+        let (_cancel_tx, cancel_rx) = tokio::sync::watch::channel(false);
+        let result = execute_task(task, Config::default(), cancel_rx, LiveOutput::default()).await.unwrap();
+        
+        assert!(result.success);
+        assert_eq!(result.stdout, "main");
+        assert!(result.stderr.starts_with("--- on-success hook could not start: "));
     }
-}
-
-async fn save_config(config: Config) -> Result<(), AppError> {
-    let path = get_data_dir()?.join("config.json");
-    let content = serde_json::to_string_pretty(&config)?;
-    fs::write(&path, content)?;
-    Ok(())
-}
-
-async fn load_tasks() -> Result<Vec<Task>, AppError> {
-    let path = get_data_dir()?.join("tasks.json");
     
-    if path.exists() {
-        let content = fs::read_to_string(&path)?;
-        Ok(serde_json::from_str(&content)?)
-    } else {
-        Ok(Vec::new())
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_relative_output_file_lands_in_working_dir() {
+        let dir = std::env::temp_dir().join(format!("twm-output-{}", Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let task = Task {
+            command: "echo report".to_string(),
+            working_dir: Some(dir.clone()),
+            output_file: Some(PathBuf::from("report.txt")),
+            ..sample_task("Report")
+        };
+        assert_eq!(task.output_path(), Some(dir.join("report.txt")));
+        
+        let (_cancel_tx, cancel_rx) = tokio::sync::watch::channel(false);
+        execute_task(task, Config::default(), cancel_rx, LiveOutput::default()).await.unwrap();
+        assert_eq!(fs::read_to_string(dir.join("report.txt")).unwrap(), "report\n");
+        let _ = fs::remove_dir_all(&dir);
+        
+        // Absolute paths, and relative ones without a working directory, are used as given
+        let absolute = Task {
+            working_dir: Some(PathBuf::from("/srv")),
+            output_file: Some(PathBuf::from("/var/log/report.txt")),
+            ..sample_task("Absolute")
+        };
+        assert_eq!(absolute.output_path(), Some(PathBuf::from("/var/log/report.txt")));
+        let plain = Task { output_file: Some(PathBuf::from("report.txt")), ..sample_task("Plain") };
+        assert_eq!(plain.output_path(), Some(PathBuf::from("report.txt")));
     }
-}
-
-async fn load_logs() -> Result<Vec<ExecutionLog>, AppError> {
-    let path = get_data_dir()?.join("logs.json");
     
-    if path.exists() {
-        let content = fs::read_to_string(&path)?;
-        Ok(serde_json::from_str(&content)?)
-    } else {
-        Ok(Vec::new())
+    #[test]
+    fn test_parse_data_dir_arg() {
+        let args = |list: &[&str]| list.iter().map(|s| s.to_string()).collect::<Vec<_>>().into_iter();
+        assert_eq!(parse_data_dir_arg(args(&[])), None);
+        assert_eq!(parse_data_dir_arg(args(&["--data-dir", "/mnt/ext/tasks"])), Some(PathBuf::from("/mnt/ext/tasks")));
+        assert_eq!(parse_data_dir_arg(args(&["--verbose", "--data-dir=/mnt/ext"])), Some(PathBuf::from("/mnt/ext")));
+        assert_eq!(parse_data_dir_arg(args(&["--data-dir"])), None);
     }
-}
-
-async fn save_task(task: Task) -> Result<(), AppError> {
-    let path = get_data_dir()?.join("tasks.json");
     
-    let mut tasks: Vec<Task> = if path.exists() {
-        let content = fs::read_to_string(&path)?;
-        serde_json::from_str(&content).unwrap_or_default()
-    } else {
-        Vec::new()
-    };
+    #[test]
+    fn test_task_sort_puts_never_run_last() {
+        let now = Local::now();
+        let mut old = sample_task("beta");
+        old.last_run = Some(now - chrono::Duration::hours(2));
+        let mut recent = sample_task("Alpha");
+        recent.last_run = Some(now);
+        let never = sample_task("gamma");
+        
+        let mut app = TaskWithMe {
+            tasks: vec![never.clone(), old.clone(), recent.clone()],
+            task_sort: TaskSort::LastRun,
+            sort_descending: true,
+            ..Default::default()
+        };
+        let titles = |app: &TaskWithMe| app.filtered_tasks().iter().map(|t| t.title.clone()).collect::<Vec<_>>();
+        assert_eq!(titles(&app), ["Alpha", "beta", "gamma"]);
+        
+        app.sort_descending = false;
+        assert_eq!(titles(&app), ["beta", "Alpha", "gamma"]);
+        
+        app.task_sort = TaskSort::Title;
+        assert_eq!(titles(&app), ["Alpha", "beta", "gamma"]);
+        
+        app.task_sort = TaskSort::Manual;
+        assert_eq!(titles(&app), ["gamma", "beta", "Alpha"]);
+    }
     
-    if let Some(pos) = tasks.iter().position(|t| t.id == task.id) {
-        tasks[pos] = task;
-    } else {
-        tasks.push(task);
+    #[test]
+    fn test_duration_stats_follow_retained_logs() {
+        let task_id = Uuid::new_v4();
+        let mut app = TaskWithMe {
+            config: Config { max_logs: 3, log_to_file: false, ..Default::default() },
+            ..Default::default()
+        };
+        assert_eq!(app.avg_duration(task_id), None);
+        
+        for (ms, success) in [(100, true), (200, true), (300, true), (600, false)] {
+            app.push_log(ExecutionLog { duration_ms: ms, ..sample_log(task_id, success, "") });
+        }
+        // The 100ms run was trimmed
+        assert_eq!(app.avg_duration(task_id), Some(366));
+        assert_eq!(app.last_duration(task_id), Some(600));
+        assert!(app.is_failing(task_id));
+        
+        let cached = app.duration_stats.clone();
+        app.rebuild_duration_stats();
+        assert_eq!(app.duration_stats, cached);
+        
+        assert_eq!(format_millis(420), "420ms");
+        assert_eq!(format_millis(1500), "1.5s");
     }
     
-    let content = serde_json::to_string_pretty(&tasks)?;
-    fs::write(&path, content)?;
-    Ok(())
-}
-
-async fn delete_task(id: Uuid) -> Result<(), AppError> {
-    let path = get_data_dir()?.join("tasks.json");
+    #[test]
+    fn test_combine_output_keeps_both_streams() {
+        assert_eq!(combine_output("", ""), "");
+        assert_eq!(combine_output("done", ""), "done");
+        assert_eq!(combine_output("", "boom"), "boom");
+        assert_eq!(combine_output("progress 50%", "boom"), "progress 50%\n--- stderr ---\nboom");
+    }
     
-    if path.exists() {
-        let content = fs::read_to_string(&path)?;
-        let mut tasks: Vec<Task> = serde_json::from_str(&content)?;
-        tasks.retain(|t| t.id != id);
-        let content = serde_json::to_string_pretty(&tasks)?;
-        fs::write(&path, content)?;
+    #[test]
+    fn test_logs_without_streams_still_load() {
+        let line = format!(
+            r#"{{"id":"{}","task_id":"{}","timestamp":"2024-01-01T00:00:00+00:00","success":false,"output":"boom","duration_ms":3}}"#,
+            Uuid::new_v4(),
+            Uuid::new_v4()
+        );
+        let (logs, _) = parse_log_lines(&line);
+        assert_eq!(logs.len(), 1);
+        assert_eq!(logs[0].output, "boom");
+        assert!(logs[0].stdout.is_empty() && logs[0].stderr.is_empty());
     }
     
-    Ok(())
-}
-
-async fn save_logs(logs: Vec<ExecutionLog>) -> Result<(), AppError> {
-    let path = get_data_dir()?.join("logs.json");
-    let content = serde_json::to_string_pretty(&logs)?;
-    fs::write(&path, content)?;
-    Ok(())
-}
-
-async fn execute_task(task: Task) -> Result<ExecutionResult, AppError> {
-    let start = Instant::now();
+    #[test]
+    fn test_command_warnings() {
+        assert!(command_warnings("ls -la \"$HOME\"").is_empty());
+        assert!(command_warnings("rm -f /tmp/x.lock").is_empty());
+        
+        let warnings = command_warnings("rm -rf /");
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("recursively deletes /"));
+        
+        let warnings = command_warnings("rm -r --force $BUILD_DIR/out");
+        assert!(warnings.iter().any(|w| w.contains("$BUILD_DIR/out")));
+        assert!(warnings.iter().any(|w| w.contains("Unquoted variable $BUILD_DIR")));
+        
+        assert!(command_warnings("echo '$NOT_EXPANDED' \"${QUOTED}\"").is_empty());
+    }
     
-    let (shell, flag) = if cfg!(target_os = "windows") {
-        ("cmd", "/C")
-    } else {
-        ("sh", "-c")
-    };
+    #[test]
+    fn test_preview_masks_secret_env_values() {
+        let options = ShellOptions {
+            login: false,
+            shell: Some("bash".to_string()),
+            shell_args: Vec::new(),
+            timeout: None,
+            grace: 0,
+            working_dir: None,
+            env_vars: vec![
+                ("API_TOKEN".to_string(), "hunter2".to_string()),
+                ("REGION".to_string(), "eu".to_string()),
+            ],
+            live_output: None,
+            secrets: vec![],
+        };
+        let config = Config { command_prefix: Some("nice".to_string()), ..Default::default() };
+        
+        let preview = preview_command("echo {{secret:db}}", &options, &config, false, false);
+        assert_eq!(preview.invocation, "bash -c 'nice echo {{secret:db}}'");
+        assert_eq!(preview.env_vars, vec![
+            ("API_TOKEN".to_string(), "****".to_string()),
+            ("REGION".to_string(), "eu".to_string()),
+        ]);
+    }
     
-    let output = tokio::process::Command::new(shell)
-        .arg(flag)
-        .arg(&task.command)
-        .stdout(Stdio::piped())
-        .stderr(Stdio::piped())
-        .output()
-        .await
-        .map_err(|e| AppError::Execution(e.to_string()))?;
+    #[test]
+    fn test_dependent_task_waits_for_upstream_success() {
+        let now = Local::now();
+        let cleanup = sample_task("Cleanup");
+        let mut backup = sample_task("Backup");
+        backup.is_active = true;
+        backup.depends_on = Some(cleanup.id);
+        backup.next_run = Some(now - chrono::Duration::seconds(1));
+        backup.last_run = Some(now - chrono::Duration::hours(1));
+        
+        // Succeeded before backup's last run, failing since
+        let cleanup = Task {
+            last_success: Some(now - chrono::Duration::hours(2)),
+            failing_since: Some(now - chrono::Duration::minutes(5)),
+            ..cleanup
+        };
+        let cleanup_id = cleanup.id;
+        let mut app = TaskWithMe {
+            tasks: vec![cleanup, backup.clone()],
+            ..Default::default()
+        };
+        assert!(app.update(Message::CheckScheduledTasks).actions().is_empty());
+        
+        let ok = ExecutionResult { success: true, output: "ok".to_string(), duration_ms: 1, metadata: None, metric: None, stdout: String::new(), stderr: String::new(), exit_code: None };
+        let _ = app.update(Message::TaskExecuted(cleanup_id, Ok(ok)));
+        // Doesn't depend on the log still being around
+        app.logs.clear();
+        assert_eq!(app.update(Message::CheckScheduledTasks).actions().len(), 1);
+    }
     
-    let duration = start.elapsed();
-    let success = output.status.success();
+    #[test]
+    fn test_dependency_cycle_detection() {
+        let a = sample_task("A");
+        let mut b = sample_task("B");
+        b.depends_on = Some(a.id);
+        let mut c = sample_task("C");
+        c.depends_on = Some(b.id);
+        let tasks = vec![a.clone(), b.clone(), c.clone()];
+        
+        assert!(creates_dependency_cycle(&tasks, a.id, c.id));
+        assert!(creates_dependency_cycle(&tasks, a.id, a.id));
+        assert!(!creates_dependency_cycle(&tasks, c.id, a.id));
+    }
     
-    let output_text = if success {
-        String::from_utf8_lossy(&output.stdout).trim().to_string()
-    } else {
-        String::from_utf8_lossy(&output.stderr).trim().to_string()
-    };
+    #[test]
+    fn test_format_countdown() {
+        assert_eq!(TaskWithMe::format_countdown(45), "45s");
+        assert_eq!(TaskWithMe::format_countdown(192), "3m 12s");
+        assert_eq!(TaskWithMe::format_countdown(3 * 3600 + 5 * 60 + 9), "3h 5m");
+        assert_eq!(TaskWithMe::format_countdown(2 * 86400), "2d");
+    }
     
-    let result = ExecutionResult {
-        success,
-        output: output_text,
-        duration_ms: duration.as_millis() as u64,
-    };
+    #[test]
+    fn test_truncate_output() {
+        assert_eq!(truncate_output("hello".to_string(), None), "hello");
+        assert_eq!(truncate_output("hello".to_string(), Some(10)), "hello");
+        assert_eq!(
+            truncate_output("hello world".to_string(), Some(5)),
+            "hello\n…(truncated, 11 bytes total)"
+        );
+        // "é" is two bytes; cutting inside it must back off to the previous boundary
+        assert_eq!(
+            truncate_output("aé".to_string(), Some(2)),
+            "a\n…(truncated, 3 bytes total)"
+        );
+    }
     
-    Ok(result)
-}
-
-// Main
-fn main() -> iced::Result {
-    TaskWithMe::run(Settings {
-        window: window::Settings {
-            size: iced::Size::new(1200.0, 800.0),
-            min_size: Some(iced::Size::new(900.0, 600.0)),
-            ..Default::default()
-        },
-        default_font: Font::default(),
-        ..Settings::default()
-    })
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
     #[test]
-    fn test_task_creation() {
-        let task = Task {
-            id: Uuid::new_v4(),
-            title: "Test".to_string(),
-            command: "echo test".to_string(),
-            interval_seconds: 60,
-            is_active: false,
-            last_run: None,
-            next_run: None,
-            created_at: Local::now(),
-            success_count: 0,
-            failure_count: 0,
-            last_output: String::new(),
-        };
+    fn test_duplicate_title_policy() {
+        let mut app = TaskWithMe::default();
+        let existing = sample_task("Backup");
+        app.tasks.push(existing.clone());
+        app.title_input = "  backup ".to_string();
+        app.command_input = "echo hi".to_string();
         
-        assert_eq!(task.title, "Test");
-        assert!(!task.is_active);
+        let _ = app.update(Message::CreateTask);
+        assert_eq!(app.pending_action, Some(PendingAction::SaveDuplicateTitle("Backup".to_string())));
+        assert!(!app.saving_task);
+        
+        app.pending_action = None;
+        app.config.duplicate_titles = DuplicateTitlePolicy::Block;
+        let _ = app.update(Message::CreateTask);
+        assert!(app.pending_action.is_none());
+        assert!(!app.saving_task);
+        
+        // Renaming a task to its own title is not a clash
+        app.editing_task = Some(existing.id);
+        assert!(app.duplicate_title("BACKUP").is_none());
     }
-
+    
     #[test]
-    fn test_success_rate_calculation() {
-        let app = TaskWithMe::default();
-        let mut task = Task {
-            id: Uuid::new_v4(),
-            title: "Test".to_string(),
-            command: "test".to_string(),
-            interval_seconds: 60,
-            is_active: false,
-            last_run: None,
-            next_run: None,
-            created_at: Local::now(),
-            success_count: 7,
-            failure_count: 3,
-            last_output: String::new(),
+    fn test_decode_output() {
+        assert_eq!(decode_output("héllo\n".as_bytes()), "héllo\n");
+        // "café" in Latin-1
+        assert_eq!(decode_output(b"caf\xe9"), "café");
+        // "ok" in UTF-16LE, with and without a BOM
+        assert_eq!(decode_output(b"\xff\xfeo\0k\0"), "ok");
+        assert_eq!(decode_output(b"d\0i\0r\0"), "dir");
+        
+        let garbage: Vec<u8> = vec![0x80, 0x01, 0xC3, 0x28, 0xFF, 0x02, 0x9F, 0x00];
+        assert_eq!(decode_output(&garbage), "[binary/non-UTF-8 output, 8 bytes omitted]");
+        // A few bad bytes in mostly readable output are kept as replacement characters
+        let mostly_text = b"build finished \x81 in 3s\x07 without errors";
+        assert_eq!(decode_output(mostly_text), "build finished \u{FFFD} in 3s\u{7} without errors");
+    }
+    
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_run_shell_streams_stdout() {
+        let live = LiveOutput::default();
+        let options = ShellOptions {
+            login: false,
+            shell: None,
+            shell_args: vec![],
+            timeout: None,
+            grace: 1,
+            working_dir: None,
+            env_vars: vec![],
+            live_output: Some(live.clone()),
+            secrets: vec!["hunter2".to_string()],
         };
+        let (_cancel_tx, cancel) = tokio::sync::watch::channel(false);
+        let run = tokio::spawn(async move {
+            run_shell("echo one; sleep 1; echo two hunter2", &options, cancel).await
+        });
         
-        assert_eq!(app.success_rate(&task), 70.0);
+        tokio::time::sleep(Duration::from_millis(500)).await;
+        assert_eq!(*live.lock().unwrap(), "one\n");
         
-        task.success_count = 0;
-        task.failure_count = 0;
-        assert_eq!(app.success_rate(&task), 0.0);
+        let output = run.await.unwrap().unwrap();
+        assert_eq!(output.stdout, b"one\ntwo hunter2\n");
+        assert_eq!(*live.lock().unwrap(), "one\ntwo ****\n");
     }
     
     #[test]
-    fn test_duration_formatting() {
-        assert_eq!(TaskWithMe::format_duration(45), "45s");
-        assert_eq!(TaskWithMe::format_duration(120), "2m");
-        assert_eq!(TaskWithMe::format_duration(7200), "2h");
-        assert_eq!(TaskWithMe::format_duration(172800), "2d");
+    fn test_append_live_output_keeps_tail() {
+        let mut out = String::new();
+        let line = format!("{}\n", "x".repeat(99));
+        for _ in 0..LIVE_OUTPUT_MAX_BYTES / 100 + 5 {
+            append_live_output(&mut out, &line);
+        }
+        append_live_output(&mut out, "last\n");
+        assert!(out.len() <= LIVE_OUTPUT_MAX_BYTES);
+        assert!(out.starts_with('x'));
+        assert!(out.ends_with("last\n"));
+    }
+    
+    #[test]
+    fn test_live_output_tail_decodes_and_masks() {
+        // UTF-16LE from a Windows tool
+        assert_eq!(live_output_tail(b"o\0k\0\n\0", &[]), "ok\n");
+        let secrets = vec!["s3cr3t".to_string()];
+        assert_eq!(live_output_tail(b"token=s3cr3t\n", &secrets), "token=****\n");
+    }
+    
+    #[test]
+    fn test_runs_per_day() {
+        let today = chrono::NaiveDate::from_ymd_opt(2024, 3, 14).unwrap();
+        let at = |day: u32, success: bool| ExecutionLog {
+            timestamp: local_midnight(chrono::NaiveDate::from_ymd_opt(2024, 3, day).unwrap()) + chrono::Duration::hours(12),
+            ..sample_log(Uuid::new_v4(), success, "")
+        };
+        let logs = vec![at(1, true), at(2, true), at(14, true), at(14, false), at(14, true), at(15, false)];
+        
+        let days = runs_per_day(&logs, today, 14);
+        assert_eq!(days.len(), 14);
+        assert_eq!(days[0], DayRuns { day: chrono::NaiveDate::from_ymd_opt(2024, 3, 1).unwrap(), succeeded: 1, failed: 0 });
+        assert_eq!(days[13], DayRuns { day: today, succeeded: 2, failed: 1 });
+        assert_eq!(days[5].succeeded + days[5].failed, 0);
+        
+        assert!(runs_per_day(&[], today, 14).iter().all(|d| d.succeeded + d.failed == 0));
     }
-}
\ No newline at end of file
+}